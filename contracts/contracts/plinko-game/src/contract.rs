@@ -1,25 +1,82 @@
+use std::cmp::Ordering;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
 use cosmwasm_std::{
-    coin, entry_point, to_json_binary, BankMsg, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-    StdResult, Uint128,
+    coin, entry_point, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal,
+    Deps, DepsMut, Env, Event, HexBinary, Int128, MessageInfo, Order, Response, SignedDecimal,
+    StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 
+use cw2::{ensure_from_older_version, set_contract_version, CONTRACT};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_storage_plus::Bound;
+
 use crate::error::ContractError;
-use crate::leaderboard::{should_reset_daily, update_leaderboard};
+use crate::leaderboard::{
+    day_index, import_leaderboard, leaderboard_entries_above, leaderboard_page, period_bounds,
+    period_index, player_rank, update_leaderboard,
+};
 use crate::msg::{
-    ConfigResponse, Difficulty, ExecuteMsg, GameRecord, HistoryResponse, InstantiateMsg,
-    LeaderboardEntry as MsgLeaderboardEntry, LeaderboardResponse, LeaderboardType, QueryMsg,
-    RiskLevel, StatsResponse, UserStatsResponse,
+    AutobetSessionResponse, BetLimitsResponse, CalendarPeriod, ConfigResponse,
+    DailyLeaderboardDaysResponse, DenomResponse, DenomsResponse, Difficulty, DrandConfig,
+    ExecuteMsg, GameOddsResponse, GameRecord, HistoryResponse, InstantiateMsg, JackpotConfig,
+    JackpotResponse, LeaderboardCursor, LeaderboardEntry as MsgLeaderboardEntry, LeaderboardPeriod,
+    LeaderboardPeriodsResponse, LeaderboardResponse, LeaderboardType, MigrateMsg,
+    PayoutTableResponse, PayoutTableVersionResponse, PayoutTablesResponse, PendingBetResponse,
+    PendingBetsResponse, PeriodLeaderboardResponse, PeriodResponse, PlayerRankResponse, QueryMsg,
+    RandomnessMode, ReceiveMsg, RiskLevel, SeasonResponse, SeasonsResponse, SeedCommitmentResponse,
+    SeedInputs, StatsResponse, UserStatsResponse, VerifyGameResponse,
+};
+use crate::rng::{
+    derive_seed, drand_round_at, drand_round_time, hash_server_seed, operator_message,
+    settle_block_seed_inputs, verify_bls_signature, verify_drand_beacon, verify_record,
+    RNG_VERSION,
 };
-use crate::multipliers::{get_multipliers, get_rows};
-use crate::rng::{calculate_bucket_index, generate_ball_path};
+use plinko_fairness::{
+    expected_return, get_rows, odds, play_table, table_for_rows, validate_table, Outcome, MAX_ROWS,
+    MIN_ROWS,
+};
+
 use crate::state::{
-    CONFIG, Config, DAILY_LEADERBOARD, DAILY_PLAYER_STATS, DailyLeaderboard, DailyPlayerStats, GAME_HISTORY, GLOBAL_BEST_WINS, GLOBAL_TOTAL_WAGERED, PLAYER_GAME_COUNT, STATS, Stats, USER_STATS, UserStats
+    board_id, cw20_address, cw20_denom, load_stats, load_user_stats, risk_from_key, risk_key,
+    stored_denom, AutobetSession, BetEntropy, BetLimits, BoardScope, Config, LeaderboardEntry,
+    PayoutTable, PendingBet, PendingGame, PeriodLeaderboard, PeriodStore, Season, SeedCommitment,
+    Stats, ACTIVE_COMMITMENT, AUTOBET_SESSIONS, BET_LIMITS, CONFIG, DEFAULT_MAX_EXPOSURE,
+    DEFAULT_MAX_RTP, DEFAULT_REFUND_TIMEOUT_BLOCKS, DEFAULT_SETTLE_DELAY_BLOCKS, DENOMS,
+    GAME_HISTORY, LEGACY_BEST_WINS, LEGACY_DAILY_LEADERBOARD, LEGACY_DAILY_PLAYER_STATS,
+    LEGACY_TOTAL_WAGERED, NEXT_BET_ID, NEXT_COMMITMENT_ID, NEXT_PAYOUT_TABLE_ID, NEXT_SEASON_ID,
+    OPEN_SEASONS, PAYOUT_TABLES, PAYOUT_TABLE_HOLDS, PAYOUT_TABLE_IDS, PAYOUT_TABLE_VERSIONS,
+    PENDING_BETS, PENDING_GAMES, PLAYER_GAME_COUNT, SEASONS, SEED_COMMITMENTS, STATS, USER_STATS,
 };
 
+/// Longest client seed accepted by committed plays
+const MAX_CLIENT_SEED_LEN: usize = 64;
+
+// Version info for migration
+const CONTRACT_NAME: &str = "crates.io:plinko-game";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Most balls a single Play can drop
+const MAX_BALL_COUNT: u32 = 100;
+
+/// Drand bets use the first round published at least this long after placement
+const DRAND_MIN_LEAD_SECONDS: u64 = 10;
+
+/// Most player stats of past periods a single game removes, per kind of period
+const PLAYER_STATS_PRUNE_LIMIT: usize = 10;
+
+/// Most seasons that can be open, started or not, at once
+const MAX_OPEN_SEASONS: usize = 10;
+
+/// Pending games a single SettleGames settles by default, and at most
+const DEFAULT_SETTLE_LIMIT: u32 = 30;
+const MAX_SETTLE_LIMIT: u32 = 100;
+
 #[entry_point]
 pub fn instantiate(
     deps: DepsMut,
-    env: Env,
+    _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
@@ -29,30 +86,120 @@ pub fn instantiate(
         token_denom: msg.token_denom,
         admin: info.sender,
         funder_address: funder_addr,
+        settle_delay_blocks: msg
+            .settle_delay_blocks
+            .unwrap_or(DEFAULT_SETTLE_DELAY_BLOCKS),
+        refund_timeout_blocks: msg
+            .refund_timeout_blocks
+            .unwrap_or(DEFAULT_REFUND_TIMEOUT_BLOCKS),
+        operator_public_key: None,
+        drand: None,
+        max_rtp: msg.max_rtp.unwrap_or(DEFAULT_MAX_RTP),
+        max_exposure: msg.max_exposure.unwrap_or(DEFAULT_MAX_EXPOSURE),
+        jackpot: None,
+        leaderboard_utc_offset_minutes: 0,
     };
 
-    let stats = Stats {
-        total_games: 0,
-        total_wagered: Uint128::zero(),
-        total_won: Uint128::zero(),
-        house_balance: Uint128::zero(),
-    };
+    if config.settle_delay_blocks == 0 || config.refund_timeout_blocks == 0 {
+        return Err(ContractError::InvalidSettlementConfig {});
+    }
+    validate_max_rtp(config.max_rtp)?;
+    validate_max_exposure(config.max_exposure)?;
 
-    let daily_leaderboard = DailyLeaderboard {
-        last_reset: env.block.time.seconds(),
-        entries_best_wins: vec![],
-        entries_wagered: vec![],
-    };
+    save_builtin_payout_tables(deps.storage, &config)?;
 
+    // Other denoms start their stats and leaderboards on their first bet or funding
     CONFIG.save(deps.storage, &config)?;
-    STATS.save(deps.storage, &stats)?;
-    GLOBAL_BEST_WINS.save(deps.storage, &vec![])?;
-    GLOBAL_TOTAL_WAGERED.save(deps.storage, &vec![])?;
-    DAILY_LEADERBOARD.save(deps.storage, &daily_leaderboard)?;
+    DENOMS.save(deps.storage, &config.token_denom, &BetLimits::default())?;
+    STATS.save(deps.storage, &config.token_denom, &Stats::default())?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::new().add_attribute("action", "instantiate"))
 }
 
+/// Start from the built-in tables, held to the same checks as the admin's
+fn save_builtin_payout_tables(
+    storage: &mut dyn Storage,
+    config: &Config,
+) -> Result<(), ContractError> {
+    for rows in MIN_ROWS..=MAX_ROWS {
+        for risk_level in [RiskLevel::Low, RiskLevel::Medium, RiskLevel::High] {
+            let table =
+                table_for_rows(rows, &risk_level).ok_or(ContractError::InvalidRows { rows })?;
+            check_payout_table(config, rows, &table)?;
+            save_payout_table(storage, rows, &risk_level, table)?;
+        }
+    }
+    Ok(())
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Contracts stored before versions were recorded are on the original layout
+    let from_version = match CONTRACT.may_load(deps.storage)? {
+        Some(_) => {
+            ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?.to_string()
+        }
+        None => {
+            migrate_original_layout(deps.storage)?;
+            set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+            String::new()
+        }
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", from_version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+/// Add what the original contract did not store and move its sorted boards to the indexed map.
+/// Its stats and history stay where they are, read under the default denom until its first
+/// write under the new keys.
+fn migrate_original_layout(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    // Settings added since take their defaults
+    let config = CONFIG.load(storage)?;
+    CONFIG.save(storage, &config)?;
+
+    save_builtin_payout_tables(storage, &config)?;
+    if !DENOMS.has(storage, &config.token_denom) {
+        DENOMS.save(storage, &config.token_denom, &BetLimits::default())?;
+    }
+
+    let denom = config.token_denom.as_str();
+    for (leaderboard_type, legacy) in [
+        (LeaderboardType::BestWins, LEGACY_BEST_WINS),
+        (LeaderboardType::TotalWagered, LEGACY_TOTAL_WAGERED),
+    ] {
+        let board = board_id(BoardScope::AllTime, &leaderboard_type, denom);
+        let entries = legacy.may_load(storage)?.unwrap_or_default();
+        import_leaderboard(storage, &board, entries)?;
+        legacy.remove(storage);
+    }
+
+    // The UTC day the original contract was playing when it was migrated
+    if let Some(daily) = LEGACY_DAILY_LEADERBOARD.may_load(storage)? {
+        if daily.last_reset != 0 {
+            let day = day_index(daily.last_reset);
+            for (leaderboard_type, entries) in [
+                (LeaderboardType::BestWins, daily.entries_best_wins),
+                (LeaderboardType::TotalWagered, daily.entries_wagered),
+            ] {
+                let scope = BoardScope::Period(PeriodStore::Daily, day);
+                import_leaderboard(storage, &board_id(scope, &leaderboard_type, denom), entries)?;
+            }
+            let leaderboard = PeriodLeaderboard {
+                last_reset: daily.last_reset,
+            };
+            PeriodStore::Daily
+                .leaderboards()
+                .save(storage, (denom, day), &leaderboard)?;
+        }
+        LEGACY_DAILY_LEADERBOARD.remove(storage);
+    }
+    Ok(())
+}
+
 #[entry_point]
 pub fn execute(
     deps: DepsMut,
@@ -60,278 +207,2199 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // CW20 tokens only arrive through Receive, never as a native coin named like one
+    if let Some(coin) = info
+        .funds
+        .iter()
+        .find(|coin| cw20_address(&coin.denom).is_some())
+    {
+        return Err(ContractError::UnsupportedDenom {
+            denom: coin.denom.clone(),
+        });
+    }
+
     match msg {
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, env, info, wrapper),
         ExecuteMsg::Play {
             difficulty,
+            rows,
+            risk_level,
+            client_seed,
+            ball_count,
+        } => {
+            let rows = board_rows(difficulty, rows)?;
+            execute_play(deps, env, info, rows, risk_level, client_seed, ball_count)
+        }
+        ExecuteMsg::WithdrawHouse { amount, denom } => {
+            execute_withdraw_house(deps, info, amount, denom)
+        }
+        ExecuteMsg::FundHouse { denom } => execute_fund_house(deps, info, denom),
+        ExecuteMsg::SyncBalance { denom } => execute_sync_balance(deps, env, info, denom),
+        ExecuteMsg::PlaceBet {
+            difficulty,
+            rows,
+            risk_level,
+            randomness,
+        } => {
+            let rows = board_rows(difficulty, rows)?;
+            execute_place_bet(deps, env, info, rows, risk_level, randomness)
+        }
+        ExecuteMsg::SettleBet { bet_id, signature } => {
+            execute_settle_bet(deps, env, bet_id, signature)
+        }
+        ExecuteMsg::RefundBet { bet_id } => execute_refund_bet(deps, env, info, bet_id),
+        ExecuteMsg::UpdateSettlementConfig {
+            settle_delay_blocks,
+            refund_timeout_blocks,
+        } => {
+            execute_update_settlement_config(deps, info, settle_delay_blocks, refund_timeout_blocks)
+        }
+        ExecuteMsg::SetDrandConfig { drand } => execute_set_drand_config(deps, info, drand),
+        ExecuteMsg::StartAutobet {
+            difficulty,
+            rows,
+            risk_level,
+            bet_amount,
+            drops,
+            stop_loss,
+            take_profit,
+            client_seed,
+        } => execute_start_autobet(
+            deps,
+            env,
+            info,
+            board_rows(difficulty, rows)?,
+            risk_level,
+            bet_amount,
+            drops,
+            stop_loss,
+            take_profit,
+            client_seed,
+        ),
+        ExecuteMsg::AdvanceAutobet { player, max_drops } => {
+            execute_advance_autobet(deps, env, player, max_drops)
+        }
+        ExecuteMsg::StopAutobet {} => execute_stop_autobet(deps, info),
+        ExecuteMsg::SetPayoutTable {
+            rows,
+            risk_level,
+            multipliers,
+        } => execute_set_payout_table(deps, info, rows, risk_level, multipliers),
+        ExecuteMsg::RemovePayoutTable { rows, risk_level } => {
+            execute_remove_payout_table(deps, info, rows, risk_level)
+        }
+        ExecuteMsg::SetMaxRtp { max_rtp } => execute_set_max_rtp(deps, info, max_rtp),
+        ExecuteMsg::SetBetLimits {
+            denom,
+            difficulty,
+            rows,
+            risk_level,
+            min_bet,
+            max_bet,
+        } => {
+            let rows = board_rows(difficulty, rows)?;
+            execute_set_bet_limits(deps, info, denom, rows, risk_level, min_bet, max_bet)
+        }
+        ExecuteMsg::SetDenom {
+            denom,
+            min_bet,
+            max_bet,
+        } => execute_set_denom(deps, info, denom, min_bet, max_bet),
+        ExecuteMsg::RemoveDenom { denom } => execute_remove_denom(deps, info, denom),
+        ExecuteMsg::SetJackpot { jackpot } => execute_set_jackpot(deps, info, jackpot),
+        ExecuteMsg::SetMaxExposure { max_exposure } => {
+            execute_set_max_exposure(deps, info, max_exposure)
+        }
+        ExecuteMsg::SetLeaderboardUtcOffset { utc_offset_minutes } => {
+            execute_set_leaderboard_utc_offset(deps, info, utc_offset_minutes)
+        }
+        ExecuteMsg::SetSeason {
+            id,
+            name,
+            start,
+            end,
+        } => execute_set_season(deps, env, info, id, name, start, end),
+        ExecuteMsg::RemoveSeason { id } => execute_remove_season(deps, env, info, id),
+        ExecuteMsg::SetOperatorKey { public_key } => {
+            execute_set_operator_key(deps, info, public_key)
+        }
+        ExecuteMsg::CommitServerSeed { commitment } => {
+            execute_commit_server_seed(deps, env, info, commitment)
+        }
+        ExecuteMsg::RevealServerSeed {
+            commitment_id,
+            server_seed,
+        } => execute_reveal_server_seed(deps, env, info, commitment_id, server_seed),
+        ExecuteMsg::SettleGames {
+            commitment_id,
+            limit,
+        } => execute_settle_games(deps, env, commitment_id, limit),
+        ExecuteMsg::RefundGame {
+            commitment_id,
+            nonce,
+        } => execute_refund_game(deps, env, info, commitment_id, nonce),
+    }
+}
+
+/// CW20 tokens sent with a hook message. The tokens are handled like a native coin of their
+/// `cw20:` denom, sent by the account that sent them to this contract.
+fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let denom = cw20_denom(&info.sender);
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(&wrapper.sender)?,
+        funds: vec![coin(wrapper.amount.u128(), denom.clone())],
+    };
+
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::Play {
+            difficulty,
+            rows,
             risk_level,
-        } => execute_play(deps, env, info, difficulty, risk_level),
-        ExecuteMsg::WithdrawHouse { amount } => execute_withdraw_house(deps, info, amount),
-        ExecuteMsg::FundHouse {} => execute_fund_house(deps, info),
-        ExecuteMsg::SyncBalance {} => execute_sync_balance(deps, env, info),
+            client_seed,
+            ball_count,
+        } => {
+            let rows = board_rows(difficulty, rows)?;
+            execute_play(deps, env, info, rows, risk_level, client_seed, ball_count)
+        }
+        ReceiveMsg::FundHouse {} => execute_fund_house(deps, info, Some(denom)),
     }
 }
 
-fn execute_play(
+/// Events for what a game put into and took out of the jackpot
+fn jackpot_events(game_record: &GameRecord) -> Vec<Event> {
+    let jackpot_event = |kind: &str, amount: Uint128| {
+        Event::new(kind)
+            .add_attribute("player", game_record.player.as_str())
+            .add_attribute("nonce", game_record.nonce.to_string())
+            .add_attribute("denom", game_record.denom.as_str())
+            .add_attribute("amount", amount)
+    };
+
+    let mut events = vec![];
+    if !game_record.jackpot_contribution.is_zero() {
+        events.push(jackpot_event(
+            "jackpot_contribution",
+            game_record.jackpot_contribution,
+        ));
+    }
+    if !game_record.jackpot_win.is_zero() {
+        events.push(jackpot_event("jackpot_payout", game_record.jackpot_win));
+    }
+    events
+}
+
+/// Event describing one drop of an autobet batch
+fn ball_event(game_record: &GameRecord, bucket_index: usize) -> Event {
+    Event::new("ball")
+        .add_attribute("nonce", game_record.nonce.to_string())
+        .add_attribute("bet_amount", game_record.bet_amount)
+        .add_attribute("win_amount", game_record.win_amount)
+        .add_attribute("multiplier", game_record.multiplier.to_string())
+        .add_attribute("bucket", bucket_index.to_string())
+        .add_attribute("path", path_to_string(&game_record.path))
+}
+
+#[allow(clippy::too_many_arguments)] // One argument per field of StartAutobet
+fn execute_start_autobet(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    difficulty: Difficulty,
+    rows: u8,
     risk_level: RiskLevel,
+    bet_amount: Uint128,
+    drops: u32,
+    stop_loss: Option<Uint128>,
+    take_profit: Option<Uint128>,
+    client_seed: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    let Coin {
+        denom,
+        amount: deposit,
+    } = bet_from_funds(deps.storage, &config, &info)?;
 
-    // Get bet amount from sent funds
-    let bet_amount = info
-        .funds
-        .iter()
-        .find(|coin| coin.denom == config.token_denom)
-        .map(|coin| coin.amount)
-        .unwrap_or(Uint128::zero());
-
-    if bet_amount.is_zero() {
-        return Err(ContractError::InvalidBetAmount {});
+    if bet_amount.is_zero() || drops == 0 || deposit < bet_amount {
+        return Err(ContractError::InvalidAutobetConfig {});
+    }
+    if AUTOBET_SESSIONS.has(deps.storage, &info.sender) {
+        return Err(ContractError::AutobetSessionActive {});
     }
+    let client_seed = client_seed_or_default(&env, client_seed)?;
+
+    // Limits apply to each drop, checked here and again before every drop
+    let (table_id, table) = payout_table(deps.storage, rows, &risk_level)?;
+    let mut stats = load_stats(deps.storage, &config, &denom)?;
+    check_bet(
+        deps.storage,
+        &config,
+        &stats,
+        &denom,
+        rows,
+        &risk_level,
+        &table,
+        bet_amount,
+        1,
+    )?;
+    hold_payout_table(deps.storage, table_id, 1)?;
 
-    let mut stats = STATS.load(deps.storage)?;
+    let commitment_id = ACTIVE_COMMITMENT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoActiveCommitment {})?;
 
-    // Get player's game count for nonce
-    let player_count = PLAYER_GAME_COUNT
+    // Every drop's nonce is fixed now, before the seed is revealed
+    let first_nonce = PLAYER_GAME_COUNT
         .may_load(deps.storage, &info.sender)?
         .unwrap_or(0);
+    PLAYER_GAME_COUNT.save(deps.storage, &info.sender, &(first_nonce + drops as u64))?;
 
-    // Generate provably fair random path
-    let rows = get_rows(&difficulty);
-    let path = generate_ball_path(&env, &info, player_count, rows);
-    let bucket_index = calculate_bucket_index(&path);
+    // The deposit stays the player's until it is bet
+    stats.autobet_balance = stats.autobet_balance.checked_add(deposit)?;
+    STATS.save(deps.storage, &denom, &stats)?;
+
+    let session = AutobetSession {
+        rows,
+        denom: denom.clone(),
+        risk_level,
+        bet_amount,
+        table_id,
+        deposit,
+        balance: deposit,
+        drops_remaining: drops,
+        drops_played: 0,
+        stop_loss,
+        take_profit,
+        started_at: env.block.time.seconds(),
+        commitment_id,
+        client_seed: client_seed.clone(),
+        first_nonce,
+    };
+    AUTOBET_SESSIONS.save(deps.storage, &info.sender, &session)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "start_autobet")
+        .add_attribute("player", info.sender)
+        .add_attribute("denom", denom)
+        .add_attribute("deposit", deposit)
+        .add_attribute("bet_amount", bet_amount)
+        .add_attribute("drops", drops.to_string())
+        .add_attribute("commitment_id", commitment_id.to_string())
+        .add_attribute("nonce", first_nonce.to_string())
+        .add_attribute("client_seed", client_seed))
+}
+
+fn execute_advance_autobet(
+    deps: DepsMut,
+    env: Env,
+    player: String,
+    max_drops: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let player = deps.api.addr_validate(&player)?;
+    let mut session = AUTOBET_SESSIONS
+        .may_load(deps.storage, &player)?
+        .ok_or(ContractError::NoAutobetSession {})?;
+
+    // Every drop is settled with the committed seed, so nobody sees an outcome before it is bound
+    let server_seed = SEED_COMMITMENTS
+        .load(deps.storage, session.commitment_id)?
+        .server_seed
+        .ok_or(ContractError::CommitmentNotRevealed {
+            commitment_id: session.commitment_id,
+        })?;
+
+    let denom = session.denom.clone();
+    let rows = session.rows;
+    let table = PAYOUT_TABLE_VERSIONS
+        .load(deps.storage, session.table_id)?
+        .0;
+    let mut stats = load_stats(deps.storage, &config, &denom)?;
+
+    let batch = max_drops.unwrap_or(MAX_BALL_COUNT).clamp(1, MAX_BALL_COUNT);
+    let mut events = vec![];
+    let mut stop_reason = autobet_stop_reason(&session);
+
+    for _ in 0..batch {
+        if stop_reason.is_some() {
+            break;
+        }
+
+        // The house balance may have moved since the session started
+        let within_limits = check_bet(
+            deps.storage,
+            &config,
+            &stats,
+            &denom,
+            rows,
+            &session.risk_level,
+            &table,
+            session.bet_amount,
+            1,
+        );
+        if within_limits.is_err() {
+            stop_reason = Some("bet_limits");
+            break;
+        }
+
+        // The bet moves from the session to the house before the drop
+        session.balance = session.balance.checked_sub(session.bet_amount)?;
+        stats.autobet_balance = stats.autobet_balance.checked_sub(session.bet_amount)?;
+        stats.house_balance = stats.house_balance.checked_add(session.bet_amount)?;
+
+        let (game_record, bucket_index) = settle_game(
+            deps.storage,
+            &env,
+            &config,
+            &mut stats,
+            GameInput {
+                player: player.clone(),
+                rows,
+                risk_level: session.risk_level.clone(),
+                denom: denom.clone(),
+                bet_amount: session.bet_amount,
+                table_id: session.table_id,
+                nonce: session.first_nonce + session.drops_played as u64,
+                seed_inputs: SeedInputs::Commitment {
+                    commitment_id: session.commitment_id,
+                    server_seed: server_seed.clone(),
+                    client_seed: session.client_seed.clone(),
+                },
+            },
+        )?;
+
+        // Winnings go back into the session instead of being sent
+        let payout = game_record.total_payout()?;
+        session.balance = session.balance.checked_add(payout)?;
+        stats.autobet_balance = stats.autobet_balance.checked_add(payout)?;
+        session.drops_remaining -= 1;
+        session.drops_played += 1;
+
+        events.push(ball_event(&game_record, bucket_index));
+        events.extend(jackpot_events(&game_record));
+        stop_reason = autobet_stop_reason(&session);
+    }
 
-    // Get multiplier for this bucket
-    let multipliers = get_multipliers(&difficulty, &risk_level);
-    if bucket_index >= multipliers.len() {
-        return Err(ContractError::InvalidMultiplierIndex {});
+    let mut response = Response::new()
+        .add_events(events)
+        .add_attribute("action", "advance_autobet")
+        .add_attribute("player", player.clone())
+        .add_attribute("drops_played", session.drops_played.to_string())
+        .add_attribute("drops_remaining", session.drops_remaining.to_string())
+        .add_attribute("balance", session.balance);
+
+    match stop_reason {
+        Some(reason) => {
+            response = response.add_attribute("stop_reason", reason);
+            response = close_autobet(deps.storage, &mut stats, &player, &session, response)?;
+        }
+        None => AUTOBET_SESSIONS.save(deps.storage, &player, &session)?,
     }
 
-    let (numerator, denominator) = multipliers[bucket_index];
+    STATS.save(deps.storage, &denom, &stats)?;
+    Ok(response)
+}
 
-    // Calculate win amount: bet_amount * (numerator / denominator)
-    let win_amount = bet_amount
-        .checked_mul(Uint128::from(numerator))
-        .map_err(|_| ContractError::OverflowError {})?
-        .checked_div(Uint128::from(denominator))
-        .map_err(|_| ContractError::OverflowError {})?;
+fn execute_stop_autobet(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let session = AUTOBET_SESSIONS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or(ContractError::NoAutobetSession {})?;
+
+    // Once the seed is out the player knows every remaining drop, so the session runs to its end
+    let commitment_id = session.commitment_id;
+    if SEED_COMMITMENTS
+        .load(deps.storage, commitment_id)?
+        .server_seed
+        .is_some()
+    {
+        return Err(ContractError::CommitmentAlreadyRevealed { commitment_id });
+    }
 
-    // Calculate PnL (can be negative, but we store as Uint128 with saturating_sub)
-    let pnl = win_amount.saturating_sub(bet_amount);
+    let denom = &session.denom;
+    let mut stats = load_stats(deps.storage, &config, denom)?;
+    let response = Response::new()
+        .add_attribute("action", "stop_autobet")
+        .add_attribute("player", info.sender.clone())
+        .add_attribute("drops_played", session.drops_played.to_string());
+    let response = close_autobet(deps.storage, &mut stats, &info.sender, &session, response)?;
+    STATS.save(deps.storage, denom, &stats)?;
 
-    // Update global stats
-    stats.total_games += 1;
-    stats.total_wagered = stats
-        .total_wagered
-        .checked_add(bet_amount)
-        .map_err(|_| ContractError::OverflowError {})?;
-    stats.total_won = stats
-        .total_won
-        .checked_add(win_amount)
-        .map_err(|_| ContractError::OverflowError {})?;
+    Ok(response)
+}
+
+/// Why an autobet session has to stop before its next drop, if it does
+fn autobet_stop_reason(session: &AutobetSession) -> Option<&'static str> {
+    let loss = session.deposit.saturating_sub(session.balance);
+    let profit = session.balance.saturating_sub(session.deposit);
+
+    if session.stop_loss.is_some_and(|limit| loss >= limit) {
+        Some("stop_loss")
+    } else if session.take_profit.is_some_and(|limit| profit >= limit) {
+        Some("take_profit")
+    } else if session.drops_remaining == 0 {
+        Some("completed")
+    } else if session.balance < session.bet_amount {
+        Some("balance_exhausted")
+    } else {
+        None
+    }
+}
 
-    // Update house balance: receives bet, pays out winnings
-    let new_house_balance_after_bet = stats.house_balance.checked_add(bet_amount)?;
+/// Remove a session and return its balance to the player
+fn close_autobet(
+    storage: &mut dyn Storage,
+    stats: &mut Stats,
+    player: &Addr,
+    session: &AutobetSession,
+    response: Response,
+) -> Result<Response, ContractError> {
+    AUTOBET_SESSIONS.remove(storage, player);
+    release_payout_table(storage, session.table_id, 1)?;
+    stats.autobet_balance = stats.autobet_balance.checked_sub(session.balance)?;
 
-    if win_amount > new_house_balance_after_bet {
-        // The house cannot afford this payout. Return a specific error.
-        return Err(ContractError::InsufficientHouseBalance {});
+    let mut response = response.add_attribute("refund", session.balance);
+    if !session.balance.is_zero() {
+        response = response.add_message(send_funds(player, session.balance, &session.denom)?);
     }
 
-    stats.house_balance = new_house_balance_after_bet.checked_sub(win_amount)?;
+    Ok(response)
+}
 
-    STATS.save(deps.storage, &stats)?;
+/// Check a client seed, standing in the placing block for a missing one. The operator committed
+/// to the server seed before either was known.
+fn client_seed_or_default(env: &Env, client_seed: Option<String>) -> Result<String, ContractError> {
+    let client_seed = client_seed.unwrap_or_else(|| {
+        let tx_index = env.transaction.as_ref().map(|tx| tx.index).unwrap_or(0);
+        format!("{}:{tx_index}", env.block.height)
+    });
+    if client_seed.is_empty() || client_seed.len() > MAX_CLIENT_SEED_LEN {
+        return Err(ContractError::InvalidClientSeed {
+            max_len: MAX_CLIENT_SEED_LEN,
+        });
+    }
+    Ok(client_seed)
+}
 
-    // Update user stats
-    let mut user_stats = USER_STATS
+/// Place a bet against the active server seed commitment.
+/// The bet and its maximum payout are held by the house until the seed is revealed, so no
+/// outcome exists while the player can still back out of the transaction.
+fn execute_play(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rows: u8,
+    risk_level: RiskLevel,
+    client_seed: Option<String>,
+    ball_count: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let bet = bet_from_funds(deps.storage, &config, &info)?;
+    let (bet_amount, ball_count) = split_bet(bet.amount, ball_count)?;
+
+    let client_seed = client_seed_or_default(&env, client_seed)?;
+
+    // Balls are paid on the table in place now, whatever the admin sets before the reveal
+    let (table_id, table) = payout_table(deps.storage, rows, &risk_level)?;
+    let stats = load_stats(deps.storage, &config, &bet.denom)?;
+    check_bet(
+        deps.storage,
+        &config,
+        &stats,
+        &bet.denom,
+        rows,
+        &risk_level,
+        &table,
+        bet_amount,
+        ball_count,
+    )?;
+
+    // Reserve the best case payout so the reveal can always settle
+    let max_payout = max_payout(bet_amount, &table)?;
+
+    let commitment_id = ACTIVE_COMMITMENT
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoActiveCommitment {})?;
+
+    let nonce = PLAYER_GAME_COUNT
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or(0);
+    PLAYER_GAME_COUNT.save(deps.storage, &info.sender, &(nonce + ball_count as u64))?;
+
+    // Every ball is escrowed and reserved together, so their combined best case must fit
+    escrow_bet(
+        deps.storage,
+        &config,
+        &bet.denom,
+        bet.amount,
+        max_payout.checked_mul(Uint128::from(ball_count))?,
+    )?;
+    hold_payout_table(deps.storage, table_id, ball_count.into())?;
+
+    // Each ball is a pending game of its own, settled when the seed is revealed
+    for ball_nonce in nonce..nonce + ball_count as u64 {
+        let pending = PendingGame {
+            rows,
+            denom: bet.denom.clone(),
+            risk_level: risk_level.clone(),
+            bet_amount,
+            max_payout,
+            table_id,
+            client_seed: client_seed.clone(),
+            placed_at: env.block.time.seconds(),
+            refund_height: env.block.height + config.refund_timeout_blocks,
+        };
+        PENDING_GAMES.save(
+            deps.storage,
+            (commitment_id, &info.sender, ball_nonce),
+            &pending,
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "play")
+        .add_attribute("player", info.sender)
+        .add_attribute("denom", bet.denom)
+        .add_attribute("commitment_id", commitment_id.to_string())
+        .add_attribute("nonce", nonce.to_string())
+        .add_attribute("ball_count", ball_count.to_string())
+        .add_attribute("bet_amount", bet_amount)
+        .add_attribute("client_seed", client_seed))
+}
+
+fn execute_place_bet(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rows: u8,
+    risk_level: RiskLevel,
+    randomness: Option<RandomnessMode>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let Coin {
+        denom,
+        amount: bet_amount,
+    } = bet_from_funds(deps.storage, &config, &info)?;
+
+    // Pin the operator key now so a later rotation cannot change how this bet settles
+    let entropy = match randomness.unwrap_or(RandomnessMode::SettleBlock) {
+        RandomnessMode::SettleBlock => BetEntropy::SettleBlock,
+        RandomnessMode::OperatorSignature => BetEntropy::OperatorSignature {
+            public_key: config
+                .operator_public_key
+                .clone()
+                .ok_or(ContractError::NoOperatorKey {})?,
+        },
+        RandomnessMode::Drand => {
+            let drand = config
+                .drand
+                .as_ref()
+                .ok_or(ContractError::NoDrandConfig {})?;
+            // Leave a margin so the round cannot already be out when the bet lands on-chain
+            let round = drand_round_at(
+                drand.genesis_time,
+                drand.period_seconds,
+                env.block.time.seconds() + DRAND_MIN_LEAD_SECONDS,
+            );
+            BetEntropy::Drand {
+                round,
+                round_time: drand_round_time(drand.genesis_time, drand.period_seconds, round),
+                public_key: drand.public_key.clone(),
+            }
+        }
+    };
+
+    let (table_id, table) = payout_table(deps.storage, rows, &risk_level)?;
+    let stats = load_stats(deps.storage, &config, &denom)?;
+    check_bet(
+        deps.storage,
+        &config,
+        &stats,
+        &denom,
+        rows,
+        &risk_level,
+        &table,
+        bet_amount,
+        1,
+    )?;
+
+    // Reserve the best case payout so the bet can always be settled
+    let max_payout = max_payout(bet_amount, &table)?;
+    escrow_bet(deps.storage, &config, &denom, bet_amount, max_payout)?;
+    hold_payout_table(deps.storage, table_id, 1)?;
+
+    let nonce = PLAYER_GAME_COUNT
         .may_load(deps.storage, &info.sender)?
-        .unwrap_or(UserStats {
-            total_games: 0,
-            total_wagered: Uint128::zero(),
-            total_won: Uint128::zero(),
-            best_win_pnl: Uint128::zero(),
-            best_win_multiplier: "0.0x".to_string(),
+        .unwrap_or(0);
+    PLAYER_GAME_COUNT.save(deps.storage, &info.sender, &(nonce + 1))?;
+
+    let bet_id = NEXT_BET_ID.may_load(deps.storage)?.unwrap_or(0);
+    NEXT_BET_ID.save(deps.storage, &(bet_id + 1))?;
+
+    // The window is fixed now so later settlement config changes cannot move it. A SettleBlock
+    // bet is seeded by the block at its settle height and settles in that block only. Drand bets
+    // never expire since anyone can settle them once the beacon is out.
+    let settle_height = env.block.height + config.settle_delay_blocks;
+    let refund_height = match entropy {
+        BetEntropy::SettleBlock => Some(settle_height + 1),
+        BetEntropy::OperatorSignature { .. } => Some(settle_height + config.refund_timeout_blocks),
+        BetEntropy::Drand { .. } => None,
+    };
+
+    let pending = PendingBet {
+        player: info.sender.clone(),
+        rows,
+        denom: denom.clone(),
+        risk_level,
+        bet_amount,
+        max_payout,
+        table_id,
+        nonce,
+        placed_height: env.block.height,
+        placed_at: env.block.time.seconds(),
+        entropy,
+        settle_height,
+        refund_height,
+    };
+    PENDING_BETS.save(deps.storage, bet_id, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "place_bet")
+        .add_attribute("player", info.sender)
+        .add_attribute("bet_id", bet_id.to_string())
+        .add_attribute("denom", denom)
+        .add_attribute("nonce", nonce.to_string())
+        .add_attribute("bet_amount", bet_amount)
+        .add_attribute("settle_height", settle_height.to_string())
+        .add_attribute(
+            "refund_height",
+            refund_height
+                .map(|height| height.to_string())
+                .unwrap_or_default(),
+        ))
+}
+
+fn execute_settle_bet(
+    deps: DepsMut,
+    env: Env,
+    bet_id: u64,
+    signature: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pending = PENDING_BETS.load(deps.storage, bet_id)?;
+
+    if env.block.height < pending.settle_height {
+        return Err(ContractError::BetNotReady {
+            settle_height: pending.settle_height,
         });
+    }
+    if pending
+        .refund_height
+        .is_some_and(|refund_height| env.block.height >= refund_height)
+    {
+        return Err(ContractError::BetExpired { bet_id });
+    }
 
-    user_stats.total_games += 1;
-    user_stats.total_wagered = user_stats.total_wagered.checked_add(bet_amount)?;
-    user_stats.total_won = user_stats.total_won.checked_add(win_amount)?;
+    // Each entropy source fixes one seed per bet whoever settles it, so anyone can settle
+    let seed_inputs = match &pending.entropy {
+        BetEntropy::SettleBlock => settle_block_seed_inputs(&env, bet_id, pending.placed_height),
+        BetEntropy::OperatorSignature { public_key } => {
+            let signature = signature.ok_or(ContractError::MissingSignature { bet_id })?;
+            let message = operator_message(
+                env.contract.address.as_str(),
+                pending.player.as_str(),
+                pending.nonce,
+                bet_id,
+            );
+            let valid = verify_bls_signature(deps.api, public_key, &message, &signature)
+                .map_err(|_| ContractError::InvalidSignature { bet_id })?;
+            if !valid {
+                return Err(ContractError::InvalidSignature { bet_id });
+            }
+            SeedInputs::OperatorSignature {
+                bet_id,
+                contract: env.contract.address.clone(),
+                public_key: public_key.clone(),
+                signature,
+            }
+        }
+        BetEntropy::Drand {
+            round, public_key, ..
+        } => {
+            let signature = signature.ok_or(ContractError::MissingSignature { bet_id })?;
+            let valid = verify_drand_beacon(deps.api, public_key, *round, &signature)
+                .map_err(|_| ContractError::InvalidSignature { bet_id })?;
+            if !valid {
+                return Err(ContractError::InvalidSignature { bet_id });
+            }
+            SeedInputs::Drand {
+                bet_id,
+                round: *round,
+                public_key: public_key.clone(),
+                signature,
+            }
+        }
+    };
 
-    let multiplier_str = format!(
-        "{}.{}x",
-        numerator / denominator,
-        (numerator % denominator) * 10 / denominator
-    );
+    let denom = &pending.denom;
+    let mut stats = load_stats(deps.storage, &config, denom)?;
+    stats.reserved_balance = stats.reserved_balance.checked_sub(pending.max_payout)?;
+
+    let (game_record, bucket_index) = settle_game(
+        deps.storage,
+        &env,
+        &config,
+        &mut stats,
+        GameInput {
+            player: pending.player.clone(),
+            rows: pending.rows,
+            risk_level: pending.risk_level,
+            denom: denom.to_string(),
+            bet_amount: pending.bet_amount,
+            table_id: pending.table_id,
+            nonce: pending.nonce,
+            seed_inputs,
+        },
+    )?;
+
+    STATS.save(deps.storage, denom, &stats)?;
+    PENDING_BETS.remove(deps.storage, bet_id);
+    release_payout_table(deps.storage, pending.table_id, 1)?;
 
-    if pnl > user_stats.best_win_pnl {
-        user_stats.best_win_pnl = pnl;
-        user_stats.best_win_multiplier = multiplier_str.clone();
+    let mut messages = vec![];
+    let payout = game_record.total_payout()?;
+    if !payout.is_zero() {
+        messages.push(send_funds(&pending.player, payout, denom)?);
     }
 
-    USER_STATS.save(deps.storage, &info.sender, &user_stats)?;
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_events(jackpot_events(&game_record))
+        .add_attribute("action", "settle_bet")
+        .add_attribute("bet_id", bet_id.to_string())
+        .add_attribute("player", pending.player)
+        .add_attribute("bet_amount", game_record.bet_amount)
+        .add_attribute("win_amount", game_record.win_amount)
+        .add_attribute("pnl", game_record.pnl.to_string())
+        .add_attribute("multiplier", game_record.multiplier.to_string())
+        .add_attribute("bucket", bucket_index.to_string())
+        .add_attribute("path", path_to_string(&game_record.path)))
+}
 
-    // Update global leaderboards
-    let mut global_best_wins = GLOBAL_BEST_WINS.load(deps.storage)?;
-    update_leaderboard(
-        &mut global_best_wins,
-        info.sender.clone(),
-        user_stats.best_win_pnl,
-        Some(user_stats.best_win_multiplier.clone()),
-    );
-    GLOBAL_BEST_WINS.save(deps.storage, &global_best_wins)?;
-
-    let mut global_wagered = GLOBAL_TOTAL_WAGERED.load(deps.storage)?;
-    update_leaderboard(
-        &mut global_wagered,
-        info.sender.clone(),
-        user_stats.total_wagered,
-        None,
-    );
-    GLOBAL_TOTAL_WAGERED.save(deps.storage, &global_wagered)?;
+fn execute_refund_bet(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bet_id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pending = PENDING_BETS.load(deps.storage, bet_id)?;
 
-    // Update daily leaderboard (with reset check)
-    let mut daily = DAILY_LEADERBOARD.load(deps.storage)?;
-    let is_new_day = should_reset_daily(daily.last_reset, env.block.time.seconds());
+    if info.sender != pending.player {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    if is_new_day {
-        daily.last_reset = env.block.time.seconds();
-        daily.entries_best_wins = vec![];
-        daily.entries_wagered = vec![];
+    // Anyone can settle a Drand bet with the published beacon, so the player could otherwise
+    // cancel every bet the beacon makes a loss
+    if let BetEntropy::Drand {
+        round, round_time, ..
+    } = pending.entropy
+    {
+        if env.block.time.seconds() >= round_time {
+            return Err(ContractError::DrandRoundPublished { bet_id, round });
+        }
+    }
+    if let Some(refund_height) = pending
+        .refund_height
+        .filter(|&refund_height| env.block.height < refund_height)
+    {
+        return Err(ContractError::BetNotExpired { refund_height });
     }
 
-    // Load the player's current daily stats, or create new ones if it's their first game of the new day.
-    let mut player_daily_stats = if is_new_day {
-        // If it's a new day, do not load old stats. Start with a fresh default.
-        DailyPlayerStats::default()
-    } else {
-        // Otherwise, load existing stats for the current day.
-        DAILY_PLAYER_STATS
-            .may_load(deps.storage, &info.sender)?
-            .unwrap_or_default()
+    // Only the operator can release the signature of an OperatorSignature bet, so withholding
+    // one must never pay off: it owes the best case payout, which the reservation already covers.
+    // A SettleBlock bet nobody settled in its block just returns the bet.
+    let amount = match pending.entropy {
+        BetEntropy::OperatorSignature { .. } => pending.bet_amount.max(pending.max_payout),
+        BetEntropy::SettleBlock | BetEntropy::Drand { .. } => pending.bet_amount,
     };
 
-    // Update the player's cumulative daily wagered amount.
-    player_daily_stats.total_wagered = player_daily_stats.total_wagered.checked_add(bet_amount)?;
+    // Release the reservation and pay the player from the house balance holding the bet
+    let denom = &pending.denom;
+    let mut stats = load_stats(deps.storage, &config, denom)?;
+    stats.reserved_balance = stats.reserved_balance.checked_sub(pending.max_payout)?;
+    stats.house_balance = stats.house_balance.checked_sub(amount)?;
+    STATS.save(deps.storage, denom, &stats)?;
+
+    PENDING_BETS.remove(deps.storage, bet_id);
+    release_payout_table(deps.storage, pending.table_id, 1)?;
+
+    let msg = send_funds(&pending.player, amount, denom)?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "refund_bet")
+        .add_attribute("bet_id", bet_id.to_string())
+        .add_attribute("player", pending.player)
+        .add_attribute("amount", amount))
+}
+
+fn execute_update_settlement_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    settle_delay_blocks: Option<u64>,
+    refund_timeout_blocks: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(settle_delay_blocks) = settle_delay_blocks {
+        config.settle_delay_blocks = settle_delay_blocks;
+    }
+    if let Some(refund_timeout_blocks) = refund_timeout_blocks {
+        config.refund_timeout_blocks = refund_timeout_blocks;
+    }
 
-    // Check if the current game's PNL is their best for the day.
-    if pnl > player_daily_stats.best_win_pnl {
-        player_daily_stats.best_win_pnl = pnl;
-        player_daily_stats.best_win_multiplier = multiplier_str.clone();
+    if config.settle_delay_blocks == 0 || config.refund_timeout_blocks == 0 {
+        return Err(ContractError::InvalidSettlementConfig {});
     }
 
-    // Save the updated daily stats for the player.
-    DAILY_PLAYER_STATS.save(deps.storage, &info.sender, &player_daily_stats)?;
+    CONFIG.save(deps.storage, &config)?;
 
-    // Now, update the main daily leaderboards using the player's cumulative daily stats.
-    update_leaderboard(
-        &mut daily.entries_best_wins,
-        info.sender.clone(),
-        player_daily_stats.best_win_pnl, // Use cumulative best PNL for the day
-        Some(player_daily_stats.best_win_multiplier.clone()),
-    );
-    update_leaderboard(
-        &mut daily.entries_wagered,
-        info.sender.clone(),
-        player_daily_stats.total_wagered, // Use cumulative wagered for the day
-        None,
-    );
+    Ok(Response::new()
+        .add_attribute("action", "update_settlement_config")
+        .add_attribute(
+            "settle_delay_blocks",
+            config.settle_delay_blocks.to_string(),
+        )
+        .add_attribute(
+            "refund_timeout_blocks",
+            config.refund_timeout_blocks.to_string(),
+        ))
+}
 
-    DAILY_LEADERBOARD.save(deps.storage, &daily)?;
+fn execute_set_drand_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    drand: Option<DrandConfig>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
 
-    // Update player game count
-    PLAYER_GAME_COUNT.save(deps.storage, &info.sender, &(player_count + 1))?;
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    // Convert Vec<u8> path to Vec<bool> for storage
-    let path_bool: Vec<bool> = path.iter().map(|&b| b != 0).collect();
+    if drand
+        .as_ref()
+        .is_some_and(|drand| drand.public_key.len() != 96 || drand.period_seconds == 0)
+    {
+        return Err(ContractError::InvalidDrandConfig {});
+    }
 
-    // Save game record
-    let game_record = GameRecord {
-        player: info.sender.clone(),
-        difficulty: difficulty.clone(),
-        risk_level: risk_level.clone(),
+    // Pending bets keep the key and round they were placed with
+    config.drand = drand;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_drand_config")
+        .add_attribute(
+            "public_key",
+            config
+                .drand
+                .map(|drand| drand.public_key.to_hex())
+                .unwrap_or_default(),
+        ))
+}
+
+fn execute_set_payout_table(
+    deps: DepsMut,
+    info: MessageInfo,
+    rows: u8,
+    risk_level: RiskLevel,
+    multipliers: Vec<Decimal>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let rtp = check_payout_table(&config, rows, &multipliers)?;
+
+    // Bets already placed keep the version they were placed on
+    let table_id = save_payout_table(deps.storage, rows, &risk_level, multipliers)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_payout_table")
+        .add_attribute("rows", rows.to_string())
+        .add_attribute("risk_level", risk_key(&risk_level))
+        .add_attribute("table_id", table_id.to_string())
+        .add_attribute("rtp", rtp.to_string()))
+}
+
+fn execute_remove_payout_table(
+    deps: DepsMut,
+    info: MessageInfo,
+    rows: u8,
+    risk_level: RiskLevel,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let key = (rows, risk_key(&risk_level));
+    let (table_id, _) = payout_table(deps.storage, rows, &risk_level)?;
+    let pending = PAYOUT_TABLE_HOLDS
+        .may_load(deps.storage, table_id)?
+        .unwrap_or(0);
+    if pending > 0 {
+        return Err(ContractError::PayoutTableInUse {
+            rows,
+            risk_level: key.1.to_string(),
+            pending,
+        });
+    }
+    // The version stays behind for the games that were paid on it
+    PAYOUT_TABLES.remove(deps.storage, key);
+    PAYOUT_TABLE_IDS.remove(deps.storage, key);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_payout_table")
+        .add_attribute("rows", rows.to_string())
+        .add_attribute("risk_level", key.1))
+}
+
+fn execute_set_max_rtp(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_rtp: Decimal,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Only checked when a table is set, tables already in place stay playable
+    validate_max_rtp(max_rtp)?;
+    config.max_rtp = max_rtp;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_max_rtp")
+        .add_attribute("max_rtp", max_rtp.to_string()))
+}
+
+fn execute_set_bet_limits(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: Option<String>,
+    rows: u8,
+    risk_level: RiskLevel,
+    min_bet: Option<Uint128>,
+    max_bet: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let limits = BetLimits { min_bet, max_bet };
+    validate_bet_limits(&limits)?;
+
+    let denom = denom_or_default(&config, denom);
+    let key = (denom.as_str(), rows, risk_key(&risk_level));
+    if min_bet.is_none() && max_bet.is_none() {
+        BET_LIMITS.remove(deps.storage, key);
+    } else {
+        BET_LIMITS.save(deps.storage, key, &limits)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_bet_limits")
+        .add_attribute("denom", key.0)
+        .add_attribute("rows", rows.to_string())
+        .add_attribute("risk_level", key.2)
+        .add_attribute("min_bet", bet_attribute(min_bet))
+        .add_attribute("max_bet", bet_attribute(max_bet)))
+}
+
+fn execute_set_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    min_bet: Option<Uint128>,
+    max_bet: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let limits = BetLimits { min_bet, max_bet };
+    validate_bet_limits(&limits)?;
+    if denom.is_empty() {
+        return Err(ContractError::UnsupportedDenom { denom });
+    }
+    if let Some(token) = cw20_address(&denom) {
+        deps.api.addr_validate(token)?;
+    }
+    DENOMS.save(deps.storage, &denom, &limits)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_denom")
+        .add_attribute("denom", denom)
+        .add_attribute("min_bet", bet_attribute(min_bet))
+        .add_attribute("max_bet", bet_attribute(max_bet)))
+}
+
+fn execute_remove_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if denom == config.token_denom {
+        return Err(ContractError::CannotRemoveDefaultDenom {});
+    }
+    if !DENOMS.has(deps.storage, &denom) {
+        return Err(ContractError::UnsupportedDenom { denom });
+    }
+    DENOMS.remove(deps.storage, &denom);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_denom")
+        .add_attribute("denom", denom))
+}
+
+fn validate_bet_limits(limits: &BetLimits) -> Result<(), ContractError> {
+    let too_small = |bet: Option<Uint128>| bet.is_some_and(|bet| bet.is_zero());
+    if too_small(limits.min_bet)
+        || too_small(limits.max_bet)
+        || limits
+            .min_bet
+            .zip(limits.max_bet)
+            .is_some_and(|(min, max)| min > max)
+    {
+        return Err(ContractError::InvalidBetLimits {});
+    }
+    Ok(())
+}
+
+fn bet_attribute(bet: Option<Uint128>) -> String {
+    bet.map(|bet| bet.to_string()).unwrap_or_default()
+}
+
+/// Bet bounds of an accepted denom. The default denom is accepted even before it is listed.
+fn denom_limits(
+    storage: &dyn Storage,
+    config: &Config,
+    denom: &str,
+) -> Result<BetLimits, ContractError> {
+    match DENOMS.may_load(storage, denom)? {
+        Some(limits) => Ok(limits),
+        None if denom == config.token_denom => Ok(BetLimits::default()),
+        None => Err(ContractError::UnsupportedDenom {
+            denom: denom.to_string(),
+        }),
+    }
+}
+
+fn denom_or_default(config: &Config, denom: Option<String>) -> String {
+    denom.unwrap_or_else(|| config.token_denom.clone())
+}
+
+fn execute_set_max_exposure(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_exposure: Decimal,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    validate_max_exposure(max_exposure)?;
+    config.max_exposure = max_exposure;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_max_exposure")
+        .add_attribute("max_exposure", max_exposure.to_string()))
+}
+
+fn execute_set_leaderboard_utc_offset(
+    deps: DepsMut,
+    info: MessageInfo,
+    utc_offset_minutes: i16,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // UTC-12:00 to UTC+14:00
+    if !(-720..=840).contains(&utc_offset_minutes) {
+        return Err(ContractError::InvalidUtcOffset {});
+    }
+    config.leaderboard_utc_offset_minutes = utc_offset_minutes;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_leaderboard_utc_offset")
+        .add_attribute("utc_offset_minutes", utc_offset_minutes.to_string()))
+}
+
+fn execute_set_season(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: Option<u64>,
+    name: String,
+    start: u64,
+    end: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let now = env.block.time.seconds();
+    if name.is_empty() || start >= end || end <= now {
+        return Err(ContractError::InvalidSeason {});
+    }
+
+    let id = match id {
+        Some(id) => {
+            let season = SEASONS.load(deps.storage, id)?;
+            if season.end <= now {
+                return Err(ContractError::InvalidSeason {});
+            }
+            if season.start <= now && start != season.start {
+                return Err(ContractError::SeasonStarted { id });
+            }
+            id
+        }
+        None => {
+            let id = NEXT_SEASON_ID.may_load(deps.storage)?.unwrap_or(1);
+            NEXT_SEASON_ID.save(deps.storage, &(id + 1))?;
+            id
+        }
+    };
+
+    // Ended seasons are only dropped from the open list by the next game
+    let mut open = vec![];
+    for open_id in OPEN_SEASONS.may_load(deps.storage)?.unwrap_or_default() {
+        if open_id != id && SEASONS.load(deps.storage, open_id)?.end > now {
+            open.push(open_id);
+        }
+    }
+    open.push(id);
+    if open.len() > MAX_OPEN_SEASONS {
+        return Err(ContractError::TooManySeasons {
+            max: MAX_OPEN_SEASONS,
+        });
+    }
+
+    SEASONS.save(deps.storage, id, &Season { name, start, end })?;
+    OPEN_SEASONS.save(deps.storage, &open)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_season")
+        .add_attribute("season_id", id.to_string())
+        .add_attribute("start", start.to_string())
+        .add_attribute("end", end.to_string()))
+}
+
+fn execute_remove_season(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // A season with games keeps its leaderboards; shorten it with SetSeason instead
+    let season = SEASONS.load(deps.storage, id)?;
+    if season.start <= env.block.time.seconds() {
+        return Err(ContractError::SeasonStarted { id });
+    }
+
+    SEASONS.remove(deps.storage, id);
+    let mut open = OPEN_SEASONS.may_load(deps.storage)?.unwrap_or_default();
+    open.retain(|open_id| *open_id != id);
+    OPEN_SEASONS.save(deps.storage, &open)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_season")
+        .add_attribute("season_id", id.to_string()))
+}
+
+fn execute_set_jackpot(
+    deps: DepsMut,
+    info: MessageInfo,
+    jackpot: Option<JackpotConfig>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let jackpot = jackpot
+        .map(|jackpot| {
+            let trigger = jackpot.trigger.unwrap_or_default();
+            validate_rows(trigger.rows)?;
+            if jackpot.contribution_bps > 10_000
+                || jackpot.payout_bps == 0
+                || jackpot.payout_bps > 10_000
+                || trigger.edge_buckets == 0
+                || trigger.edge_buckets > trigger.rows / 2
+            {
+                return Err(ContractError::InvalidJackpotConfig {});
+            }
+            Ok(JackpotConfig {
+                trigger: Some(trigger),
+                ..jackpot
+            })
+        })
+        .transpose()?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "set_jackpot")
+        .add_attribute("enabled", jackpot.is_some().to_string());
+    if let Some(jackpot) = &jackpot {
+        response = response
+            .add_attribute("contribution_bps", jackpot.contribution_bps.to_string())
+            .add_attribute("payout_bps", jackpot.payout_bps.to_string());
+    }
+
+    config.jackpot = jackpot;
+    CONFIG.save(deps.storage, &config)?;
+    Ok(response)
+}
+
+fn validate_max_exposure(max_exposure: Decimal) -> Result<(), ContractError> {
+    if max_exposure.is_zero() || max_exposure > Decimal::one() {
+        return Err(ContractError::InvalidMaxExposure {});
+    }
+    Ok(())
+}
+
+/// Current bet bounds of a board in a denom: the denom's and the board's limits, capped so that
+/// a win at the table's top multiplier stays within `max_exposure` of the denom's house balance
+fn bet_limits(
+    storage: &dyn Storage,
+    config: &Config,
+    stats: &Stats,
+    denom: &str,
+    rows: u8,
+    risk_level: &RiskLevel,
+    table: &[Decimal],
+) -> Result<BetLimitsResponse, ContractError> {
+    let denom_limits = denom_limits(storage, config, denom)?;
+    let board_limits = BET_LIMITS
+        .may_load(storage, (denom, rows, risk_key(risk_level)))?
+        .unwrap_or_default();
+    let min_bet = denom_limits.min_bet.max(board_limits.min_bet);
+    let configured_max_bet = match (denom_limits.max_bet, board_limits.max_bet) {
+        (Some(denom_max), Some(board_max)) => Some(denom_max.min(board_max)),
+        (denom_max, board_max) => denom_max.or(board_max),
+    };
+    let max_multiplier = table.iter().max().copied().unwrap_or_default();
+
+    let exposure = stats.house_balance.mul_floor(config.max_exposure);
+    let exposure_max_bet = if max_multiplier.is_zero() {
+        Uint128::MAX // Nothing on the table can win
+    } else {
+        exposure
+            .checked_div_floor(max_multiplier)
+            .map_err(|_| ContractError::OverflowError {})?
+    };
+
+    Ok(BetLimitsResponse {
+        denom: denom.to_string(),
+        rows,
+        risk_level: risk_level.clone(),
+        min_bet: min_bet.unwrap_or(Uint128::one()),
+        max_bet: configured_max_bet
+            .map_or(exposure_max_bet, |max_bet| max_bet.min(exposure_max_bet)),
+        configured_max_bet,
+        exposure_max_bet,
+    })
+}
+
+/// Reject a bet of `ball_count` balls of `bet_amount` outside the current bounds of its board and
+/// denom before any outcome is drawn. All balls together must stay within the exposure cap of the
+/// table they are paid on.
+#[allow(clippy::too_many_arguments)] // The board, the denom and the bet, each in full
+fn check_bet(
+    storage: &dyn Storage,
+    config: &Config,
+    stats: &Stats,
+    denom: &str,
+    rows: u8,
+    risk_level: &RiskLevel,
+    table: &[Decimal],
+    bet_amount: Uint128,
+    ball_count: u32,
+) -> Result<(), ContractError> {
+    let limits = bet_limits(storage, config, stats, denom, rows, risk_level, table)?;
+    if bet_amount < limits.min_bet {
+        return Err(ContractError::BetBelowMinimum {
+            min_bet: limits.min_bet,
+        });
+    }
+    // bet * balls <= exposure_max_bet, as a bound on each ball
+    let exposure_max_bet = limits.exposure_max_bet / Uint128::from(ball_count);
+    let max_bet = limits.max_bet.min(exposure_max_bet);
+    if bet_amount > max_bet {
+        return Err(ContractError::BetAboveMaximum { max_bet });
+    }
+    Ok(())
+}
+
+fn validate_max_rtp(max_rtp: Decimal) -> Result<(), ContractError> {
+    if max_rtp.is_zero() || max_rtp > Decimal::one() {
+        return Err(ContractError::InvalidMaxRtp {});
+    }
+    Ok(())
+}
+
+/// Check a table's board size, shape and RTP, returning the RTP
+fn check_payout_table(
+    config: &Config,
+    rows: u8,
+    table: &[Decimal],
+) -> Result<Decimal, ContractError> {
+    validate_rows(rows)?;
+    validate_table(rows, table)?;
+    let rtp = table_rtp(table)?;
+    if rtp > config.max_rtp {
+        return Err(ContractError::RtpAboveCeiling {
+            rtp,
+            max_rtp: config.max_rtp,
+        });
+    }
+    Ok(rtp)
+}
+
+fn validate_rows(rows: u8) -> Result<(), ContractError> {
+    if !(MIN_ROWS..=MAX_ROWS).contains(&rows) {
+        return Err(ContractError::InvalidRows { rows });
+    }
+    Ok(())
+}
+
+/// Rows of the board a bet is placed on, given as a difficulty or a row count
+fn board_rows(difficulty: Option<Difficulty>, rows: Option<u8>) -> Result<u8, ContractError> {
+    let rows = match (difficulty, rows) {
+        (Some(difficulty), None) => get_rows(&difficulty),
+        (None, Some(rows)) => rows,
+        (Some(difficulty), Some(rows)) if get_rows(&difficulty) == rows => rows,
+        _ => return Err(ContractError::InvalidBoard {}),
+    };
+    validate_rows(rows)?;
+    Ok(rows)
+}
+
+fn table_rtp(table: &[Decimal]) -> Result<Decimal, ContractError> {
+    expected_return(table).ok_or(ContractError::OverflowError {})
+}
+
+/// Put a payout table in place for a board size and risk level under a new version id
+fn save_payout_table(
+    storage: &mut dyn Storage,
+    rows: u8,
+    risk_level: &RiskLevel,
+    table: Vec<Decimal>,
+) -> StdResult<u64> {
+    let key = (rows, risk_key(risk_level));
+    let table_id = NEXT_PAYOUT_TABLE_ID.may_load(storage)?.unwrap_or(1);
+    NEXT_PAYOUT_TABLE_ID.save(storage, &(table_id + 1))?;
+
+    let table = PayoutTable(table);
+    PAYOUT_TABLE_VERSIONS.save(storage, table_id, &table)?;
+    PAYOUT_TABLE_IDS.save(storage, key, &table_id)?;
+    PAYOUT_TABLES.save(storage, key, &table)?;
+    Ok(table_id)
+}
+
+/// Payout table currently configured for a board size and risk level, with its version id
+fn payout_table(
+    storage: &dyn Storage,
+    rows: u8,
+    risk_level: &RiskLevel,
+) -> Result<(u64, Vec<Decimal>), ContractError> {
+    let key = (rows, risk_key(risk_level));
+    let not_found = || ContractError::PayoutTableNotFound {
+        rows,
+        risk_level: key.1.to_string(),
+    };
+    let table = PAYOUT_TABLES
+        .may_load(storage, key)?
+        .ok_or_else(not_found)?;
+    let table_id = PAYOUT_TABLE_IDS
+        .may_load(storage, key)?
+        .ok_or_else(not_found)?;
+    Ok((table_id, table.0))
+}
+
+/// Count `count` more unsettled games paid on a table version
+fn hold_payout_table(storage: &mut dyn Storage, table_id: u64, count: u64) -> StdResult<()> {
+    PAYOUT_TABLE_HOLDS.update(storage, table_id, |holds| {
+        Ok::<_, StdError>(holds.unwrap_or(0) + count)
+    })?;
+    Ok(())
+}
+
+/// Count `count` fewer unsettled games paid on a table version
+fn release_payout_table(storage: &mut dyn Storage, table_id: u64, count: u64) -> StdResult<()> {
+    let holds = PAYOUT_TABLE_HOLDS
+        .may_load(storage, table_id)?
+        .unwrap_or(0)
+        .saturating_sub(count);
+    if holds == 0 {
+        PAYOUT_TABLE_HOLDS.remove(storage, table_id);
+    } else {
+        PAYOUT_TABLE_HOLDS.save(storage, table_id, &holds)?;
+    }
+    Ok(())
+}
+
+fn execute_set_operator_key(
+    deps: DepsMut,
+    info: MessageInfo,
+    public_key: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if public_key.as_ref().is_some_and(|key| key.len() != 96) {
+        return Err(ContractError::InvalidOperatorKey {});
+    }
+
+    // Pending bets keep the key they were placed with
+    config.operator_public_key = public_key;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_operator_key")
+        .add_attribute(
+            "public_key",
+            config
+                .operator_public_key
+                .map(|key| key.to_base64())
+                .unwrap_or_default(),
+        ))
+}
+
+fn execute_commit_server_seed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    commitment: HexBinary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if commitment.len() != 32 {
+        return Err(ContractError::InvalidCommitment {});
+    }
+
+    let id = NEXT_COMMITMENT_ID.may_load(deps.storage)?.unwrap_or(0);
+    NEXT_COMMITMENT_ID.save(deps.storage, &(id + 1))?;
+
+    let seed_commitment = SeedCommitment {
+        id,
+        commitment: commitment.clone(),
+        server_seed: None,
+        committed_at: env.block.time.seconds(),
+        revealed_at: None,
+    };
+    SEED_COMMITMENTS.save(deps.storage, id, &seed_commitment)?;
+
+    // New bets go against the latest commitment; older ones wait for their own reveal
+    ACTIVE_COMMITMENT.save(deps.storage, &id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "commit_server_seed")
+        .add_attribute("commitment_id", id.to_string())
+        .add_attribute("commitment", commitment.to_hex()))
+}
+
+fn execute_reveal_server_seed(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    commitment_id: u64,
+    server_seed: HexBinary,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    if info.sender != config.admin {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut seed_commitment = SEED_COMMITMENTS.load(deps.storage, commitment_id)?;
+
+    if seed_commitment.server_seed.is_some() {
+        return Err(ContractError::CommitmentAlreadyRevealed { commitment_id });
+    }
+
+    if hash_server_seed(&server_seed) != seed_commitment.commitment.as_slice() {
+        return Err(ContractError::InvalidServerSeed { commitment_id });
+    }
+
+    seed_commitment.server_seed = Some(server_seed.clone());
+    seed_commitment.revealed_at = Some(env.block.time.seconds());
+    SEED_COMMITMENTS.save(deps.storage, commitment_id, &seed_commitment)?;
+
+    // A revealed seed must never be used for new bets
+    if ACTIVE_COMMITMENT.may_load(deps.storage)? == Some(commitment_id) {
+        ACTIVE_COMMITMENT.remove(deps.storage);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "reveal_server_seed")
+        .add_attribute("commitment_id", commitment_id.to_string())
+        .add_attribute("server_seed", server_seed.to_hex()))
+}
+
+/// Settle up to `limit` pending games of a revealed commitment (anyone).
+/// Settled games are removed, so repeated calls page through the rest.
+fn execute_settle_games(
+    deps: DepsMut,
+    env: Env,
+    commitment_id: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let server_seed = SEED_COMMITMENTS
+        .load(deps.storage, commitment_id)?
+        .server_seed
+        .ok_or(ContractError::CommitmentNotRevealed { commitment_id })?;
+
+    let limit = limit
+        .unwrap_or(DEFAULT_SETTLE_LIMIT)
+        .clamp(1, MAX_SETTLE_LIMIT) as usize;
+    let pending_games = PENDING_GAMES
+        .sub_prefix(commitment_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    let settled = pending_games.len();
+
+    // Stats of every denom settled here, saved once all games are settled
+    let mut stats_by_denom: BTreeMap<String, Stats> = BTreeMap::new();
+    // Winnings of each player, paid out in one transfer per denom
+    let mut payouts: BTreeMap<(Addr, String), Uint128> = BTreeMap::new();
+    let mut events = vec![];
+
+    for ((player, nonce), pending) in pending_games {
+        let denom = pending.denom.clone();
+        let stats = match stats_by_denom.entry(denom.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(load_stats(deps.storage, &config, &denom)?),
+        };
+        stats.reserved_balance = stats.reserved_balance.checked_sub(pending.max_payout)?;
+
+        let (game_record, bucket_index) = settle_game(
+            deps.storage,
+            &env,
+            &config,
+            stats,
+            GameInput {
+                player: player.clone(),
+                rows: pending.rows,
+                risk_level: pending.risk_level,
+                denom: denom.clone(),
+                bet_amount: pending.bet_amount,
+                table_id: pending.table_id,
+                nonce,
+                seed_inputs: SeedInputs::Commitment {
+                    commitment_id,
+                    server_seed: server_seed.clone(),
+                    client_seed: pending.client_seed,
+                },
+            },
+        )?;
+
+        PENDING_GAMES.remove(deps.storage, (commitment_id, &player, nonce));
+        release_payout_table(deps.storage, pending.table_id, 1)?;
+
+        let payout = payouts.entry((player.clone(), denom.clone())).or_default();
+        *payout = payout.checked_add(game_record.total_payout()?)?;
+
+        events.extend(jackpot_events(&game_record));
+        events.push(
+            Event::new("settle_game")
+                .add_attribute("player", player)
+                .add_attribute("nonce", nonce.to_string())
+                .add_attribute("denom", denom)
+                .add_attribute("bet_amount", game_record.bet_amount)
+                .add_attribute("win_amount", game_record.win_amount)
+                .add_attribute("multiplier", game_record.multiplier.to_string())
+                .add_attribute("bucket", bucket_index.to_string())
+                .add_attribute("path", path_to_string(&game_record.path)),
+        );
+    }
+
+    for (denom, stats) in &stats_by_denom {
+        STATS.save(deps.storage, denom, stats)?;
+    }
+
+    let mut messages = vec![];
+    for ((player, denom), payout) in payouts {
+        if !payout.is_zero() {
+            messages.push(send_funds(&player, payout, &denom)?);
+        }
+    }
+
+    let remaining = !PENDING_GAMES
+        .sub_prefix(commitment_id)
+        .is_empty(deps.storage);
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_events(events)
+        .add_attribute("action", "settle_games")
+        .add_attribute("commitment_id", commitment_id.to_string())
+        .add_attribute("settled", settled.to_string())
+        .add_attribute("remaining", remaining.to_string()))
+}
+
+/// Return a pending game whose commitment was not revealed in time (player only).
+/// Once the seed is revealed the game can only be settled.
+fn execute_refund_game(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    commitment_id: u64,
+    nonce: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pending = PENDING_GAMES.load(deps.storage, (commitment_id, &info.sender, nonce))?;
+
+    if SEED_COMMITMENTS
+        .load(deps.storage, commitment_id)?
+        .server_seed
+        .is_some()
+    {
+        return Err(ContractError::CommitmentAlreadyRevealed { commitment_id });
+    }
+    if env.block.height < pending.refund_height {
+        return Err(ContractError::BetNotExpired {
+            refund_height: pending.refund_height,
+        });
+    }
+
+    // Release the reservation and hand the escrowed bet back
+    let denom = &pending.denom;
+    let mut stats = load_stats(deps.storage, &config, denom)?;
+    stats.reserved_balance = stats.reserved_balance.checked_sub(pending.max_payout)?;
+    stats.house_balance = stats.house_balance.checked_sub(pending.bet_amount)?;
+    STATS.save(deps.storage, denom, &stats)?;
+
+    PENDING_GAMES.remove(deps.storage, (commitment_id, &info.sender, nonce));
+    release_payout_table(deps.storage, pending.table_id, 1)?;
+
+    let msg = send_funds(&info.sender, pending.bet_amount, denom)?;
+
+    Ok(Response::new()
+        .add_message(msg)
+        .add_attribute("action", "refund_game")
+        .add_attribute("commitment_id", commitment_id.to_string())
+        .add_attribute("player", info.sender)
+        .add_attribute("nonce", nonce.to_string())
+        .add_attribute("amount", pending.bet_amount))
+}
+
+/// Everything needed to derive and record a game
+struct GameInput {
+    player: Addr,
+    rows: u8,
+    risk_level: RiskLevel,
+    denom: String,
+    bet_amount: Uint128,
+    table_id: u64, // Payout table version fixed when the bet was placed
+    nonce: u64,
+    seed_inputs: SeedInputs,
+}
+
+/// Seasons running at `now`. Ended seasons are dropped from the open list.
+fn active_seasons(storage: &mut dyn Storage, now: u64) -> StdResult<Vec<u64>> {
+    let open = OPEN_SEASONS.may_load(storage)?.unwrap_or_default();
+    let mut still_open = vec![];
+    let mut active = vec![];
+    for &id in &open {
+        let season = SEASONS.load(storage, id)?;
+        if season.end > now {
+            still_open.push(id);
+        }
+        if season.is_active(now) {
+            active.push(id);
+        }
+    }
+    if still_open.len() < open.len() {
+        OPEN_SEASONS.save(storage, &still_open)?;
+    }
+    Ok(active)
+}
+
+/// Remove up to PLAYER_STATS_PRUNE_LIMIT player stats of periods before `index`, oldest first.
+/// Days then prune the original contract's daily stats, which were not keyed by day.
+fn prune_player_stats(
+    storage: &mut dyn Storage,
+    config: &Config,
+    store: PeriodStore,
+    denom: &str,
+    index: u64,
+) -> StdResult<()> {
+    // Keys are ordered by period, and no player sorts before the empty address
+    let first_player = Addr::unchecked("");
+    let past: Vec<(u64, Addr)> = store
+        .player_stats()
+        .sub_prefix(denom)
+        .keys(
+            storage,
+            None,
+            Some(Bound::exclusive((index, &first_player))),
+            Order::Ascending,
+        )
+        .take(PLAYER_STATS_PRUNE_LIMIT)
+        .collect::<StdResult<_>>()?;
+    for (past_index, player) in &past {
+        store
+            .player_stats()
+            .remove(storage, (denom, *past_index, player));
+    }
+
+    if store != PeriodStore::Daily {
+        return Ok(());
+    }
+    if denom == config.token_denom {
+        let limit = PLAYER_STATS_PRUNE_LIMIT - past.len();
+        let legacy: Vec<Addr> = LEGACY_DAILY_PLAYER_STATS
+            .keys(storage, None, None, Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<_>>()?;
+        for player in &legacy {
+            LEGACY_DAILY_PLAYER_STATS.remove(storage, player);
+        }
+    }
+    Ok(())
+}
+
+/// Drop the ball and record the game in its denom's stats and leaderboards, and in history.
+/// The caller must already have credited the bet to the house balance and saves `stats` afterwards.
+fn settle_game(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    stats: &mut Stats,
+    game: GameInput,
+) -> Result<(GameRecord, usize), ContractError> {
+    let bet_amount = game.bet_amount;
+
+    // Generate provably fair random path
+    let seed = derive_seed(&game.seed_inputs, game.player.as_str(), game.nonce);
+    let table = PAYOUT_TABLE_VERSIONS.load(storage, game.table_id)?.0;
+    let Outcome {
+        path,
+        bucket: bucket_index,
+        multiplier,
+        win_amount,
+    } = play_table(seed, RNG_VERSION, &table, bet_amount.u128())?;
+    let win_amount = Uint128::new(win_amount);
+
+    let pnl = net_pnl(win_amount, bet_amount)?;
+    let profit = win_amount.saturating_sub(bet_amount); // Best wins only count games in profit
+    let loss = bet_amount.saturating_sub(win_amount);
+
+    // Update global stats
+    stats.total_games += 1;
+    stats.total_wagered = stats
+        .total_wagered
+        .checked_add(bet_amount)
+        .map_err(|_| ContractError::OverflowError {})?;
+    stats.total_won = stats
+        .total_won
+        .checked_add(win_amount)
+        .map_err(|_| ContractError::OverflowError {})?;
+    match win_amount.cmp(&bet_amount) {
+        Ordering::Greater => stats.wins += 1,
+        Ordering::Less => stats.losses += 1,
+        Ordering::Equal => {}
+    }
+    stats.biggest_loss = stats.biggest_loss.max(loss);
+
+    // Winnings can only come out of the part of the house balance not reserved for pending games
+    if win_amount > stats.house_balance.saturating_sub(stats.reserved_balance) {
+        // The house cannot afford this payout. Return a specific error.
+        return Err(ContractError::InsufficientHouseBalance {});
+    }
+
+    stats.house_balance = stats.house_balance.checked_sub(win_amount)?;
+
+    // A slice of the bet feeds the jackpot, as far as the house can spare it after the win
+    let (jackpot_contribution, jackpot_win) = match &config.jackpot {
+        Some(jackpot) => {
+            let contribution = bet_amount
+                .mul_floor(Decimal::bps(jackpot.contribution_bps.into()))
+                .min(stats.house_balance.saturating_sub(stats.reserved_balance));
+            stats.house_balance = stats.house_balance.checked_sub(contribution)?;
+            stats.jackpot_balance = stats.jackpot_balance.checked_add(contribution)?;
+
+            let hit = jackpot
+                .trigger
+                .as_ref()
+                .is_some_and(|trigger| trigger.hits(game.rows, &game.risk_level, bucket_index));
+            let jackpot_win = if hit {
+                stats
+                    .jackpot_balance
+                    .mul_floor(Decimal::bps(jackpot.payout_bps.into()))
+            } else {
+                Uint128::zero()
+            };
+            stats.jackpot_balance = stats.jackpot_balance.checked_sub(jackpot_win)?;
+            (contribution, jackpot_win)
+        }
+        None => (Uint128::zero(), Uint128::zero()),
+    };
+
+    // Update user stats
+    let denom = game.denom.as_str();
+    let mut user_stats = load_user_stats(storage, config, denom, &game.player)?;
+
+    user_stats.total_games += 1;
+    user_stats.total_wagered = user_stats.total_wagered.checked_add(bet_amount)?;
+    user_stats.total_won = user_stats.total_won.checked_add(win_amount)?;
+    match win_amount.cmp(&bet_amount) {
+        Ordering::Greater => user_stats.wins += 1,
+        Ordering::Less => user_stats.losses += 1,
+        Ordering::Equal => {}
+    }
+    user_stats.biggest_loss = user_stats.biggest_loss.max(loss);
+
+    if profit > user_stats.best_win_pnl {
+        user_stats.best_win_pnl = profit;
+        user_stats.best_win_multiplier = multiplier;
+        user_stats.best_win_rows = game.rows;
+    }
+
+    USER_STATS.save(storage, (denom, &game.player), &user_stats)?;
+
+    // Update global leaderboards
+    for (leaderboard_type, value, best_win) in [
+        (
+            LeaderboardType::BestWins,
+            Int128::try_from(user_stats.best_win_pnl)?,
+            Some((user_stats.best_win_multiplier, user_stats.best_win_rows)),
+        ),
+        (
+            LeaderboardType::TotalWagered,
+            Int128::try_from(user_stats.total_wagered)?,
+            None,
+        ),
+        (
+            LeaderboardType::NetProfit,
+            net_pnl(user_stats.total_won, user_stats.total_wagered)?,
+            None,
+        ),
+    ] {
+        let board = board_id(BoardScope::AllTime, &leaderboard_type, denom);
+        update_leaderboard(storage, &board, &game.player, value, best_win)?;
+    }
+
+    // Update the daily, weekly, monthly and season leaderboards
+    let now = env.block.time.seconds();
+    let mut periods: Vec<(PeriodStore, u64)> = [
+        CalendarPeriod::Daily,
+        CalendarPeriod::Weekly,
+        CalendarPeriod::Monthly,
+    ]
+    .into_iter()
+    .map(|period| {
+        let index = period_index(period, now, config.leaderboard_utc_offset_minutes);
+        (PeriodStore::from(period), index)
+    })
+    .collect();
+    for id in active_seasons(storage, now)? {
+        periods.push((PeriodStore::Season, id));
+    }
+
+    for (store, index) in periods {
+        // The period is listed from its first game
+        if !store.leaderboards().has(storage, (denom, index)) {
+            let leaderboard = PeriodLeaderboard { last_reset: now };
+            store
+                .leaderboards()
+                .save(storage, (denom, index), &leaderboard)?;
+        }
+
+        // Load the player's stats for the period, empty on their first game in it.
+        let mut player_stats = store
+            .player_stats()
+            .may_load(storage, (denom, index, &game.player))?
+            .unwrap_or_default();
+
+        // Update the player's cumulative wagered and won amounts.
+        player_stats.total_wagered = player_stats.total_wagered.checked_add(bet_amount)?;
+        player_stats.total_won = player_stats.total_won.checked_add(win_amount)?;
+
+        // Check if the current game's PNL is their best for the period.
+        if profit > player_stats.best_win_pnl {
+            player_stats.best_win_pnl = profit;
+            player_stats.best_win_multiplier = multiplier;
+            player_stats.best_win_rows = game.rows;
+        }
+
+        store
+            .player_stats()
+            .save(storage, (denom, index, &game.player), &player_stats)?;
+        if store != PeriodStore::Season {
+            prune_player_stats(storage, config, store, denom, index)?;
+        }
+
+        // Now, update the period's leaderboards using the player's cumulative stats.
+        for (leaderboard_type, value, best_win) in [
+            (
+                LeaderboardType::BestWins,
+                Int128::try_from(player_stats.best_win_pnl)?,
+                Some((player_stats.best_win_multiplier, player_stats.best_win_rows)),
+            ),
+            (
+                LeaderboardType::TotalWagered,
+                Int128::try_from(player_stats.total_wagered)?,
+                None,
+            ),
+            (
+                LeaderboardType::NetProfit,
+                net_pnl(player_stats.total_won, player_stats.total_wagered)?,
+                None,
+            ),
+        ] {
+            let board = board_id(BoardScope::Period(store, index), &leaderboard_type, denom);
+            update_leaderboard(storage, &board, &game.player, value, best_win)?;
+        }
+    }
+
+    // Convert Vec<u8> path to Vec<bool> for storage
+    let path_bool: Vec<bool> = path.iter().map(|&b| b != 0).collect();
+
+    // Save game record
+    let game_record = GameRecord {
+        player: game.player.clone(),
+        difficulty: Difficulty::from_rows(game.rows),
+        rows: game.rows,
+        risk_level: game.risk_level,
+        denom: game.denom,
         bet_amount,
-        multiplier: multiplier_str.clone(),
+        multiplier,
         win_amount,
         pnl,
         timestamp: env.block.time.seconds(),
-        path: path_bool.clone(),
+        path: path_bool,
+        nonce: game.nonce,
+        rng_version: RNG_VERSION,
+        seed_inputs: Some(game.seed_inputs),
+        jackpot_contribution,
+        jackpot_win,
+        table_id: Some(game.table_id),
     };
 
-    GAME_HISTORY.save(deps.storage, (&info.sender, player_count), &game_record)?;
+    GAME_HISTORY.save(storage, (&game.player, game.nonce), &game_record)?;
 
-    // Create bank message to send winnings
-    let mut messages = vec![];
-    if !win_amount.is_zero() {
-        messages.push(BankMsg::Send {
-            to_address: info.sender.to_string(),
-            amount: vec![coin(win_amount.u128(), config.token_denom)],
+    Ok((game_record, bucket_index))
+}
+
+/// Signed profit of winnings against the amount wagered
+fn net_pnl(won: Uint128, wagered: Uint128) -> StdResult<Int128> {
+    Ok(Int128::try_from(won)?.checked_sub(Int128::try_from(wagered)?)?)
+}
+
+/// Share of the amount wagered paid back, zero before anything is wagered
+fn realized_rtp(won: Uint128, wagered: Uint128) -> Decimal {
+    Decimal::checked_from_ratio(won, wagered).unwrap_or_default()
+}
+
+/// Get the bet from sent funds: a single coin of an accepted denom
+fn bet_from_funds(
+    storage: &dyn Storage,
+    config: &Config,
+    info: &MessageInfo,
+) -> Result<Coin, ContractError> {
+    let bet = match info.funds.as_slice() {
+        [] => return Err(ContractError::InvalidBetAmount {}),
+        [bet] => bet.clone(),
+        _ => return Err(ContractError::MultipleDenoms {}),
+    };
+
+    if bet.amount.is_zero() {
+        return Err(ContractError::InvalidBetAmount {});
+    }
+    denom_limits(storage, config, &bet.denom)?;
+
+    Ok(bet)
+}
+
+/// Split the funds of a Play evenly between its balls
+fn split_bet(funds: Uint128, ball_count: Option<u32>) -> Result<(Uint128, u32), ContractError> {
+    let ball_count = ball_count.unwrap_or(1);
+    if ball_count == 0 || ball_count > MAX_BALL_COUNT {
+        return Err(ContractError::InvalidBallCount {
+            max: MAX_BALL_COUNT,
         });
     }
 
-    let path_str: String = path_bool
+    // Every ball must carry the same bet
+    let balls = Uint128::from(ball_count);
+    if !(funds % balls).is_zero() {
+        return Err(ContractError::InvalidBetAmount {});
+    }
+
+    Ok((funds / balls, ball_count))
+}
+
+/// Move a bet into its denom's house balance and reserve its maximum payout
+fn escrow_bet(
+    storage: &mut dyn Storage,
+    config: &Config,
+    denom: &str,
+    bet_amount: Uint128,
+    max_payout: Uint128,
+) -> Result<(), ContractError> {
+    let mut stats = load_stats(storage, config, denom)?;
+    stats.house_balance = stats.house_balance.checked_add(bet_amount)?;
+    stats.reserved_balance = stats.reserved_balance.checked_add(max_payout)?;
+
+    if stats.reserved_balance > stats.house_balance {
+        return Err(ContractError::InsufficientHouseBalance {});
+    }
+
+    STATS.save(storage, denom, &stats)?;
+    Ok(())
+}
+
+/// Largest amount a bet can win on the given table
+fn max_payout(bet_amount: Uint128, table: &[Decimal]) -> Result<Uint128, ContractError> {
+    let max_multiplier = table
         .iter()
-        .map(|&b| if b { '1' } else { '0' })
-        .collect();
+        .max()
+        .copied()
+        .ok_or(ContractError::InvalidMultiplierIndex {})?;
 
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("action", "play")
-        .add_attribute("player", info.sender)
-        .add_attribute("bet_amount", bet_amount)
-        .add_attribute("win_amount", win_amount)
-        .add_attribute("pnl", pnl)
-        .add_attribute("multiplier", multiplier_str)
-        .add_attribute("bucket", bucket_index.to_string())
-        .add_attribute("path", path_str))
+    bet_amount
+        .checked_mul_floor(max_multiplier)
+        .map_err(|_| ContractError::OverflowError {})
+}
+
+/// Pay out an amount of a denom, with a bank send or a CW20 transfer
+fn send_funds(recipient: &Addr, amount: Uint128, denom: &str) -> StdResult<CosmosMsg> {
+    Ok(match cw20_address(denom) {
+        Some(token) => WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+        None => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![coin(amount.u128(), denom)],
+        }
+        .into(),
+    })
+}
+
+fn path_to_string(path: &[bool]) -> String {
+    path.iter().map(|&b| if b { '1' } else { '0' }).collect()
 }
 
 fn execute_withdraw_house(
     deps: DepsMut,
     info: MessageInfo,
     amount: Uint128,
+    denom: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut stats = STATS.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let mut stats = load_stats(deps.storage, &config, &denom)?;
 
     if info.sender != config.admin {
         return Err(ContractError::Unauthorized {});
     }
 
-    if amount > stats.house_balance {
+    // Funds reserved for pending games cannot be withdrawn
+    if amount > stats.house_balance.saturating_sub(stats.reserved_balance) {
         return Err(ContractError::InsufficientBalance {});
     }
 
     stats.house_balance = stats.house_balance.checked_sub(amount)?;
-    STATS.save(deps.storage, &stats)?;
+    STATS.save(deps.storage, &denom, &stats)?;
 
-    let msg = BankMsg::Send {
-        to_address: config.admin.to_string(),
-        amount: vec![coin(amount.u128(), config.token_denom)],
-    };
+    let msg = send_funds(&config.admin, amount, &denom)?;
 
     Ok(Response::new()
         .add_message(msg)
         .add_attribute("action", "withdraw_house")
+        .add_attribute("denom", denom)
         .add_attribute("amount", amount))
 }
 
-fn execute_fund_house(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+fn execute_fund_house(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: Option<String>,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut stats = STATS.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
 
     // Only the admin can fund the house
     if info.sender != config.funder_address {
         return Err(ContractError::Unauthorized {});
     }
 
-    // Find the amount of the game's native token that was sent with this message
+    // Only denoms bets are accepted in have a house to fund
+    denom_limits(deps.storage, &config, &denom)?;
+    let mut stats = load_stats(deps.storage, &config, &denom)?;
+
+    // Find the amount of the denom that was sent with this message
     let amount = info
         .funds
         .iter()
-        .find(|c| c.denom == config.token_denom)
+        .find(|c| c.denom == denom)
         .map(|c| c.amount)
         .unwrap_or_else(Uint128::zero);
 
@@ -341,10 +2409,11 @@ fn execute_fund_house(deps: DepsMut, info: MessageInfo) -> Result<Response, Cont
 
     // Update the internal house balance state
     stats.house_balance = stats.house_balance.checked_add(amount)?;
-    STATS.save(deps.storage, &stats)?;
+    STATS.save(deps.storage, &denom, &stats)?;
 
     Ok(Response::new()
         .add_attribute("action", "fund_house")
+        .add_attribute("denom", denom)
         .add_attribute("amount", amount))
 }
 
@@ -352,36 +2421,59 @@ fn execute_sync_balance(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    denom: Option<String>,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let mut stats = STATS.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let mut stats = load_stats(deps.storage, &config, &denom)?;
 
     // Only the admin can perform this action
     if info.sender != config.admin {
         return Err(ContractError::Unauthorized {});
     }
 
-    // Use the querier to get the contract's ACTUAL on-chain balance of its native token
-    let actual_balance = deps
-        .querier
-        .query_balance(env.contract.address, config.token_denom)?;
+    // Use the querier to get the contract's ACTUAL on-chain balance of the denom
+    let actual_balance = match cw20_address(&denom) {
+        Some(token) => {
+            let balance: Cw20BalanceResponse = deps.querier.query_wasm_smart(
+                token,
+                &Cw20QueryMsg::Balance {
+                    address: env.contract.address.to_string(),
+                },
+            )?;
+            balance.balance
+        }
+        None => {
+            deps.querier
+                .query_balance(env.contract.address, denom.clone())?
+                .amount
+        }
+    };
+
+    // Autobet deposits are held for players and the jackpot pool for its winners,
+    // neither belongs to the house
+    let house_share = actual_balance
+        .saturating_sub(stats.autobet_balance)
+        .saturating_sub(stats.jackpot_balance);
 
     // Check if the actual balance is greater than what our internal ledger says
-    if actual_balance.amount > stats.house_balance {
-        let surplus = actual_balance.amount.checked_sub(stats.house_balance)?;
+    if house_share > stats.house_balance {
+        let surplus = house_share.checked_sub(stats.house_balance)?;
 
         // Update the internal ledger to match reality
-        stats.house_balance = actual_balance.amount;
-        STATS.save(deps.storage, &stats)?;
+        stats.house_balance = house_share;
+        STATS.save(deps.storage, &denom, &stats)?;
 
         Ok(Response::new()
             .add_attribute("action", "sync_balance")
+            .add_attribute("denom", denom)
             .add_attribute("funds_recovered", surplus)
             .add_attribute("new_house_balance", stats.house_balance))
     } else {
         // If there's no surplus, there's nothing to do.
         Ok(Response::new()
             .add_attribute("action", "sync_balance")
+            .add_attribute("denom", denom)
             .add_attribute("funds_recovered", "0")
             .add_attribute("message", "No surplus funds detected."))
     }
@@ -391,21 +2483,135 @@ fn execute_sync_balance(
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
-        QueryMsg::Stats {} => to_json_binary(&query_stats(deps)?),
+        QueryMsg::Stats { denom } => to_json_binary(&query_stats(deps, denom)?),
         QueryMsg::History { player, limit } => to_json_binary(&query_history(deps, player, limit)?),
-        QueryMsg::UserStats { player } => to_json_binary(&query_user_stats(deps, player)?),
+        QueryMsg::UserStats { player, denom } => {
+            to_json_binary(&query_user_stats(deps, player, denom)?)
+        }
         QueryMsg::GlobalLeaderboard {
             leaderboard_type,
             limit,
-        } => to_json_binary(&query_global_leaderboard(deps, leaderboard_type, limit)?),
+            denom,
+        } => to_json_binary(&query_global_leaderboard(
+            deps,
+            leaderboard_type,
+            limit,
+            denom,
+        )?),
         QueryMsg::DailyLeaderboard {
             leaderboard_type,
             limit,
+            denom,
         } => to_json_binary(&query_daily_leaderboard(
             deps,
             env,
             leaderboard_type,
             limit,
+            denom,
+        )?),
+        QueryMsg::DailyLeaderboardForDay {
+            day,
+            leaderboard_type,
+            limit,
+            denom,
+        } => to_json_binary(&query_daily_leaderboard_for_day(
+            deps,
+            day,
+            leaderboard_type,
+            limit,
+            denom,
+        )?),
+        QueryMsg::DailyLeaderboardDays {
+            denom,
+            start_after,
+            limit,
+        } => to_json_binary(&query_daily_leaderboard_days(
+            deps,
+            denom,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Leaderboard {
+            period,
+            index,
+            leaderboard_type,
+            start_after,
+            limit,
+            denom,
+        } => to_json_binary(&query_leaderboard(
+            deps,
+            env,
+            period,
+            index,
+            leaderboard_type,
+            start_after,
+            limit,
+            denom,
+        )?),
+        QueryMsg::LeaderboardPeriods {
+            period,
+            denom,
+            start_after,
+            limit,
+        } => to_json_binary(&query_leaderboard_periods(
+            deps,
+            period,
+            denom,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::Seasons { start_after, limit } => {
+            to_json_binary(&query_seasons(deps, start_after, limit)?)
+        }
+        QueryMsg::PlayerRank {
+            player,
+            leaderboard_type,
+            scope,
+            index,
+            neighbours,
+            denom,
+        } => to_json_binary(&query_player_rank(
+            deps,
+            env,
+            player,
+            leaderboard_type,
+            scope,
+            index,
+            neighbours,
+            denom,
+        )?),
+        QueryMsg::Denoms {} => to_json_binary(&query_denoms(deps)?),
+        QueryMsg::Jackpot { denom } => to_json_binary(&query_jackpot(deps, denom)?),
+        QueryMsg::SeedCommitment { id } => to_json_binary(&query_seed_commitment(deps, id)?),
+        QueryMsg::PendingBet { bet_id } => to_json_binary(&query_pending_bet(deps, bet_id)?),
+        QueryMsg::PendingBets { start_after, limit } => {
+            to_json_binary(&query_pending_bets(deps, start_after, limit)?)
+        }
+        QueryMsg::AutobetSession { player } => {
+            to_json_binary(&query_autobet_session(deps, player)?)
+        }
+        QueryMsg::VerifyGame { player, game_index } => {
+            to_json_binary(&query_verify_game(deps, player, game_index)?)
+        }
+        QueryMsg::PayoutTable { rows, risk_level } => {
+            to_json_binary(&query_payout_table(deps, rows, risk_level)?)
+        }
+        QueryMsg::PayoutTables {} => to_json_binary(&query_payout_tables(deps)?),
+        QueryMsg::PayoutTableVersion { table_id } => {
+            to_json_binary(&query_payout_table_version(deps, table_id)?)
+        }
+        QueryMsg::GameOdds {
+            difficulty,
+            rows,
+            risk_level,
+        } => to_json_binary(&query_game_odds(deps, difficulty, rows, risk_level)?),
+        QueryMsg::BetLimits {
+            denom,
+            difficulty,
+            rows,
+            risk_level,
+        } => to_json_binary(&query_bet_limits(
+            deps, denom, difficulty, rows, risk_level,
         )?),
     }
 }
@@ -415,57 +2621,96 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     Ok(ConfigResponse {
         token_denom: config.token_denom,
         admin: config.admin,
+        settle_delay_blocks: config.settle_delay_blocks,
+        refund_timeout_blocks: config.refund_timeout_blocks,
+        operator_public_key: config.operator_public_key,
+        drand: config.drand,
+        max_rtp: config.max_rtp,
+        max_exposure: config.max_exposure,
+        leaderboard_utc_offset_minutes: config.leaderboard_utc_offset_minutes,
     })
 }
 
-fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
-    let stats = STATS.load(deps.storage)?;
+fn query_stats(deps: Deps, denom: Option<String>) -> StdResult<StatsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let stats = load_stats(deps.storage, &config, &denom)?;
     Ok(StatsResponse {
+        denom,
         total_games: stats.total_games,
         total_wagered: stats.total_wagered,
         total_won: stats.total_won,
         house_balance: stats.house_balance,
+        reserved_balance: stats.reserved_balance,
+        autobet_balance: stats.autobet_balance,
+        net_pnl: net_pnl(stats.total_won, stats.total_wagered)?,
+        rtp: realized_rtp(stats.total_won, stats.total_wagered),
+        wins: stats.wins,
+        losses: stats.losses,
+        biggest_loss: stats.biggest_loss,
+        jackpot_balance: stats.jackpot_balance,
+    })
+}
+
+fn query_jackpot(deps: Deps, denom: Option<String>) -> StdResult<JackpotResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let stats = load_stats(deps.storage, &config, &denom)?;
+    Ok(JackpotResponse {
+        denom,
+        balance: stats.jackpot_balance,
+        jackpot: config.jackpot,
     })
 }
 
 fn query_history(deps: Deps, player: String, limit: Option<u32>) -> StdResult<HistoryResponse> {
     let player_addr = deps.api.addr_validate(&player)?;
-    let player_count = PLAYER_GAME_COUNT
-        .may_load(deps.storage, &player_addr)?
-        .unwrap_or(0);
-
-    let limit = limit.unwrap_or(10).min(100) as u64;
-    let start = player_count.saturating_sub(limit);
+    let limit = limit.unwrap_or(10).min(100) as usize;
+    let config = CONFIG.load(deps.storage)?;
 
-    let mut games = Vec::new();
-    for i in start..player_count {
-        if let Some(game) = GAME_HISTORY.may_load(deps.storage, (&player_addr, i))? {
-            games.push(game);
-        }
-    }
+    // The latest games, oldest first. Nonces reserved for pending games and autobet drops
+    // leave gaps until they are settled.
+    let mut games = GAME_HISTORY
+        .prefix(&player_addr)
+        .range(deps.storage, None, None, Order::Descending)
+        .take(limit)
+        .map(|item| {
+            let (_, mut game) = item?;
+            // Games recorded before PnL was signed stored every loss as 0
+            game.pnl = net_pnl(game.win_amount, game.bet_amount)?;
+            game.denom = stored_denom(&game.denom, &config).to_string();
+            Ok(game)
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    games.reverse();
 
     Ok(HistoryResponse { games })
 }
 
-fn query_user_stats(deps: Deps, player: String) -> StdResult<UserStatsResponse> {
+fn query_user_stats(
+    deps: Deps,
+    player: String,
+    denom: Option<String>,
+) -> StdResult<UserStatsResponse> {
     let player_addr = deps.api.addr_validate(&player)?;
-    let user_stats = USER_STATS
-        .may_load(deps.storage, &player_addr)?
-        .unwrap_or(UserStats {
-            total_games: 0,
-            total_wagered: Uint128::zero(),
-            total_won: Uint128::zero(),
-            best_win_pnl: Uint128::zero(),
-            best_win_multiplier: "0.0x".to_string(),
-        });
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let user_stats = load_user_stats(deps.storage, &config, &denom, &player_addr)?;
 
     Ok(UserStatsResponse {
         player: player_addr,
+        denom,
         total_games: user_stats.total_games,
         total_wagered: user_stats.total_wagered,
         total_won: user_stats.total_won,
         best_win_pnl: user_stats.best_win_pnl,
         best_win_multiplier: user_stats.best_win_multiplier,
+        best_win_rows: user_stats.best_win_rows,
+        net_pnl: net_pnl(user_stats.total_won, user_stats.total_wagered)?,
+        rtp: realized_rtp(user_stats.total_won, user_stats.total_wagered),
+        wins: user_stats.wins,
+        losses: user_stats.losses,
+        biggest_loss: user_stats.biggest_loss,
     })
 }
 
@@ -473,27 +2718,17 @@ fn query_global_leaderboard(
     deps: Deps,
     leaderboard_type: LeaderboardType,
     limit: Option<u32>,
+    denom: Option<String>,
 ) -> StdResult<LeaderboardResponse> {
-    let limit = limit.unwrap_or(10).min(100) as usize;
-
-    let entries = match leaderboard_type {
-        LeaderboardType::BestWins => GLOBAL_BEST_WINS.load(deps.storage)?,
-        LeaderboardType::TotalWagered => GLOBAL_TOTAL_WAGERED.load(deps.storage)?,
-    };
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
 
-    let limited_entries: Vec<MsgLeaderboardEntry> = entries
-        .into_iter()
-        .take(limit)
-        .map(|e| MsgLeaderboardEntry {
-            player: e.player,
-            value: e.value,
-            multiplier: e.multiplier,
-        })
-        .collect();
+    let board = board_id(BoardScope::AllTime, &leaderboard_type, &denom);
 
     Ok(LeaderboardResponse {
-        entries: limited_entries,
+        entries: leaderboard_entries(deps, &board, None, limit)?,
         leaderboard_type,
+        denom,
     })
 }
 
@@ -502,32 +2737,527 @@ fn query_daily_leaderboard(
     env: Env,
     leaderboard_type: LeaderboardType,
     limit: Option<u32>,
+    denom: Option<String>,
 ) -> StdResult<LeaderboardResponse> {
-    let limit = limit.unwrap_or(10).min(100) as usize;
-    let daily = DAILY_LEADERBOARD.load(deps.storage)?;
+    let config = CONFIG.load(deps.storage)?;
+    let today = period_index(
+        CalendarPeriod::Daily,
+        env.block.time.seconds(),
+        config.leaderboard_utc_offset_minutes,
+    );
+    query_daily_leaderboard_for_day(deps, today, leaderboard_type, limit, denom)
+}
 
-    // Check if reset is needed (for query consistency)
-    let entries = if should_reset_daily(daily.last_reset, env.block.time.seconds()) {
-        vec![] // Return empty if reset is due
-    } else {
-        match leaderboard_type {
-            LeaderboardType::BestWins => daily.entries_best_wins,
-            LeaderboardType::TotalWagered => daily.entries_wagered,
+fn query_daily_leaderboard_for_day(
+    deps: Deps,
+    day: u64,
+    leaderboard_type: LeaderboardType,
+    limit: Option<u32>,
+    denom: Option<String>,
+) -> StdResult<LeaderboardResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let board = board_id(
+        BoardScope::Period(PeriodStore::Daily, day),
+        &leaderboard_type,
+        &denom,
+    );
+
+    Ok(LeaderboardResponse {
+        entries: leaderboard_entries(deps, &board, None, limit)?,
+        leaderboard_type,
+        denom,
+    })
+}
+
+fn query_daily_leaderboard_days(
+    deps: Deps,
+    denom: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DailyLeaderboardDaysResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let days = period_indexes(deps, PeriodStore::Daily, &denom, start_after, limit)?;
+    Ok(DailyLeaderboardDaysResponse { denom, days })
+}
+
+#[allow(clippy::too_many_arguments)] // One argument per field of the Leaderboard query
+fn query_leaderboard(
+    deps: Deps,
+    env: Env,
+    period: LeaderboardPeriod,
+    index: Option<u64>,
+    leaderboard_type: LeaderboardType,
+    start_after: Option<LeaderboardCursor>,
+    limit: Option<u32>,
+    denom: Option<String>,
+) -> StdResult<PeriodLeaderboardResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+
+    let start_after = start_after
+        .map(|cursor| {
+            deps.api
+                .addr_validate(&cursor.player)
+                .map(|player| (cursor.value, player))
+        })
+        .transpose()?;
+
+    let board = load_board(
+        deps,
+        &env,
+        &config,
+        &period,
+        index,
+        &leaderboard_type,
+        &denom,
+    )?;
+
+    Ok(PeriodLeaderboardResponse {
+        entries: leaderboard_entries(deps, &board.id, start_after, limit)?,
+        leaderboard_type,
+        denom,
+        period,
+        index: board.index,
+        start: board.bounds.map(|(start, _)| start),
+        end: board.bounds.map(|(_, end)| end),
+    })
+}
+
+#[allow(clippy::too_many_arguments)] // One argument per field of the PlayerRank query
+fn query_player_rank(
+    deps: Deps,
+    env: Env,
+    player: String,
+    leaderboard_type: LeaderboardType,
+    scope: LeaderboardPeriod,
+    index: Option<u64>,
+    neighbours: Option<u32>,
+    denom: Option<String>,
+) -> StdResult<PlayerRankResponse> {
+    let player = deps.api.addr_validate(&player)?;
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let neighbours = neighbours.unwrap_or(5).min(50) as usize;
+
+    let board = load_board(
+        deps,
+        &env,
+        &config,
+        &scope,
+        index,
+        &leaderboard_type,
+        &denom,
+    )?;
+
+    let (rank, above, below) = match player_rank(deps.storage, &board.id, &player)? {
+        Some((rank, entry)) => {
+            let above = leaderboard_entries_above(
+                deps.storage,
+                &board.id,
+                entry.value,
+                &player,
+                neighbours,
+            )?;
+            let start_after = Some((entry.value, player.clone()));
+            let below = leaderboard_page(deps.storage, &board.id, start_after, neighbours)?;
+            (Some((rank, entry.value)), above, below)
+        }
+        None => (None, vec![], vec![]),
+    };
+
+    let gap = match (rank, above.last()) {
+        (Some((_, value)), Some(next)) => Some(next.value.checked_sub(value)?),
+        _ => None,
+    };
+
+    Ok(PlayerRankResponse {
+        player,
+        leaderboard_type,
+        denom,
+        scope,
+        index: board.index,
+        rank: rank.and_then(|(rank, _)| rank),
+        value: rank.map(|(_, value)| value),
+        gap,
+        above: msg_entries(above),
+        below: msg_entries(below),
+    })
+}
+
+/// The board a leaderboard query reads
+struct Board {
+    id: String,                 // See board_id
+    index: Option<u64>,         // None for AllTime
+    bounds: Option<(u64, u64)>, // Start and exclusive end of the period
+}
+
+fn load_board(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    period: &LeaderboardPeriod,
+    index: Option<u64>,
+    leaderboard_type: &LeaderboardType,
+    denom: &str,
+) -> StdResult<Board> {
+    let offset = config.leaderboard_utc_offset_minutes;
+    let (scope, bounds) = match (period, period.calendar()) {
+        (_, Some(calendar)) => {
+            let index =
+                index.unwrap_or_else(|| period_index(calendar, env.block.time.seconds(), offset));
+            let bounds = period_bounds(calendar, index, offset);
+            (BoardScope::Period(calendar.into(), index), Some(bounds))
+        }
+        (LeaderboardPeriod::Season { id }, None) => {
+            let season = SEASONS.load(deps.storage, *id)?;
+            let bounds = (season.start, season.end);
+            (BoardScope::Period(PeriodStore::Season, *id), Some(bounds))
         }
+        (_, None) => (BoardScope::AllTime, None),
     };
 
-    let limited_entries: Vec<MsgLeaderboardEntry> = entries
+    Ok(Board {
+        id: board_id(scope, leaderboard_type, denom),
+        index: match scope {
+            BoardScope::AllTime => None,
+            BoardScope::Period(_, index) => Some(index),
+        },
+        bounds,
+    })
+}
+
+fn query_leaderboard_periods(
+    deps: Deps,
+    period: CalendarPeriod,
+    denom: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<LeaderboardPeriodsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let offset = config.leaderboard_utc_offset_minutes;
+
+    let periods = period_indexes(deps, period.into(), &denom, start_after, limit)?
         .into_iter()
+        .map(|index| {
+            let (start, end) = period_bounds(period, index, offset);
+            PeriodResponse { index, start, end }
+        })
+        .collect();
+
+    Ok(LeaderboardPeriodsResponse {
+        denom,
+        period,
+        periods,
+    })
+}
+
+fn query_seasons(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SeasonsResponse> {
+    let limit = limit.unwrap_or(10).min(100) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let seasons = SEASONS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(id, season)| SeasonResponse {
+                id,
+                name: season.name,
+                start: season.start,
+                end: season.end,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(SeasonsResponse { seasons })
+}
+
+/// Indexes of the periods a denom has leaderboards for, oldest first
+fn period_indexes(
+    deps: Deps,
+    store: PeriodStore,
+    denom: &str,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<u64>> {
+    let limit = limit.unwrap_or(10).min(100) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    store
+        .leaderboards()
+        .prefix(denom)
+        .keys(deps.storage, start, None, Order::Ascending)
         .take(limit)
+        .collect()
+}
+
+/// Up to `limit` entries of a board after `start_after`, 10 by default and at most 100
+fn leaderboard_entries(
+    deps: Deps,
+    board: &str,
+    start_after: Option<(Int128, Addr)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<MsgLeaderboardEntry>> {
+    let limit = limit.unwrap_or(10).min(100) as usize;
+    let entries = leaderboard_page(deps.storage, board, start_after, limit)?;
+    Ok(msg_entries(entries))
+}
+
+fn msg_entries(entries: Vec<LeaderboardEntry>) -> Vec<MsgLeaderboardEntry> {
+    entries
+        .into_iter()
         .map(|e| MsgLeaderboardEntry {
             player: e.player,
             value: e.value,
             multiplier: e.multiplier,
+            rows: e.rows,
         })
-        .collect();
+        .collect()
+}
 
-    Ok(LeaderboardResponse {
-        entries: limited_entries,
-        leaderboard_type,
+fn query_denoms(deps: Deps) -> StdResult<DenomsResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut denoms = DENOMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (denom, limits) = item?;
+            Ok(DenomResponse {
+                default: denom == config.token_denom,
+                denom,
+                min_bet: limits.min_bet,
+                max_bet: limits.max_bet,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // Contracts from before denoms were listed only accept the default one
+    if !denoms.iter().any(|denom| denom.default) {
+        denoms.insert(
+            0,
+            DenomResponse {
+                denom: config.token_denom,
+                min_bet: None,
+                max_bet: None,
+                default: true,
+            },
+        );
+    }
+
+    Ok(DenomsResponse { denoms })
+}
+
+fn query_seed_commitment(deps: Deps, id: Option<u64>) -> StdResult<SeedCommitmentResponse> {
+    let active = ACTIVE_COMMITMENT.may_load(deps.storage)?;
+    let id = id
+        .or(active)
+        .ok_or_else(|| StdError::not_found("SeedCommitment"))?;
+    let seed_commitment = SEED_COMMITMENTS.load(deps.storage, id)?;
+
+    Ok(SeedCommitmentResponse {
+        id,
+        commitment: seed_commitment.commitment,
+        server_seed: seed_commitment.server_seed,
+        committed_at: seed_commitment.committed_at,
+        revealed_at: seed_commitment.revealed_at,
+        active: active == Some(id),
+    })
+}
+
+fn query_pending_bet(deps: Deps, bet_id: u64) -> StdResult<PendingBetResponse> {
+    let pending = PENDING_BETS.load(deps.storage, bet_id)?;
+    Ok(pending_bet_response(bet_id, pending))
+}
+
+fn query_pending_bets(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PendingBetsResponse> {
+    let limit = limit.unwrap_or(10).min(100) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let bets = PENDING_BETS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(bet_id, pending)| pending_bet_response(bet_id, pending)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PendingBetsResponse { bets })
+}
+
+fn pending_bet_response(bet_id: u64, pending: PendingBet) -> PendingBetResponse {
+    let rows = pending.rows;
+    PendingBetResponse {
+        bet_id,
+        player: pending.player,
+        difficulty: Difficulty::from_rows(rows),
+        rows,
+        risk_level: pending.risk_level,
+        denom: pending.denom.clone(),
+        bet_amount: pending.bet_amount,
+        table_id: pending.table_id,
+        nonce: pending.nonce,
+        placed_height: pending.placed_height,
+        settle_height: pending.settle_height,
+        refund_height: pending.refund_height,
+        randomness: match pending.entropy {
+            BetEntropy::SettleBlock => RandomnessMode::SettleBlock,
+            BetEntropy::OperatorSignature { .. } => RandomnessMode::OperatorSignature,
+            BetEntropy::Drand { .. } => RandomnessMode::Drand,
+        },
+        drand_round: match pending.entropy {
+            BetEntropy::Drand { round, .. } => Some(round),
+            _ => None,
+        },
+        drand_round_time: match pending.entropy {
+            BetEntropy::Drand { round_time, .. } => Some(round_time),
+            _ => None,
+        },
+    }
+}
+
+fn query_autobet_session(deps: Deps, player: String) -> StdResult<AutobetSessionResponse> {
+    let player = deps.api.addr_validate(&player)?;
+    let session = AUTOBET_SESSIONS.load(deps.storage, &player)?;
+    let rows = session.rows;
+    Ok(AutobetSessionResponse {
+        player,
+        difficulty: Difficulty::from_rows(rows),
+        rows,
+        risk_level: session.risk_level,
+        denom: session.denom.clone(),
+        bet_amount: session.bet_amount,
+        table_id: session.table_id,
+        deposit: session.deposit,
+        balance: session.balance,
+        drops_remaining: session.drops_remaining,
+        drops_played: session.drops_played,
+        stop_loss: session.stop_loss,
+        take_profit: session.take_profit,
+        started_at: session.started_at,
+        commitment_id: session.commitment_id,
+        client_seed: session.client_seed,
+    })
+}
+
+fn query_verify_game(deps: Deps, player: String, game_index: u64) -> StdResult<VerifyGameResponse> {
+    let player = deps.api.addr_validate(&player)?;
+    let record = GAME_HISTORY.load(deps.storage, (&player, game_index))?;
+
+    // Commitment games are checked against the hash committed on-chain
+    let commitment = match &record.seed_inputs {
+        Some(SeedInputs::Commitment { commitment_id, .. }) => Some(
+            SEED_COMMITMENTS
+                .may_load(deps.storage, *commitment_id)?
+                .map(|c| c.commitment.to_vec())
+                .unwrap_or_default(),
+        ),
+        _ => None,
+    };
+
+    // Games are paid out again on the table version they were played on. Games from before
+    // tables were versioned only have the multiplier they recorded to check the win against.
+    let table = match record.table_id {
+        Some(table_id) => Some(PAYOUT_TABLE_VERSIONS.load(deps.storage, table_id)?.0),
+        None => None,
+    };
+
+    verify_record(deps.api, &record, commitment.as_deref(), table.as_deref())
+}
+
+fn query_payout_table(
+    deps: Deps,
+    rows: u8,
+    risk_level: RiskLevel,
+) -> StdResult<PayoutTableResponse> {
+    let PayoutTable(multipliers) =
+        PAYOUT_TABLES.load(deps.storage, (rows, risk_key(&risk_level)))?;
+    Ok(PayoutTableResponse {
+        rows,
+        risk_level,
+        rtp: table_rtp(&multipliers).map_err(|e| StdError::generic_err(e.to_string()))?,
+        multipliers,
+    })
+}
+
+fn query_payout_tables(deps: Deps) -> StdResult<PayoutTablesResponse> {
+    let tables = PAYOUT_TABLES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| {
+            let ((rows, risk), PayoutTable(multipliers)) = item?;
+            let risk_level = risk_from_key(&risk)
+                .ok_or_else(|| StdError::generic_err(format!("Unknown risk level {risk}")))?;
+            Ok(PayoutTableResponse {
+                rows,
+                risk_level,
+                rtp: table_rtp(&multipliers).map_err(|e| StdError::generic_err(e.to_string()))?,
+                multipliers,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(PayoutTablesResponse { tables })
+}
+
+fn query_payout_table_version(deps: Deps, table_id: u64) -> StdResult<PayoutTableVersionResponse> {
+    let PayoutTable(multipliers) = PAYOUT_TABLE_VERSIONS.load(deps.storage, table_id)?;
+    Ok(PayoutTableVersionResponse {
+        table_id,
+        multipliers,
+    })
+}
+
+/// Odds of the table currently in place for a board and risk level
+fn query_game_odds(
+    deps: Deps,
+    difficulty: Option<Difficulty>,
+    rows: Option<u8>,
+    risk_level: RiskLevel,
+) -> StdResult<GameOddsResponse> {
+    let to_std = |err: ContractError| StdError::generic_err(err.to_string());
+    let rows = board_rows(difficulty, rows).map_err(to_std)?;
+    let (_, table) = payout_table(deps.storage, rows, &risk_level).map_err(to_std)?;
+    let odds = odds(&table).ok_or_else(|| to_std(ContractError::OverflowError {}))?;
+
+    Ok(GameOddsResponse {
+        rows,
+        risk_level,
+        rtp: odds.rtp,
+        house_edge: SignedDecimal::one()
+            - SignedDecimal::try_from(odds.rtp)
+                .map_err(|e| StdError::generic_err(e.to_string()))?,
+        variance: odds.variance,
+        max_multiplier: odds.max_multiplier,
     })
 }
+
+fn query_bet_limits(
+    deps: Deps,
+    denom: Option<String>,
+    difficulty: Option<Difficulty>,
+    rows: Option<u8>,
+    risk_level: RiskLevel,
+) -> StdResult<BetLimitsResponse> {
+    let to_std = |err: ContractError| StdError::generic_err(err.to_string());
+    let rows = board_rows(difficulty, rows).map_err(to_std)?;
+    let config = CONFIG.load(deps.storage)?;
+    let denom = denom_or_default(&config, denom);
+    let stats = load_stats(deps.storage, &config, &denom)?;
+    let (_, table) = payout_table(deps.storage, rows, &risk_level).map_err(to_std)?;
+    bet_limits(
+        deps.storage,
+        &config,
+        &stats,
+        &denom,
+        rows,
+        &risk_level,
+        &table,
+    )
+    .map_err(to_std)
+}
@@ -1,4 +1,5 @@
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{ConversionOverflowError, Decimal, OverflowError, StdError, Uint128};
+use plinko_fairness::{PlayError, TableError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,6 +10,9 @@ pub enum ContractError {
     #[error("{0}")]
     Overflow(#[from] OverflowError),
 
+    #[error("{0}")]
+    ConversionOverflow(#[from] ConversionOverflowError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
@@ -29,4 +33,157 @@ pub enum ContractError {
 
     #[error("Insufficient house balance to pay out winnings")]
     InsufficientHouseBalance {},
+
+    #[error("No server seed commitment is active")]
+    NoActiveCommitment {},
+
+    #[error("Commitment must be a 32 byte sha256 hash")]
+    InvalidCommitment {},
+
+    #[error("Server seed does not match commitment {commitment_id}")]
+    InvalidServerSeed { commitment_id: u64 },
+
+    #[error("Commitment {commitment_id} has already been revealed")]
+    CommitmentAlreadyRevealed { commitment_id: u64 },
+
+    #[error("Commitment {commitment_id} has not been revealed yet")]
+    CommitmentNotRevealed { commitment_id: u64 },
+
+    #[error("Client seed must be between 1 and {max_len} bytes")]
+    InvalidClientSeed { max_len: usize },
+
+    #[error("Bet cannot be settled before block {settle_height}")]
+    BetNotReady { settle_height: u64 },
+
+    #[error("Bet {bet_id} expired and can only be refunded")]
+    BetExpired { bet_id: u64 },
+
+    #[error("Bet cannot be refunded before block {refund_height}")]
+    BetNotExpired { refund_height: u64 },
+
+    #[error("Drand round {round} of bet {bet_id} is published, the bet can only be settled")]
+    DrandRoundPublished { bet_id: u64, round: u64 },
+
+    #[error("Settle delay and refund timeout must be at least one block")]
+    InvalidSettlementConfig {},
+
+    #[error("Operator public key must be a 96 byte BLS12-381 G2 point")]
+    InvalidOperatorKey {},
+
+    #[error("No operator key is registered")]
+    NoOperatorKey {},
+
+    #[error("Bet {bet_id} needs an operator signature to settle")]
+    MissingSignature { bet_id: u64 },
+
+    #[error("Invalid operator signature for bet {bet_id}")]
+    InvalidSignature { bet_id: u64 },
+
+    #[error("No drand beacon is configured")]
+    NoDrandConfig {},
+
+    #[error("Invalid drand config: public key must be 96 bytes and period non-zero")]
+    InvalidDrandConfig {},
+
+    #[error("Ball count must be between 1 and {max}")]
+    InvalidBallCount { max: u32 },
+
+    #[error("No autobet session")]
+    NoAutobetSession {},
+
+    #[error("An autobet session is already running")]
+    AutobetSessionActive {},
+
+    #[error("Invalid autobet: bet and drops must be non-zero and the deposit must cover one bet")]
+    InvalidAutobetConfig {},
+
+    #[error("Unsupported RNG version {version}")]
+    UnsupportedRngVersion { version: u8 },
+
+    #[error("No payout table for {rows} rows at {risk_level} risk")]
+    PayoutTableNotFound { rows: u8, risk_level: String },
+
+    #[error(
+        "The payout table for {rows} rows at {risk_level} risk still has {pending} unsettled games"
+    )]
+    PayoutTableInUse {
+        rows: u8,
+        risk_level: String,
+        pending: u64,
+    },
+
+    #[error("No game has {rows} rows")]
+    InvalidRows { rows: u8 },
+
+    #[error("Choose the board with a difficulty or a row count, not two different ones")]
+    InvalidBoard {},
+
+    #[error("Payout table must have {expected} multipliers, one per bucket")]
+    PayoutTableLength { expected: usize },
+
+    #[error("Payout table must be symmetric")]
+    PayoutTableAsymmetric {},
+
+    #[error("Payout table RTP {rtp} is above the ceiling of {max_rtp}")]
+    RtpAboveCeiling { rtp: Decimal, max_rtp: Decimal },
+
+    #[error("RTP ceiling must be above zero and at most 1")]
+    InvalidMaxRtp {},
+
+    #[error("Bet is below the minimum of {min_bet}")]
+    BetBelowMinimum { min_bet: Uint128 },
+
+    #[error("Bet is above the maximum of {max_bet}")]
+    BetAboveMaximum { max_bet: Uint128 },
+
+    #[error("Minimum bet must be above zero and at most the maximum bet")]
+    InvalidBetLimits {},
+
+    #[error("Max exposure must be above zero and at most 1")]
+    InvalidMaxExposure {},
+
+    #[error("Bets are not accepted in {denom}")]
+    UnsupportedDenom { denom: String },
+
+    #[error("Send the bet as a single coin")]
+    MultipleDenoms {},
+
+    #[error("The default denom cannot be removed")]
+    CannotRemoveDefaultDenom {},
+
+    #[error("Invalid jackpot: shares are at most 10000 bps, the payout above zero, and the trigger must leave a bucket out")]
+    InvalidJackpotConfig {},
+
+    #[error("Leaderboard UTC offset must be between -720 and 840 minutes")]
+    InvalidUtcOffset {},
+
+    #[error("Invalid season: it needs a name and must end after it starts and in the future")]
+    InvalidSeason {},
+
+    #[error("Season {id} has already started")]
+    SeasonStarted { id: u64 },
+
+    #[error("At most {max} seasons can be open at once")]
+    TooManySeasons { max: usize },
+}
+
+impl From<PlayError> for ContractError {
+    fn from(err: PlayError) -> Self {
+        match err {
+            PlayError::InvalidMultiplierIndex => ContractError::InvalidMultiplierIndex {},
+            PlayError::Overflow => ContractError::OverflowError {},
+            PlayError::UnsupportedRngVersion(version) => {
+                ContractError::UnsupportedRngVersion { version }
+            }
+        }
+    }
+}
+
+impl From<TableError> for ContractError {
+    fn from(err: TableError) -> Self {
+        match err {
+            TableError::WrongLength { expected } => ContractError::PayoutTableLength { expected },
+            TableError::Asymmetric => ContractError::PayoutTableAsymmetric {},
+        }
+    }
 }
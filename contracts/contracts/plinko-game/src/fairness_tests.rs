@@ -0,0 +1,164 @@
+//! Statistical fairness checks of the paths games are played with, for every RNG version.
+//!
+//! Every test drops DROPS balls per row count from one player with consecutive nonces against
+//! one committed server seed, the way a player's games are seeded. Inputs are fixed, so results
+//! are deterministic; run with `--nocapture` to print the statistics as a fairness report.
+
+use cosmwasm_std::testing::MockApi;
+
+use crate::rng::{calculate_bucket_index, commitment_seed};
+use plinko_fairness::rng::{generate_path, RNG_V1_REHASH, RNG_V2_EXPAND};
+use plinko_fairness::{MAX_ROWS, MIN_ROWS};
+
+const DROPS: u64 = 1_000_000;
+
+/// Chi-square critical values at p = 0.001 for 8..=16 degrees of freedom (rows)
+const CHI_SQUARE_CRITICAL: [f64; 9] = [
+    26.124, 27.877, 29.588, 31.264, 32.909, 34.528, 36.123, 37.697, 39.252,
+];
+
+fn chi_square_critical(degrees_of_freedom: u8) -> f64 {
+    CHI_SQUARE_CRITICAL[(degrees_of_freedom - MIN_ROWS) as usize]
+}
+
+/// Largest |z| accepted for a normal approximation; with fixed inputs this only fails on real bias
+const Z_LIMIT: f64 = 4.5;
+
+/// Every RNG version a recorded game can have been played with
+const RNG_VERSIONS: [u8; 2] = [RNG_V1_REHASH, RNG_V2_EXPAND];
+
+/// Every (RNG version, rows) pair to test
+fn boards() -> impl Iterator<Item = (u8, u8)> {
+    RNG_VERSIONS
+        .into_iter()
+        .flat_map(|rng_version| (MIN_ROWS..=MAX_ROWS).map(move |rows| (rng_version, rows)))
+}
+
+/// Path of the player's game `nonce` against a fixed server seed
+fn drop_ball(player: &str, rng_version: u8, nonce: u64, rows: u8) -> Vec<u8> {
+    let seed = commitment_seed(b"fairness server seed", "fairness", player, nonce);
+    generate_path(seed, rows, rng_version).unwrap()
+}
+
+/// P(bucket = k) under Binomial(rows, 0.5)
+fn binomial_pmf(rows: u8, k: usize) -> f64 {
+    let rows = rows as u64;
+    let k = k as u64;
+    let mut choose = 1u64;
+    for i in 0..k {
+        choose = choose * (rows - i) / (i + 1);
+    }
+    choose as f64 / (1u64 << rows) as f64
+}
+
+/// Bucket histogram and the paths' total right moves over DROPS consecutive nonces
+fn drop_balls(rng_version: u8, rows: u8) -> (Vec<u64>, u64) {
+    let player = MockApi::default().addr_make("player");
+    let mut buckets = vec![0u64; rows as usize + 1];
+    let mut rights = 0u64;
+
+    for nonce in 0..DROPS {
+        let bucket = calculate_bucket_index(&drop_ball(player.as_str(), rng_version, nonce, rows));
+        buckets[bucket] += 1;
+        rights += bucket as u64;
+    }
+
+    (buckets, rights)
+}
+
+#[test]
+fn test_bucket_distribution_is_binomial() {
+    for (rng_version, rows) in boards() {
+        let (buckets, rights) = drop_balls(rng_version, rows);
+        let n = DROPS as f64;
+
+        // Chi-square goodness of fit against Binomial(rows, 0.5)
+        let chi_square: f64 = buckets
+            .iter()
+            .enumerate()
+            .map(|(k, &observed)| {
+                let expected = n * binomial_pmf(rows, k);
+                (observed as f64 - expected).powi(2) / expected
+            })
+            .sum();
+        println!(
+            "RNG v{rng_version}, {rows} rows: chi-square = {chi_square:.2} with {rows} degrees of freedom (critical {})",
+            chi_square_critical(rows)
+        );
+        assert!(
+            chi_square < chi_square_critical(rows),
+            "Buckets of {rows} rows with RNG v{rng_version} do not follow Binomial({rows}, 0.5): chi-square = {chi_square:.2}"
+        );
+
+        // Each bucket count is itself Binomial(DROPS, p_k)
+        for (k, &observed) in buckets.iter().enumerate() {
+            let p = binomial_pmf(rows, k);
+            let z = (observed as f64 - n * p) / (n * p * (1.0 - p)).sqrt();
+            assert!(
+                z.abs() < Z_LIMIT,
+                "Bucket {k} of {rows} rows with RNG v{rng_version}: {observed} drops, z = {z:.2}"
+            );
+        }
+
+        // All right moves together are Binomial(DROPS * rows, 0.5)
+        let moves = n * rows as f64;
+        let z = (rights as f64 - moves / 2.0) / (moves / 4.0).sqrt();
+        println!("RNG v{rng_version}, {rows} rows: {rights} right moves of {moves}, z = {z:.2}");
+        assert!(
+            z.abs() < Z_LIMIT,
+            "{rows} rows with RNG v{rng_version} lean to one side: z = {z:.2}"
+        );
+    }
+}
+
+#[test]
+fn test_consecutive_nonces_are_uncorrelated() {
+    let player = MockApi::default().addr_make("player");
+
+    for (rng_version, rows) in boards() {
+        let mut previous = drop_ball(player.as_str(), rng_version, 0, rows);
+
+        // Sums for the Pearson correlation of consecutive buckets
+        let (mut sum_x, mut sum_y, mut sum_xy, mut sum_xx, mut sum_yy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        // How often each row goes the same way as in the previous nonce's drop
+        let mut same_direction = vec![0u64; rows as usize];
+
+        for nonce in 1..DROPS {
+            let path = drop_ball(player.as_str(), rng_version, nonce, rows);
+            let x = calculate_bucket_index(&previous) as f64;
+            let y = calculate_bucket_index(&path) as f64;
+            sum_x += x;
+            sum_y += y;
+            sum_xy += x * y;
+            sum_xx += x * x;
+            sum_yy += y * y;
+
+            for (row, (a, b)) in previous.iter().zip(&path).enumerate() {
+                same_direction[row] += (a == b) as u64;
+            }
+            previous = path;
+        }
+
+        let n = (DROPS - 1) as f64;
+        let covariance = sum_xy / n - (sum_x / n) * (sum_y / n);
+        let correlation = covariance
+            / ((sum_xx / n - (sum_x / n).powi(2)) * (sum_yy / n - (sum_y / n).powi(2))).sqrt();
+        // Under independence r * sqrt(n) is approximately standard normal
+        let z = correlation * n.sqrt();
+        println!(
+            "RNG v{rng_version}, {rows} rows: consecutive nonce bucket correlation = {correlation:.5}, z = {z:.2}"
+        );
+        assert!(
+            z.abs() < Z_LIMIT,
+            "Consecutive nonces are correlated on {rows} rows with RNG v{rng_version}: r = {correlation:.5}"
+        );
+
+        for (row, &same) in same_direction.iter().enumerate() {
+            let z = (same as f64 - n / 2.0) / (n / 4.0).sqrt();
+            assert!(
+                z.abs() < Z_LIMIT,
+                "Row {row} of {rows} rows with RNG v{rng_version} repeats the previous nonce's direction: z = {z:.2}"
+            );
+        }
+    }
+}
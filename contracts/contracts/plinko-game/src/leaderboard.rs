@@ -1,147 +1,645 @@
-use crate::state::LeaderboardEntry;
-use cosmwasm_std::{Addr, Uint128};
+use crate::msg::CalendarPeriod;
+use crate::state::{leaderboard_entries, LeaderboardEntry, RankedEntry};
+use cosmwasm_std::{Addr, Decimal, Int128, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
 
-const MAX_LEADERBOARD_SIZE: usize = 100;
+/// The number of seconds in a standard 24-hour day.
+const SECONDS_IN_A_DAY: u64 = 86_400;
 
-/// Update leaderboard with new entry, maintaining sorted order
+/// Days from the Monday starting ISO week 0 to 1970-01-01, a Thursday
+const EPOCH_WEEKDAY: u64 = 3;
+
+/// Lowest rank `player_rank` counts down to, which bounds the entries it reads
+pub const MAX_RANK: u64 = 1_000;
+
+/// Set a player's entry on a board, replacing their previous one.
+/// Only the player's entry and its index key are read and written, whatever the board's size.
 pub fn update_leaderboard(
-    leaderboard: &mut Vec<LeaderboardEntry>,
-    player: Addr,
-    value: Uint128,
-    multiplier: Option<String>,
-) {
-    // Remove existing entry for this player if present
-    leaderboard.retain(|entry| entry.player != player);
-
-    // Create new entry
-    let new_entry = LeaderboardEntry {
-        player,
+    storage: &mut dyn Storage,
+    board: &str, // See state::board_id
+    player: &Addr,
+    value: Int128,
+    best_win: Option<(Decimal, u8)>, // Multiplier and rows of the best win, for BestWins
+) -> StdResult<()> {
+    let (multiplier, rows) = best_win.unzip();
+    let entry = LeaderboardEntry {
+        player: player.clone(),
         value,
         multiplier,
+        rows,
+    };
+    save_entry(storage, board, entry)
+}
+
+/// Move the entries of a board stored as a sorted `Vec` to [`leaderboard_entries`]
+pub fn import_leaderboard(
+    storage: &mut dyn Storage,
+    board: &str,
+    entries: Vec<LeaderboardEntry>,
+) -> StdResult<()> {
+    entries
+        .into_iter()
+        .try_for_each(|entry| save_entry(storage, board, entry))
+}
+
+/// Entries of a board from the highest value down, ties by descending address.
+/// Starts after the entry with the given value and player, if any.
+pub fn leaderboard_page(
+    storage: &dyn Storage,
+    board: &str,
+    start_after: Option<(Int128, Addr)>,
+    limit: usize,
+) -> StdResult<Vec<LeaderboardEntry>> {
+    let max = start_after
+        .as_ref()
+        .map(|(value, player)| Bound::exclusive((value.i128(), (board, player))));
+    leaderboard_entries()
+        .idx
+        .value
+        .sub_prefix(board.to_string())
+        .range(storage, None, max, Order::Descending)
+        .take(limit)
+        .map(|item| item.map(|(_, ranked)| ranked.entry))
+        .collect()
+}
+
+/// A player's 1-based rank on a board and their entry, None if they are not on it.
+/// Counts at most MAX_RANK entries above the player; the rank is None below MAX_RANK.
+pub fn player_rank(
+    storage: &dyn Storage,
+    board: &str,
+    player: &Addr,
+) -> StdResult<Option<(Option<u64>, LeaderboardEntry)>> {
+    let Some(ranked) = leaderboard_entries().may_load(storage, (board, player))? else {
+        return Ok(None);
     };
+    let min = Bound::exclusive((ranked.entry.value.i128(), (board, player)));
+    let above = leaderboard_entries()
+        .idx
+        .value
+        .sub_prefix(board.to_string())
+        .keys_raw(storage, Some(min), None, Order::Ascending)
+        .take(MAX_RANK as usize)
+        .count() as u64;
+    let rank = (above < MAX_RANK).then_some(above + 1);
+    Ok(Some((rank, ranked.entry)))
+}
 
-    // Find insertion position (descending order)
-    let insert_pos = leaderboard
-        .iter()
-        .position(|entry| entry.value < value)
-        .unwrap_or(leaderboard.len());
+/// Up to `limit` entries ranked right above the entry with the given value and player,
+/// highest first
+pub fn leaderboard_entries_above(
+    storage: &dyn Storage,
+    board: &str,
+    value: Int128,
+    player: &Addr,
+    limit: usize,
+) -> StdResult<Vec<LeaderboardEntry>> {
+    let min = Bound::exclusive((value.i128(), (board, player)));
+    let mut entries = leaderboard_entries()
+        .idx
+        .value
+        .sub_prefix(board.to_string())
+        .range(storage, Some(min), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, ranked)| ranked.entry))
+        .collect::<StdResult<Vec<_>>>()?;
+    entries.reverse();
+    Ok(entries)
+}
 
-    // Insert at correct position
-    leaderboard.insert(insert_pos, new_entry);
+fn save_entry(storage: &mut dyn Storage, board: &str, entry: LeaderboardEntry) -> StdResult<()> {
+    let player = entry.player.clone();
+    let ranked = RankedEntry {
+        board: board.to_string(),
+        entry,
+    };
+    leaderboard_entries().save(storage, (board, &player), &ranked)
+}
 
-    // Trim to max size
-    if leaderboard.len() > MAX_LEADERBOARD_SIZE {
-        leaderboard.truncate(MAX_LEADERBOARD_SIZE);
+/// Number of full UTC days since the Unix epoch, unique per calendar day
+pub fn day_index(seconds: u64) -> u64 {
+    // Integer division effectively floors the result
+    seconds / SECONDS_IN_A_DAY
+}
+
+/// Index of the period containing a time: days, ISO weeks or months since the Unix epoch,
+/// counted in local time `utc_offset_minutes` east of UTC
+pub fn period_index(period: CalendarPeriod, seconds: u64, utc_offset_minutes: i16) -> u64 {
+    let local_seconds = seconds.saturating_add_signed(i64::from(utc_offset_minutes) * 60);
+    let day = day_index(local_seconds);
+    match period {
+        CalendarPeriod::Daily => day,
+        CalendarPeriod::Weekly => (day + EPOCH_WEEKDAY) / 7,
+        CalendarPeriod::Monthly => {
+            let (year, month) = civil_from_days(day);
+            (year - 1970) * 12 + month - 1
+        }
     }
 }
 
-/// Check if daily leaderboard needs reset (00:00 UTC)
-pub fn should_reset_daily(last_reset_seconds: u64, current_time_seconds: u64) -> bool {
-    // The number of seconds in a standard 24-hour day.
-    const SECONDS_IN_A_DAY: u64 = 86_400;
+/// Start and exclusive end of a period in seconds since the Unix epoch, see [`period_index`]
+pub fn period_bounds(period: CalendarPeriod, index: u64, utc_offset_minutes: i16) -> (u64, u64) {
+    // Wide enough for any index, clamped to u64 at the end
+    let first_day = |index: i128| match period {
+        CalendarPeriod::Daily => index,
+        CalendarPeriod::Weekly => index * 7 - i128::from(EPOCH_WEEKDAY),
+        CalendarPeriod::Monthly => days_from_civil(1970 + index / 12, index % 12 + 1),
+    };
+    let seconds = |day: i128| {
+        let seconds = day * i128::from(SECONDS_IN_A_DAY) - i128::from(utc_offset_minutes) * 60;
+        seconds.clamp(0, i128::from(u64::MAX)) as u64
+    };
+    let index = i128::from(index);
+    (seconds(first_day(index)), seconds(first_day(index + 1)))
+}
 
-    // Calculate the number of full days that have passed since the Unix epoch for each timestamp.
-    // Integer division effectively floors the result, giving us a unique "day index".
-    let last_reset_day_index = last_reset_seconds / SECONDS_IN_A_DAY;
-    let current_day_index = current_time_seconds / SECONDS_IN_A_DAY;
+/// Year and month of a day since the Unix epoch, in the proleptic Gregorian calendar
+fn civil_from_days(day: u64) -> (u64, u64) {
+    // Counted from 0000-03-01 in 400 year eras, so leap days end each year
+    let days = day + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    (year, month)
+}
 
-    // If the current day's index is greater than the last reset's day index,
-    // it means we have crossed over midnight UTC.
-    current_day_index > last_reset_day_index
+/// Day since the Unix epoch of the first of a month, the inverse of [`civil_from_days`]
+fn days_from_civil(year: i128, month: i128) -> i128 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let month_from_march = (month + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5;
+    let day_of_era = 365 * year_of_era + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use cosmwasm_std::Addr;
+    use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::Record;
+    use cw_storage_plus::Map;
+    use std::cell::Cell;
+
+    const BOARD: &str = "all/best_wins/uusdc";
+
+    fn players(storage: &dyn Storage, board: &str) -> Vec<String> {
+        leaderboard_page(storage, board, None, usize::MAX)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.player.to_string())
+            .collect()
+    }
 
     #[test]
     fn test_update_leaderboard_new_entry() {
-        let mut leaderboard = vec![];
+        let mut storage = MockStorage::new();
 
         update_leaderboard(
-            &mut leaderboard,
-            Addr::unchecked("player1"),
-            Uint128::new(100),
-            Some("2.0x".to_string()),
-        );
+            &mut storage,
+            BOARD,
+            &Addr::unchecked("player1"),
+            Int128::new(100),
+            Some((Decimal::percent(200), 8)),
+        )
+        .unwrap();
 
+        let leaderboard = leaderboard_page(&storage, BOARD, None, 10).unwrap();
         assert_eq!(leaderboard.len(), 1);
         assert_eq!(leaderboard[0].player.as_str(), "player1");
-        assert_eq!(leaderboard[0].value, Uint128::new(100));
+        assert_eq!(leaderboard[0].value, Int128::new(100));
+        assert_eq!(leaderboard[0].multiplier, Some(Decimal::percent(200)));
+        assert_eq!(leaderboard[0].rows, Some(8));
     }
 
     #[test]
     fn test_update_leaderboard_sorted_order() {
-        let mut leaderboard = vec![];
+        let mut storage = MockStorage::new();
 
+        for (player, value) in [("player1", 100), ("player2", 200), ("player3", 150)] {
+            let player = Addr::unchecked(player);
+            update_leaderboard(&mut storage, BOARD, &player, Int128::new(value), None).unwrap();
+        }
+        // Other boards are ranked apart
+        let other = "all/best_wins/uatom";
         update_leaderboard(
-            &mut leaderboard,
-            Addr::unchecked("player1"),
-            Uint128::new(100),
-            None,
-        );
-        update_leaderboard(
-            &mut leaderboard,
-            Addr::unchecked("player2"),
-            Uint128::new(200),
+            &mut storage,
+            other,
+            &Addr::unchecked("player4"),
+            Int128::new(175),
             None,
-        );
-        update_leaderboard(
-            &mut leaderboard,
-            Addr::unchecked("player3"),
-            Uint128::new(150),
-            None,
-        );
+        )
+        .unwrap();
 
+        let leaderboard = leaderboard_page(&storage, BOARD, None, 10).unwrap();
         assert_eq!(leaderboard.len(), 3);
-        assert_eq!(leaderboard[0].value, Uint128::new(200));
-        assert_eq!(leaderboard[1].value, Uint128::new(150));
-        assert_eq!(leaderboard[2].value, Uint128::new(100));
+        assert_eq!(leaderboard[0].value, Int128::new(200));
+        assert_eq!(leaderboard[1].value, Int128::new(150));
+        assert_eq!(leaderboard[2].value, Int128::new(100));
+        assert_eq!(players(&storage, other), vec!["player4"]);
     }
 
     #[test]
     fn test_update_leaderboard_replace_existing() {
-        let mut leaderboard = vec![];
+        let mut storage = MockStorage::new();
+        let player = Addr::unchecked("player1");
 
-        update_leaderboard(
-            &mut leaderboard,
-            Addr::unchecked("player1"),
-            Uint128::new(100),
-            None,
+        update_leaderboard(&mut storage, BOARD, &player, Int128::new(100), None).unwrap();
+        update_leaderboard(&mut storage, BOARD, &player, Int128::new(200), None).unwrap();
+
+        let leaderboard = leaderboard_page(&storage, BOARD, None, 10).unwrap();
+        assert_eq!(leaderboard.len(), 1);
+        assert_eq!(leaderboard[0].value, Int128::new(200));
+        // The old value left the index too
+        let stale = Some((Int128::new(150), Addr::unchecked("player2")));
+        assert!(leaderboard_page(&storage, BOARD, stale, 10)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_update_leaderboard_net_losses() {
+        let mut storage = MockStorage::new();
+
+        for (player, value) in [("player1", -300), ("player2", 0), ("player3", -50)] {
+            let player = Addr::unchecked(player);
+            update_leaderboard(&mut storage, BOARD, &player, Int128::new(value), None).unwrap();
+        }
+
+        // Smaller losses rank higher
+        assert_eq!(
+            players(&storage, BOARD),
+            vec!["player2", "player3", "player1"]
         );
+    }
+
+    #[test]
+    fn test_leaderboard_page() {
+        let mut storage = MockStorage::new();
+        for i in 0..250u32 {
+            let player = Addr::unchecked(format!("player{i:03}"));
+            let value = Int128::new(i128::from(i / 2)); // Pairs of players tie
+            update_leaderboard(&mut storage, BOARD, &player, value, None).unwrap();
+        }
+
+        // Every entry once, ties by descending address
+        let mut ranking = vec![];
+        let mut start_after = None;
+        loop {
+            let page = leaderboard_page(&storage, BOARD, start_after, 100).unwrap();
+            let Some(last) = page.last() else { break };
+            start_after = Some((last.value, last.player.clone()));
+            ranking.extend(page.into_iter().map(|e| e.player.to_string()));
+        }
+        assert_eq!(ranking.len(), 250);
+        assert_eq!(&ranking[..3], ["player249", "player248", "player247"]);
+        assert_eq!(ranking[249], "player000");
+
+        // A cursor between two tied players
+        let cursor = Some((Int128::new(1), Addr::unchecked("player003")));
+        let page = leaderboard_page(&storage, BOARD, cursor, 2).unwrap();
+        let page: Vec<&str> = page.iter().map(|e| e.player.as_str()).collect();
+        assert_eq!(page, vec!["player002", "player001"]);
+    }
+
+    #[test]
+    fn test_import_leaderboard() {
+        let mut storage = MockStorage::new();
+        let entry = |player: &str, value: i128| LeaderboardEntry {
+            player: Addr::unchecked(player),
+            value: Int128::new(value),
+            multiplier: None,
+            rows: None,
+        };
+
+        import_leaderboard(
+            &mut storage,
+            BOARD,
+            vec![entry("player1", 300), entry("player2", 200)],
+        )
+        .unwrap();
         update_leaderboard(
-            &mut leaderboard,
-            Addr::unchecked("player1"),
-            Uint128::new(200),
+            &mut storage,
+            BOARD,
+            &Addr::unchecked("player2"),
+            Int128::new(400),
             None,
+        )
+        .unwrap();
+
+        assert_eq!(players(&storage, BOARD), vec!["player2", "player1"]);
+    }
+
+    #[test]
+    fn test_player_rank() {
+        let mut storage = MockStorage::new();
+        for (player, value) in [("a", 300), ("b", 100), ("c", 200), ("d", 200), ("e", -50)] {
+            let player = Addr::unchecked(player);
+            update_leaderboard(&mut storage, BOARD, &player, Int128::new(value), None).unwrap();
+        }
+        let player = |name: &str| Addr::unchecked(name);
+        let rank = |name: &str| {
+            player_rank(&storage, BOARD, &player(name))
+                .unwrap()
+                .map(|(rank, entry)| (rank, entry.value.i128()))
+        };
+
+        // Ties by descending address: d ranks above c
+        assert_eq!(rank("a"), Some((Some(1), 300)));
+        assert_eq!(rank("d"), Some((Some(2), 200)));
+        assert_eq!(rank("c"), Some((Some(3), 200)));
+        assert_eq!(rank("e"), Some((Some(5), -50)));
+        assert_eq!(rank("f"), None);
+
+        let names = |entries: Vec<LeaderboardEntry>| {
+            entries
+                .into_iter()
+                .map(|e| e.player.to_string())
+                .collect::<Vec<_>>()
+        };
+        let above = |name: &str, value: i128, limit: usize| {
+            let value = Int128::new(value);
+            names(leaderboard_entries_above(&storage, BOARD, value, &player(name), limit).unwrap())
+        };
+        assert_eq!(above("b", 100, 2), vec!["d", "c"]);
+        assert_eq!(above("b", 100, 10), vec!["a", "d", "c"]);
+        assert!(above("a", 300, 10).is_empty());
+    }
+
+    /// Storage charging the Cosmos SDK's default KV store gas
+    #[derive(Default)]
+    struct MeteredStorage {
+        storage: MockStorage,
+        gas: Cell<u64>,
+    }
+
+    impl MeteredStorage {
+        fn charge(&self, gas: usize) {
+            self.gas.set(self.gas.get() + gas as u64);
+        }
+
+        fn gas_used(&mut self, f: impl FnOnce(&mut Self)) -> u64 {
+            let before = self.gas.get();
+            f(self);
+            self.gas.get() - before
+        }
+    }
+
+    impl Storage for MeteredStorage {
+        fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+            let value = self.storage.get(key);
+            self.charge(1000 + 3 * (key.len() + value.as_ref().map_or(0, Vec::len)));
+            value
+        }
+
+        fn range<'a>(
+            &'a self,
+            start: Option<&[u8]>,
+            end: Option<&[u8]>,
+            order: Order,
+        ) -> Box<dyn Iterator<Item = Record> + 'a> {
+            Box::new(
+                self.storage
+                    .range(start, end, order)
+                    .inspect(|(key, value)| {
+                        self.charge(30 + 3 * (key.len() + value.len()));
+                    }),
+            )
+        }
+
+        fn set(&mut self, key: &[u8], value: &[u8]) {
+            self.charge(2000 + 30 * (key.len() + value.len()));
+            self.storage.set(key, value);
+        }
+
+        fn remove(&mut self, key: &[u8]) {
+            self.charge(1000);
+            self.storage.remove(key);
+        }
+    }
+
+    /// The previous design: each board one sorted `Vec` of at most 100 entries
+    const VEC_LEADERBOARDS: Map<&str, Vec<LeaderboardEntry>> = Map::new("vec_leaderboards");
+
+    fn update_vec_leaderboard(storage: &mut dyn Storage, player: &Addr, value: Int128) {
+        let mut leaderboard = VEC_LEADERBOARDS
+            .may_load(storage, BOARD)
+            .unwrap()
+            .unwrap_or_default();
+        leaderboard.retain(|entry| entry.player != player);
+        let insert_pos = leaderboard
+            .iter()
+            .position(|entry| entry.value < value)
+            .unwrap_or(leaderboard.len());
+        leaderboard.insert(
+            insert_pos,
+            LeaderboardEntry {
+                player: player.clone(),
+                value,
+                multiplier: None,
+                rows: None,
+            },
         );
+        leaderboard.truncate(100);
+        VEC_LEADERBOARDS.save(storage, BOARD, &leaderboard).unwrap();
+    }
 
-        assert_eq!(leaderboard.len(), 1);
-        assert_eq!(leaderboard[0].value, Uint128::new(200));
+    fn address(i: u32) -> Addr {
+        Addr::unchecked(format!("cosmwasm1player{i:0>38}"))
+    }
+
+    fn value(i: u32) -> Int128 {
+        Int128::new(1_000_000_000 + i128::from(i) * 1_000)
+    }
+
+    /// Gas of one update and one top 10 read with `size` entries on the board, per design.
+    /// Run with `--nocapture` to print the figures.
+    #[test]
+    fn test_leaderboard_gas_benchmark() {
+        let mut results = vec![];
+        for size in [100, 1_000, 10_000] {
+            let mut storage = MeteredStorage::default();
+            for i in 0..size {
+                update_leaderboard(&mut storage, BOARD, &address(i), value(i), None).unwrap();
+                if i < 100 {
+                    update_vec_leaderboard(&mut storage, &address(i), value(i));
+                }
+            }
+            // A mid-table player moves up, and a new player enters
+            let mid = address(size / 2);
+            let update = storage.gas_used(|s| {
+                update_leaderboard(s, BOARD, &mid, value(size), None).unwrap();
+                update_leaderboard(s, BOARD, &address(size), value(size + 1), None).unwrap();
+            }) / 2;
+            let read = storage.gas_used(|s| {
+                leaderboard_page(s, BOARD, None, 10).unwrap();
+            });
+
+            let vec_mid = address(50);
+            let vec_update = storage.gas_used(|s| {
+                update_vec_leaderboard(s, &vec_mid, value(size));
+                update_vec_leaderboard(s, &address(size), value(size + 1));
+            }) / 2;
+            let vec_read = storage.gas_used(|s| {
+                VEC_LEADERBOARDS.load(s, BOARD).unwrap();
+            });
+
+            println!(
+                "{size:>6} entries: update {update:>6} gas (Vec of 100: {vec_update:>7}), \
+                 top 10 {read:>6} gas (Vec of 100: {vec_read:>6})"
+            );
+            results.push((update, read, vec_update, vec_read));
+        }
+
+        for &(update, read, vec_update, vec_read) in &results {
+            assert!(update * 10 < vec_update);
+            assert!(read < vec_read);
+        }
+        // The indexed design costs the same whatever the board's size
+        let (update, read, ..) = results[0];
+        assert!(results
+            .iter()
+            .all(|result| (result.0, result.1) == (update, read)));
+    }
+
+    #[test]
+    fn test_player_rank_is_capped() {
+        let mut results = vec![];
+        for size in [MAX_RANK as u32 + 500, 3 * MAX_RANK as u32] {
+            let mut storage = MeteredStorage::default();
+            for i in 0..size {
+                update_leaderboard(&mut storage, BOARD, &address(i), value(i), None).unwrap();
+            }
+            let rank = |storage: &MeteredStorage, i: u32| {
+                player_rank(storage, BOARD, &address(i)).unwrap().unwrap().0
+            };
+            // Player i has size - 1 - i entries above them
+            assert_eq!(rank(&storage, size - 1), Some(1));
+            assert_eq!(rank(&storage, size - MAX_RANK as u32), Some(MAX_RANK));
+            assert_eq!(rank(&storage, size - MAX_RANK as u32 - 1), None);
+
+            let last = storage.gas_used(|s| {
+                assert_eq!(rank(s, 0), None);
+            });
+            results.push(last);
+        }
+        // The last player costs the same whatever the board's size
+        assert_eq!(results[0], results[1]);
     }
 
     #[test]
-    fn test_should_reset_daily_same_day() {
+    fn test_day_index() {
         let base_time = 1704067200; // 2024-01-01 00:00:00 UTC
-        let later_same_day = base_time + 3600; // 1 hour later
 
-        assert!(!should_reset_daily(base_time, later_same_day));
+        assert_eq!(day_index(base_time), 19723);
+        assert_eq!(day_index(base_time + 86399), 19723);
+        assert_eq!(day_index(base_time + 86400), 19724);
     }
 
     #[test]
-    fn test_should_reset_daily_next_day() {
+    fn test_daily_period_same_day() {
         let base_time = 1704067200; // 2024-01-01 00:00:00 UTC
-        let next_day = base_time + 86400; // Next day
+        let later_same_day = base_time + 3600; // 1 hour later
 
-        assert!(should_reset_daily(base_time, next_day));
+        assert_eq!(
+            period_index(CalendarPeriod::Daily, base_time, 0),
+            period_index(CalendarPeriod::Daily, later_same_day, 0)
+        );
     }
 
     #[test]
-    fn test_should_reset_daily_multiple_days() {
+    fn test_daily_period_next_day() {
         let base_time = 1704067200; // 2024-01-01 00:00:00 UTC
+        let next_day = base_time + 86400; // Next day
         let three_days_later = base_time + (86400 * 3);
 
-        assert!(should_reset_daily(base_time, three_days_later));
+        assert_eq!(period_index(CalendarPeriod::Daily, next_day, 0), 19724);
+        assert_eq!(
+            period_index(CalendarPeriod::Daily, three_days_later, 0),
+            19726
+        );
+    }
+
+    #[test]
+    fn test_period_index() {
+        let monday = 1704067200; // 2024-01-01 00:00:00 UTC, a Monday
+        let sunday = monday - 1; // 2023-12-31 23:59:59 UTC
+
+        assert_eq!(period_index(CalendarPeriod::Weekly, monday, 0), 2818);
+        assert_eq!(
+            period_index(CalendarPeriod::Weekly, monday + 6 * 86400, 0),
+            2818
+        );
+        assert_eq!(period_index(CalendarPeriod::Weekly, sunday, 0), 2817);
+        assert_eq!(period_index(CalendarPeriod::Weekly, 0, 0), 0);
+        assert_eq!(period_index(CalendarPeriod::Monthly, monday, 0), 648);
+        assert_eq!(period_index(CalendarPeriod::Monthly, sunday, 0), 647);
+        assert_eq!(period_index(CalendarPeriod::Monthly, 0, 0), 0);
+        // 2024-02-29, a leap day
+        assert_eq!(period_index(CalendarPeriod::Monthly, 1709164800, 0), 649);
+
+        // Half an hour past midnight UTC is still the last day of 2023 at UTC-1
+        let local = monday + 1800;
+        assert_eq!(period_index(CalendarPeriod::Daily, local, -60), 19722);
+        assert_eq!(period_index(CalendarPeriod::Weekly, local, -60), 2817);
+        assert_eq!(period_index(CalendarPeriod::Monthly, local, -60), 647);
+        assert_eq!(period_index(CalendarPeriod::Daily, sunday, 60), 19723);
+    }
+
+    #[test]
+    fn test_period_bounds() {
+        let monday = 1704067200; // 2024-01-01 00:00:00 UTC
+
+        assert_eq!(
+            period_bounds(CalendarPeriod::Daily, 19723, 0),
+            (monday, monday + 86400)
+        );
+        assert_eq!(
+            period_bounds(CalendarPeriod::Daily, 19723, 120),
+            (monday - 7200, monday + 86400 - 7200)
+        );
+        assert_eq!(
+            period_bounds(CalendarPeriod::Weekly, 2818, 0),
+            (monday, monday + 7 * 86400)
+        );
+        // February 2024 has 29 days
+        assert_eq!(
+            period_bounds(CalendarPeriod::Monthly, 649, 0),
+            (1706745600, 1709251200)
+        );
+        assert_eq!(
+            period_bounds(CalendarPeriod::Monthly, 11, 0),
+            (28857600, 31536000)
+        );
+        // Week 0 began before the epoch
+        assert_eq!(period_bounds(CalendarPeriod::Weekly, 0, 0), (0, 4 * 86400));
+        assert_eq!(
+            period_bounds(CalendarPeriod::Monthly, u64::MAX, 0),
+            (u64::MAX, u64::MAX)
+        );
+
+        // Every period index round trips through its bounds
+        for period in [
+            CalendarPeriod::Daily,
+            CalendarPeriod::Weekly,
+            CalendarPeriod::Monthly,
+        ] {
+            for index in [1, 100, 648, 1000] {
+                let (start, end) = period_bounds(period, index, 330);
+                assert_eq!(period_index(period, start, 330), index);
+                assert_eq!(period_index(period, end - 1, 330), index);
+                assert_eq!(period_index(period, end, 330), index + 1);
+            }
+        }
     }
 }
@@ -0,0 +1,58 @@
+//! Decoding of values stored before multipliers were `Decimal`.
+//!
+//! Games, user stats and leaderboards used to store multipliers as display strings like "5.6x",
+//! truncated to one decimal. They are read as `Decimal` and written back in the new format the
+//! next time the entry is saved.
+
+use std::str::FromStr;
+
+use cosmwasm_std::Decimal;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer};
+
+/// A multiplier stored as a decimal string, or as a legacy string with a trailing "x"
+pub fn multiplier<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse_multiplier(&value).map_err(D::Error::custom)
+}
+
+/// An optional multiplier, see [`multiplier`]
+pub fn optional_multiplier<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Decimal>, D::Error> {
+    Option::<String>::deserialize(deserializer)?
+        .map(|value| parse_multiplier(&value).map_err(D::Error::custom))
+        .transpose()
+}
+
+fn parse_multiplier(value: &str) -> Result<Decimal, cosmwasm_std::StdError> {
+    Decimal::from_str(value.strip_suffix('x').unwrap_or(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct Record {
+        #[serde(deserialize_with = "multiplier")]
+        multiplier: Decimal,
+        #[serde(default, deserialize_with = "optional_multiplier")]
+        best: Option<Decimal>,
+    }
+
+    #[test]
+    fn test_legacy_values() {
+        let legacy: Record =
+            cosmwasm_std::from_json(r#"{"multiplier":"5.6x","best":"0.0x"}"#).unwrap();
+        assert_eq!(legacy.multiplier, Decimal::permille(5600));
+        assert_eq!(legacy.best, Some(Decimal::zero()));
+
+        let current: Record = cosmwasm_std::from_json(r#"{"multiplier":"0.25"}"#).unwrap();
+        assert_eq!(current.multiplier, Decimal::permille(250));
+        assert_eq!(current.best, None);
+
+        assert!(cosmwasm_std::from_json::<Record>(r#"{"multiplier":"5.6y"}"#).is_err());
+    }
+}
@@ -1,10 +1,13 @@
 pub mod contract;
 pub mod error;
 pub mod leaderboard;
+pub mod legacy;
 pub mod msg;
-pub mod multipliers;
 pub mod rng;
 pub mod state;
 
 #[cfg(test)]
+mod fairness_tests;
+#[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests;
@@ -1,25 +1,209 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{
+    Addr, Binary, Decimal, HexBinary, Int128, OverflowError, SignedDecimal, Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+
+use plinko_fairness::get_rows;
+
+use crate::legacy;
+pub use plinko_fairness::verify::SeedInputs;
+pub use plinko_fairness::{Difficulty, RiskLevel};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub token_denom: String,
     pub funder_address: String,
+    pub settle_delay_blocks: Option<u64>,
+    pub refund_timeout_blocks: Option<u64>,
+    pub max_rtp: Option<Decimal>, // Ceiling for payout tables set later, defaults to 1
+    pub max_exposure: Option<Decimal>, // Share of the house balance one bet may win, defaults to 1
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum ExecuteMsg {
+    /// CW20 tokens sent with a `ReceiveMsg`. A token is accepted once the admin lists its
+    /// `cw20:<contract>` denom, and winnings are paid back with a CW20 transfer.
+    Receive(Cw20ReceiveMsg),
+    /// The bet is the single coin sent, in any accepted denom. It is placed against the active
+    /// server seed commitment and settles when the operator reveals that seed.
+    /// The client seed defaults to the height and transaction index of the placing block.
+    /// The board is either a difficulty or a row count from 8 to 16.
     Play {
-        difficulty: Difficulty,
+        difficulty: Option<Difficulty>, // Alias for 8, 12 or 16 rows
+        rows: Option<u8>,
         risk_level: RiskLevel,
+        client_seed: Option<String>,
+        ball_count: Option<u32>, // Drops this many balls, the funds are split evenly between them
     },
     /// Withdraw house winnings (admin only)
     WithdrawHouse {
         amount: Uint128,
+        denom: Option<String>, // Defaults to the default denom, as do the denoms below
     },
     /// This message must be sent with the native tokens to be funded.
+    FundHouse {
+        denom: Option<String>,
+    },
+    SyncBalance {
+        denom: Option<String>,
+    },
+    /// Escrow a bet now and settle it later with SettleBet.
+    /// Randomness defaults to the settling block's entropy.
+    PlaceBet {
+        difficulty: Option<Difficulty>,
+        rows: Option<u8>,
+        risk_level: RiskLevel,
+        randomness: Option<RandomnessMode>,
+    },
+    /// Settle a placed bet once its settle height is reached, by anyone. SettleBlock bets settle
+    /// in the block at their settle height only, since that block seeds them.
+    /// OperatorSignature bets need the operator's signature over `operator_message`,
+    /// Drand bets need the beacon signature of their round.
+    SettleBet {
+        bet_id: u64,
+        signature: Option<Binary>,
+    },
+    /// Close a bet that was not settled in time (player only).
+    /// An expired OperatorSignature bet pays the larger of the bet and its reserved maximum
+    /// payout. A SettleBlock bet not settled in its block returns the bet, and so does a Drand
+    /// bet, only before its round is published.
+    RefundBet {
+        bet_id: u64,
+    },
+    /// Update the PlaceBet settle delay and refund timeout (admin only)
+    UpdateSettlementConfig {
+        settle_delay_blocks: Option<u64>,
+        refund_timeout_blocks: Option<u64>,
+    },
+    /// Configure or disable the drand beacon used by Drand bets (admin only)
+    SetDrandConfig {
+        drand: Option<DrandConfig>,
+    },
+    /// Start an autobet session funded with the tokens sent; `drops` balls of `bet_amount` each.
+    /// The drops are bound to the active server seed commitment and a client seed, which
+    /// defaults like Play's, and can only run once that seed is revealed.
+    StartAutobet {
+        difficulty: Option<Difficulty>,
+        rows: Option<u8>,
+        risk_level: RiskLevel,
+        bet_amount: Uint128,
+        drops: u32,
+        stop_loss: Option<Uint128>,
+        take_profit: Option<Uint128>,
+        client_seed: Option<String>,
+    },
+    /// Run the next batch of a player's autobet session once its seed is revealed (anyone).
+    /// The remaining balance is paid out once a limit is reached.
+    AdvanceAutobet {
+        player: String,
+        max_drops: Option<u32>,
+    },
+    /// End the sender's autobet session and return its balance, only before its seed is revealed
+    StopAutobet {},
+    /// Add or replace the payout table of a board size and risk level (admin only).
+    /// Multipliers run from the leftmost bucket to the rightmost.
+    SetPayoutTable {
+        rows: u8,
+        risk_level: RiskLevel,
+        multipliers: Vec<Decimal>,
+    },
+    /// Remove a payout table, which stops play on it (admin only)
+    RemovePayoutTable {
+        rows: u8,
+        risk_level: RiskLevel,
+    },
+    /// Set the highest RTP a new payout table may have (admin only)
+    SetMaxRtp {
+        max_rtp: Decimal,
+    },
+    /// Set or clear the bet bounds of a board and risk level in one denom (admin only)
+    SetBetLimits {
+        denom: Option<String>,
+        difficulty: Option<Difficulty>,
+        rows: Option<u8>,
+        risk_level: RiskLevel,
+        min_bet: Option<Uint128>,
+        max_bet: Option<Uint128>,
+    },
+    /// Accept bets in a denom, or update its bet bounds on every board (admin only)
+    SetDenom {
+        denom: String,
+        min_bet: Option<Uint128>,
+        max_bet: Option<Uint128>,
+    },
+    /// Stop accepting bets in a denom other than the default (admin only).
+    /// Its house balance can still be withdrawn and its pending bets still settle.
+    RemoveDenom {
+        denom: String,
+    },
+    /// Configure or disable the progressive jackpot (admin only).
+    /// A disabled jackpot keeps its pool until it is enabled again.
+    SetJackpot {
+        jackpot: Option<JackpotConfig>,
+    },
+    /// Set the share of the house balance a single bet may win at the top multiplier (admin only)
+    SetMaxExposure {
+        max_exposure: Decimal,
+    },
+    /// Set the time zone daily, weekly and monthly leaderboards restart in, from -720 to 840
+    /// minutes east of UTC (admin only). Applies immediately, also mid-period: later games count
+    /// in the period the new offset puts them in, and boards already played on keep their games.
+    SetLeaderboardUtcOffset {
+        utc_offset_minutes: i16,
+    },
+    /// Create a season, or change one that has not ended (admin only).
+    /// Times are seconds since the Unix epoch; a started season keeps its start.
+    SetSeason {
+        id: Option<u64>, // None creates a season
+        name: String,
+        start: u64,
+        end: u64,
+    },
+    /// Remove a season that has not started (admin only)
+    RemoveSeason {
+        id: u64,
+    },
+    /// Rotate or remove the BLS12-381 G2 operator key (admin only)
+    SetOperatorKey {
+        public_key: Option<Binary>,
+    },
+    /// Commit sha256(server_seed) for upcoming games (admin only)
+    CommitServerSeed {
+        commitment: HexBinary,
+    },
+    /// Reveal a committed server seed so its pending games can be settled (admin only)
+    RevealServerSeed {
+        commitment_id: u64,
+        server_seed: HexBinary,
+    },
+    /// Settle up to `limit` pending games of a revealed commitment (anyone)
+    SettleGames {
+        commitment_id: u64,
+        limit: Option<u32>,
+    },
+    /// Return a pending game whose commitment was not revealed within the refund timeout
+    /// (player only)
+    RefundGame {
+        commitment_id: u64,
+        nonce: u64,
+    },
+}
+
+/// Hook messages of a CW20 Receive, acting like the ExecuteMsg of the same name
+#[cw_serde]
+pub enum ReceiveMsg {
+    Play {
+        difficulty: Option<Difficulty>,
+        rows: Option<u8>,
+        risk_level: RiskLevel,
+        client_seed: Option<String>,
+        ball_count: Option<u32>,
+    },
     FundHouse {},
-    SyncBalance {},
 }
 
 #[cw_serde]
@@ -27,79 +211,452 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(ConfigResponse)]
     Config {},
+    /// Statistics and leaderboards are kept per denom, defaulting to the default denom
     #[returns(StatsResponse)]
-    Stats {},
+    Stats { denom: Option<String> },
     #[returns(HistoryResponse)]
     History { player: String, limit: Option<u32> },
     #[returns(UserStatsResponse)]
-    UserStats { player: String },
+    UserStats {
+        player: String,
+        denom: Option<String>,
+    },
     #[returns(LeaderboardResponse)]
     GlobalLeaderboard {
         leaderboard_type: LeaderboardType,
         limit: Option<u32>,
+        denom: Option<String>,
     },
     #[returns(LeaderboardResponse)]
     DailyLeaderboard {
         leaderboard_type: LeaderboardType,
         limit: Option<u32>,
+        denom: Option<String>,
+    },
+    /// Daily leaderboard of a day, by days since the Unix epoch; empty if nobody played
+    #[returns(LeaderboardResponse)]
+    DailyLeaderboardForDay {
+        day: u64,
+        leaderboard_type: LeaderboardType,
+        limit: Option<u32>,
+        denom: Option<String>,
+    },
+    /// Days a denom's daily leaderboard has games on, oldest first
+    #[returns(DailyLeaderboardDaysResponse)]
+    DailyLeaderboardDays {
+        denom: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Leaderboard of any period. Calendar periods take the index of a past one,
+    /// defaulting to the one in progress. Pages through the full ranking from `start_after`.
+    #[returns(PeriodLeaderboardResponse)]
+    Leaderboard {
+        period: LeaderboardPeriod,
+        index: Option<u64>,
+        leaderboard_type: LeaderboardType,
+        start_after: Option<LeaderboardCursor>,
+        limit: Option<u32>,
+        denom: Option<String>,
+    },
+    /// Calendar periods a denom has leaderboards for, oldest first
+    #[returns(LeaderboardPeriodsResponse)]
+    LeaderboardPeriods {
+        period: CalendarPeriod,
+        denom: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(SeasonsResponse)]
+    Seasons {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// A player's rank on a leaderboard, with up to `neighbours` entries (5 by default, at
+    /// most 50) on each side. Periods are chosen as in `Leaderboard`. Ranks are counted down
+    /// to 1,000; below that only the rank is left out.
+    #[returns(PlayerRankResponse)]
+    PlayerRank {
+        player: String,
+        leaderboard_type: LeaderboardType,
+        scope: LeaderboardPeriod,
+        index: Option<u64>,
+        neighbours: Option<u32>,
+        denom: Option<String>,
+    },
+    /// Denoms bets are accepted in, with their bet bounds
+    #[returns(DenomsResponse)]
+    Denoms {},
+    /// Defaults to the active commitment when no id is given
+    #[returns(SeedCommitmentResponse)]
+    SeedCommitment { id: Option<u64> },
+    #[returns(PendingBetResponse)]
+    PendingBet { bet_id: u64 },
+    #[returns(PendingBetsResponse)]
+    PendingBets {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(AutobetSessionResponse)]
+    AutobetSession { player: String },
+    /// Recompute a recorded game from its stored seed inputs
+    #[returns(VerifyGameResponse)]
+    VerifyGame { player: String, game_index: u64 },
+    #[returns(PayoutTableResponse)]
+    PayoutTable { rows: u8, risk_level: RiskLevel },
+    #[returns(PayoutTablesResponse)]
+    PayoutTables {},
+    /// A payout table as it was put in place, by the `table_id` games record
+    #[returns(PayoutTableVersionResponse)]
+    PayoutTableVersion { table_id: u64 },
+    /// RTP, house edge and spread of a board's current payout table
+    #[returns(GameOddsResponse)]
+    GameOdds {
+        difficulty: Option<Difficulty>,
+        rows: Option<u8>,
+        risk_level: RiskLevel,
+    },
+    /// Jackpot pool of a denom and the jackpot settings
+    #[returns(JackpotResponse)]
+    Jackpot { denom: Option<String> },
+    /// Smallest and largest bet a board accepts right now in a denom
+    #[returns(BetLimitsResponse)]
+    BetLimits {
+        denom: Option<String>,
+        difficulty: Option<Difficulty>,
+        rows: Option<u8>,
+        risk_level: RiskLevel,
     },
 }
 
 #[cw_serde]
-pub enum Difficulty {
-    Easy,   // 8 rows
-    Medium, // 12 rows
-    Hard,   // 16 rows
+pub enum RandomnessMode {
+    SettleBlock,       // Entropy from the block at the bet's settle height
+    OperatorSignature, // BLS signature by the registered operator key
+    Drand,             // Beacon of a drand round published after the bet
 }
 
+/// An unchained drand network (bls-unchained-g1-rfc9380, e.g. quicknet)
 #[cw_serde]
-pub enum RiskLevel {
-    Low,
-    Medium,
-    High,
+pub struct DrandConfig {
+    pub public_key: HexBinary, // 96 byte G2 group public key
+    pub genesis_time: u64,
+    pub period_seconds: u64,
+}
+
+/// A pool fed by a slice of every bet and paid out, on top of the table win, by chosen buckets
+#[cw_serde]
+pub struct JackpotConfig {
+    pub contribution_bps: u16, // Share of each bet moved from the house balance into the pool
+    pub payout_bps: u16,       // Share of the pool paid on a hit
+    pub trigger: Option<JackpotTrigger>, // Defaults to the outer buckets of Hard/High
+}
+
+/// Board, risk level and edge buckets that hit the jackpot
+#[cw_serde]
+pub struct JackpotTrigger {
+    pub rows: u8,
+    pub risk_level: RiskLevel,
+    pub edge_buckets: u8, // Buckets counted in from either edge
+}
+
+impl Default for JackpotTrigger {
+    fn default() -> Self {
+        JackpotTrigger {
+            rows: get_rows(&Difficulty::Hard),
+            risk_level: RiskLevel::High,
+            edge_buckets: 1,
+        }
+    }
+}
+
+impl JackpotTrigger {
+    /// Whether a ball landing in `bucket` on this board hits the jackpot
+    pub fn hits(&self, rows: u8, risk_level: &RiskLevel, bucket: usize) -> bool {
+        let edge = self.edge_buckets as usize;
+        rows == self.rows
+            && *risk_level == self.risk_level
+            && (bucket < edge || bucket + edge > rows as usize)
+    }
 }
 
 #[cw_serde]
 pub enum LeaderboardType {
     BestWins,     // Sorted by best single game PnL
     TotalWagered, // Sorted by cumulative wagered amount
+    NetProfit,    // Sorted by cumulative winnings minus wagers
+}
+
+/// Periods leaderboards restart on, at midnight in the leaderboard time zone
+#[cw_serde]
+#[derive(Copy)]
+pub enum CalendarPeriod {
+    Daily,
+    Weekly, // ISO weeks, Monday to Sunday
+    Monthly,
+}
+
+#[cw_serde]
+pub enum LeaderboardPeriod {
+    AllTime,
+    Daily,
+    Weekly,
+    Monthly,
+    Season { id: u64 },
+}
+
+impl LeaderboardPeriod {
+    pub fn calendar(&self) -> Option<CalendarPeriod> {
+        match self {
+            LeaderboardPeriod::Daily => Some(CalendarPeriod::Daily),
+            LeaderboardPeriod::Weekly => Some(CalendarPeriod::Weekly),
+            LeaderboardPeriod::Monthly => Some(CalendarPeriod::Monthly),
+            LeaderboardPeriod::AllTime | LeaderboardPeriod::Season { .. } => None,
+        }
+    }
 }
 
 #[cw_serde]
 pub struct ConfigResponse {
     pub token_denom: String,
     pub admin: Addr,
+    pub settle_delay_blocks: u64,
+    pub refund_timeout_blocks: u64,
+    pub operator_public_key: Option<Binary>,
+    pub drand: Option<DrandConfig>,
+    pub max_rtp: Decimal,
+    pub max_exposure: Decimal,
+    pub leaderboard_utc_offset_minutes: i16,
 }
 
 #[cw_serde]
 pub struct StatsResponse {
+    pub denom: String,
     pub total_games: u64,
     pub total_wagered: Uint128,
     pub total_won: Uint128,
     pub house_balance: Uint128,
+    pub reserved_balance: Uint128,
+    pub autobet_balance: Uint128,
+    pub net_pnl: Int128, // Players' total_won - total_wagered, the house's result negated
+    pub rtp: Decimal,    // total_won / total_wagered
+    pub wins: u64,
+    pub losses: u64,
+    pub biggest_loss: Uint128,
+    pub jackpot_balance: Uint128, // Held for the jackpot, not part of house_balance
 }
 
 #[cw_serde]
 pub struct UserStatsResponse {
     pub player: Addr,
+    pub denom: String,
     pub total_games: u64,
     pub total_wagered: Uint128,
     pub total_won: Uint128,
     pub best_win_pnl: Uint128,
-    pub best_win_multiplier: String,
+    pub best_win_multiplier: Decimal,
+    pub best_win_rows: u8,
+    pub net_pnl: Int128, // total_won - total_wagered
+    pub rtp: Decimal,    // total_won / total_wagered
+    pub wins: u64,       // Games that paid more than the bet
+    pub losses: u64,     // Games that paid less than the bet
+    pub biggest_loss: Uint128,
 }
 
 #[cw_serde]
 pub struct LeaderboardEntry {
     pub player: Addr,
-    pub value: Uint128,
-    pub multiplier: Option<String>, // Only for BestWins
+    pub value: Int128,
+    pub multiplier: Option<Decimal>, // Only for BestWins
+    pub rows: Option<u8>,            // Only for BestWins
+}
+
+/// The value and player of the last entry of a page
+#[cw_serde]
+pub struct LeaderboardCursor {
+    pub value: Int128,
+    pub player: String,
 }
 
 #[cw_serde]
 pub struct LeaderboardResponse {
     pub entries: Vec<LeaderboardEntry>,
     pub leaderboard_type: LeaderboardType,
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct DailyLeaderboardDaysResponse {
+    pub denom: String,
+    pub days: Vec<u64>,
+}
+
+#[cw_serde]
+pub struct PeriodLeaderboardResponse {
+    pub entries: Vec<LeaderboardEntry>,
+    pub leaderboard_type: LeaderboardType,
+    pub denom: String,
+    pub period: LeaderboardPeriod,
+    pub index: Option<u64>, // None for AllTime
+    pub start: Option<u64>, // Seconds since the Unix epoch, None for AllTime
+    pub end: Option<u64>,   // Exclusive
+}
+
+/// Rank, value and gap are None when the player is not on the board, and rank alone when
+/// they rank below 1,000
+#[cw_serde]
+pub struct PlayerRankResponse {
+    pub player: Addr,
+    pub leaderboard_type: LeaderboardType,
+    pub denom: String,
+    pub scope: LeaderboardPeriod,
+    pub index: Option<u64>, // None for AllTime
+    pub rank: Option<u64>,  // 1-based
+    pub value: Option<Int128>,
+    pub gap: Option<Int128>, // Lead of the entry ranked just above, None at the top
+    pub above: Vec<LeaderboardEntry>, // Highest first, as are all entries
+    pub below: Vec<LeaderboardEntry>,
+}
+
+#[cw_serde]
+pub struct PeriodResponse {
+    pub index: u64,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[cw_serde]
+pub struct LeaderboardPeriodsResponse {
+    pub denom: String,
+    pub period: CalendarPeriod,
+    pub periods: Vec<PeriodResponse>,
+}
+
+#[cw_serde]
+pub struct SeasonResponse {
+    pub id: u64,
+    pub name: String,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[cw_serde]
+pub struct SeasonsResponse {
+    pub seasons: Vec<SeasonResponse>,
+}
+
+#[cw_serde]
+pub struct DenomResponse {
+    pub denom: String,
+    pub min_bet: Option<Uint128>,
+    pub max_bet: Option<Uint128>,
+    pub default: bool, // The configured token_denom, which cannot be removed
+}
+
+#[cw_serde]
+pub struct DenomsResponse {
+    pub denoms: Vec<DenomResponse>,
+}
+
+#[cw_serde]
+pub struct SeedCommitmentResponse {
+    pub id: u64,
+    pub commitment: HexBinary,
+    pub server_seed: Option<HexBinary>,
+    pub committed_at: u64,
+    pub revealed_at: Option<u64>,
+    pub active: bool,
+}
+
+#[cw_serde]
+pub struct PendingBetResponse {
+    pub bet_id: u64,
+    pub player: Addr,
+    pub difficulty: Option<Difficulty>,
+    pub rows: u8,
+    pub risk_level: RiskLevel,
+    pub denom: String,
+    pub bet_amount: Uint128,
+    pub table_id: u64, // Payout table version the bet is paid on
+    pub nonce: u64,
+    pub placed_height: u64,
+    pub settle_height: u64,         // First block the bet can be settled in
+    pub refund_height: Option<u64>, // First block the player can claim a refund in
+    pub randomness: RandomnessMode,
+    pub drand_round: Option<u64>,      // Round a Drand bet settles with
+    pub drand_round_time: Option<u64>, // Time the round is published, until which it can be cancelled
+}
+
+#[cw_serde]
+pub struct AutobetSessionResponse {
+    pub player: Addr,
+    pub difficulty: Option<Difficulty>,
+    pub rows: u8,
+    pub risk_level: RiskLevel,
+    pub denom: String,
+    pub bet_amount: Uint128,
+    pub table_id: u64, // Payout table version every drop is paid on
+    pub deposit: Uint128,
+    pub balance: Uint128,
+    pub drops_remaining: u32,
+    pub drops_played: u32,
+    pub stop_loss: Option<Uint128>,
+    pub take_profit: Option<Uint128>,
+    pub started_at: u64,
+    pub commitment_id: u64,
+    pub client_seed: String,
+}
+
+#[cw_serde]
+pub struct PayoutTableResponse {
+    pub rows: u8,
+    pub risk_level: RiskLevel,
+    pub multipliers: Vec<Decimal>,
+    pub rtp: Decimal, // Expected return per unit bet
+}
+
+#[cw_serde]
+pub struct BetLimitsResponse {
+    pub denom: String,
+    pub rows: u8,
+    pub risk_level: RiskLevel,
+    pub min_bet: Uint128, // Larger of the denom's and the board's minimum, or 1
+    pub max_bet: Uint128, // Smaller of the configured maximum and exposure_max_bet
+    pub configured_max_bet: Option<Uint128>, // Smaller of the denom's and the board's maximum
+    pub exposure_max_bet: Uint128, // Largest bet the house balance covers at the top multiplier
+}
+
+#[cw_serde]
+pub struct JackpotResponse {
+    pub denom: String,
+    pub balance: Uint128,
+    pub jackpot: Option<JackpotConfig>, // None while disabled
+}
+
+#[cw_serde]
+pub struct GameOddsResponse {
+    pub rows: u8,
+    pub risk_level: RiskLevel,
+    pub rtp: Decimal,              // Expected return per unit bet
+    pub house_edge: SignedDecimal, // 1 - rtp, negative if the table favours players
+    pub variance: Decimal,         // Variance of the multiplier
+    pub max_multiplier: Decimal,
+}
+
+#[cw_serde]
+pub struct PayoutTablesResponse {
+    pub tables: Vec<PayoutTableResponse>,
+}
+
+#[cw_serde]
+pub struct PayoutTableVersionResponse {
+    pub table_id: u64,
+    pub multipliers: Vec<Decimal>,
+}
+
+#[cw_serde]
+pub struct PendingBetsResponse {
+    pub bets: Vec<PendingBetResponse>,
 }
 
 #[cw_serde]
@@ -110,12 +667,59 @@ pub struct HistoryResponse {
 #[cw_serde]
 pub struct GameRecord {
     pub player: Addr,
-    pub difficulty: Difficulty,
+    pub difficulty: Option<Difficulty>, // Set when the row count has a difficulty alias
+    #[serde(default)]
+    pub rows: u8,  // 0 for games recorded before the row count was stored
     pub risk_level: RiskLevel,
+    #[serde(default)]
+    pub denom: String, // Empty for games recorded before denoms were separated
     pub bet_amount: Uint128,
-    pub multiplier: String,
+    #[serde(deserialize_with = "legacy::multiplier")]
+    pub multiplier: Decimal,
     pub win_amount: Uint128,
-    pub pnl: Uint128, // Profit/Loss (win_amount - bet_amount)
+    pub pnl: Int128, // Profit/Loss (win_amount - bet_amount)
     pub timestamp: u64,
     pub path: Vec<bool>,
+    #[serde(default)]
+    pub nonce: u64, // Player game count used as the RNG nonce
+    #[serde(default)]
+    pub rng_version: u8, // 0 for games recorded before seed inputs were stored
+    #[serde(default)]
+    pub seed_inputs: Option<SeedInputs>,
+    #[serde(default)]
+    pub jackpot_contribution: Uint128, // Part of the bet moved into the jackpot
+    #[serde(default)]
+    pub jackpot_win: Uint128, // Paid from the jackpot on top of win_amount
+    #[serde(default)]
+    pub table_id: Option<u64>, // Payout table version paid on, None for games before versions
+}
+
+impl GameRecord {
+    /// Rows the game was played on
+    pub fn board_rows(&self) -> u8 {
+        stored_rows(self.rows, self.difficulty.as_ref())
+    }
+
+    /// Everything the game paid the player, the table win plus any jackpot
+    pub fn total_payout(&self) -> Result<Uint128, OverflowError> {
+        self.win_amount.checked_add(self.jackpot_win)
+    }
+}
+
+/// Row count of an entry that may predate rows being stored, when only its difficulty was
+pub fn stored_rows(rows: u8, difficulty: Option<&Difficulty>) -> u8 {
+    match (rows, difficulty) {
+        (0, Some(difficulty)) => get_rows(difficulty),
+        _ => rows,
+    }
+}
+
+#[cw_serde]
+pub struct VerifyGameResponse {
+    pub verified: bool, // Recomputed outcome matches the record and any signature or commitment checks out
+    pub rng_version: u8,
+    pub path: Vec<bool>,
+    pub bucket: u32,
+    pub multiplier: Decimal,
+    pub win_amount: Uint128,
 }
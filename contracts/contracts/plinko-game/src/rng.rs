@@ -1,41 +1,58 @@
-use cosmwasm_std::{Env, MessageInfo};
-use sha2::{Digest, Sha256};
+use cosmwasm_std::{Api, Decimal, Env, StdError, StdResult};
+use plinko_fairness::verify::{verify_game, RecordedGame, VerifyError};
 
-/// Generate provably fair random path for the ball
-/// Uses tx index, block height, timestamp, sender, and nonce for randomness
-pub fn generate_ball_path(env: &Env, info: &MessageInfo, nonce: u64, rows: u8) -> Vec<u8> {
-    let mut path = Vec::new();
+use crate::error::ContractError;
+use crate::msg::{GameRecord, SeedInputs, VerifyGameResponse};
 
-    // Create seed from multiple sources
-    let mut hasher = Sha256::new();
-    hasher.update(env.transaction.as_ref().map(|tx| tx.index.to_be_bytes()).unwrap_or([0u8; 4]));
-    hasher.update(env.block.height.to_be_bytes());
-    hasher.update(env.block.time.nanos().to_be_bytes());
-    hasher.update(info.sender.as_bytes());
-    hasher.update(nonce.to_be_bytes());
-    hasher.update(env.contract.address.as_bytes());
-    
-    if let Some(coin) = info.funds.first() {
-        hasher.update(coin.amount.u128().to_be_bytes());
-    }
-
-    let mut seed = hasher.finalize();
-
-    // Generate path (0 = left, 1 = right)
-    for _ in 0..rows {
-        let mut hasher = Sha256::new();
-        hasher.update(seed);
-        seed = hasher.finalize();
+pub use plinko_fairness::rng::{
+    calculate_bucket_index, commitment_seed, drand_round_at, drand_round_time, drand_seed,
+    generate_path_from_seed, hash_server_seed, operator_message, signature_seed, RNG_VERSION,
+};
+pub use plinko_fairness::verify::{derive_seed, verify_bls_signature, verify_drand_beacon};
 
-        // Use first byte to determine direction
-        let direction = if seed[0] % 2 == 0 { 0 } else { 1 };
-        path.push(direction);
+/// Seed inputs of a placed bet settled with the entropy of the current block, its settle height
+pub fn settle_block_seed_inputs(env: &Env, bet_id: u64, placed_height: u64) -> SeedInputs {
+    SeedInputs::SettleBlock {
+        bet_id,
+        placed_height,
+        height: env.block.height,
+        time_nanos: env.block.time.nanos(),
+        contract: env.contract.address.clone(),
     }
-
-    path
 }
 
-/// Calculate final bucket index from path
-pub fn calculate_bucket_index(path: &[u8]) -> usize {
-    path.iter().filter(|&&x| x == 1).count()
+/// Replay a recorded game with `plinko_fairness::verify::verify_game`, checking signatures
+/// with the chain's `api`
+pub fn verify_record(
+    api: &dyn Api,
+    record: &GameRecord,
+    commitment: Option<&[u8]>,
+    table: Option<&[Decimal]>,
+) -> StdResult<VerifyGameResponse> {
+    let game = RecordedGame {
+        player: record.player.as_str(),
+        nonce: record.nonce,
+        rows: record.board_rows(),
+        rng_version: record.rng_version,
+        bet_amount: record.bet_amount,
+        multiplier: record.multiplier,
+        win_amount: record.win_amount,
+        path: &record.path,
+        seed_inputs: record.seed_inputs.as_ref(),
+    };
+    let verification = verify_game(api, &game, commitment, table).map_err(|e| match e {
+        VerifyError::NoSeedInputs => {
+            StdError::generic_err("Game was recorded without seed inputs and cannot be verified")
+        }
+        VerifyError::Play(e) => StdError::generic_err(ContractError::from(e).to_string()),
+    })?;
+
+    Ok(VerifyGameResponse {
+        verified: verification.verified,
+        rng_version: record.rng_version,
+        path: verification.path,
+        bucket: verification.bucket as u32,
+        multiplier: verification.multiplier,
+        win_amount: verification.win_amount,
+    })
 }
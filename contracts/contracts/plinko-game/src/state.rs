@@ -1,67 +1,495 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Binary, Decimal, HexBinary, Int128, StdResult, Storage, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::msg::GameRecord;
+use crate::legacy;
+use crate::msg::{
+    CalendarPeriod, DrandConfig, GameRecord, JackpotConfig, LeaderboardType, RiskLevel,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
-    pub token_denom: String,
+    pub token_denom: String, // Default denom, always accepted
     pub admin: Addr,
     pub funder_address: Addr,
+    /// Blocks a placed bet must wait before it can be settled
+    #[serde(default = "default_settle_delay_blocks")]
+    pub settle_delay_blocks: u64,
+    /// Blocks after the settle height before an unsettled OperatorSignature bet can be refunded
+    #[serde(default = "default_refund_timeout_blocks")]
+    pub refund_timeout_blocks: u64,
+    /// BLS12-381 G2 key whose G1 signatures seed OperatorSignature bets
+    #[serde(default)]
+    pub operator_public_key: Option<Binary>,
+    /// drand network whose beacons seed Drand bets
+    #[serde(default)]
+    pub drand: Option<DrandConfig>,
+    /// Highest return to player a payout table set by the admin may have
+    #[serde(default = "default_max_rtp")]
+    pub max_rtp: Decimal,
+    /// Share of the house balance a single bet may win at its table's top multiplier
+    #[serde(default = "default_max_exposure")]
+    pub max_exposure: Decimal,
+    /// Progressive jackpot, off unless configured
+    #[serde(default)]
+    pub jackpot: Option<JackpotConfig>,
+    /// Minutes east of UTC that daily, weekly and monthly leaderboards restart at midnight in
+    #[serde(default)]
+    pub leaderboard_utc_offset_minutes: i16,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub const DEFAULT_SETTLE_DELAY_BLOCKS: u64 = 1;
+pub const DEFAULT_REFUND_TIMEOUT_BLOCKS: u64 = 100;
+pub const DEFAULT_MAX_RTP: Decimal = Decimal::one();
+pub const DEFAULT_MAX_EXPOSURE: Decimal = Decimal::one();
+
+fn default_settle_delay_blocks() -> u64 {
+    DEFAULT_SETTLE_DELAY_BLOCKS
+}
+
+fn default_refund_timeout_blocks() -> u64 {
+    DEFAULT_REFUND_TIMEOUT_BLOCKS
+}
+
+fn default_max_rtp() -> Decimal {
+    DEFAULT_MAX_RTP
+}
+
+fn default_max_exposure() -> Decimal {
+    DEFAULT_MAX_EXPOSURE
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
 pub struct Stats {
     pub total_games: u64,
     pub total_wagered: Uint128,
     pub total_won: Uint128,
     pub house_balance: Uint128,
+    /// Part of house_balance held back to cover the maximum payout of unsettled games
+    #[serde(default)]
+    pub reserved_balance: Uint128,
+    /// Player funds held by autobet sessions, not part of house_balance
+    #[serde(default)]
+    pub autobet_balance: Uint128,
+    /// Games won, lost and the largest single loss, counted from when they were first tracked
+    #[serde(default)]
+    pub wins: u64,
+    #[serde(default)]
+    pub losses: u64,
+    #[serde(default)]
+    pub biggest_loss: Uint128,
+    /// Pool of the progressive jackpot, not part of house_balance
+    #[serde(default)]
+    pub jackpot_balance: Uint128,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
 pub struct UserStats {
     pub total_games: u64,
     pub total_wagered: Uint128,
     pub total_won: Uint128,
     pub best_win_pnl: Uint128,
-    pub best_win_multiplier: String,
+    #[serde(deserialize_with = "legacy::multiplier")]
+    pub best_win_multiplier: Decimal,
+    #[serde(default)]
+    pub best_win_rows: u8,
+    /// Games won, lost and the largest single loss, counted from when they were first tracked
+    #[serde(default)]
+    pub wins: u64,
+    #[serde(default)]
+    pub losses: u64,
+    #[serde(default)]
+    pub biggest_loss: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct LeaderboardEntry {
     pub player: Addr,
-    pub value: Uint128,
-    pub multiplier: Option<String>,
+    pub value: Int128, // Negative only on NetProfit
+    #[serde(default, deserialize_with = "legacy::optional_multiplier")]
+    pub multiplier: Option<Decimal>,
+    #[serde(default)]
+    pub rows: Option<u8>,
 }
 
+/// A player's entry on one leaderboard, see [`leaderboard_entries`]
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct DailyLeaderboard {
-    pub last_reset: u64, // Timestamp of last reset
+pub struct RankedEntry {
+    pub board: String, // See board_id
+    pub entry: LeaderboardEntry,
+}
+
+pub struct LeaderboardIndexes<'a> {
+    // Entries of a board by (board, value), ties broken by player
+    pub value: MultiIndex<'a, (String, i128), RankedEntry, (&'a str, &'a Addr)>,
+}
+
+impl IndexList<RankedEntry> for LeaderboardIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<RankedEntry>> + '_> {
+        let indexes: Vec<&dyn Index<RankedEntry>> = vec![&self.value];
+        Box::new(indexes.into_iter())
+    }
+}
+
+/// Entries of every leaderboard keyed by (board_id, player), ranked through the value index
+pub fn leaderboard_entries<'a>(
+) -> IndexedMap<(&'a str, &'a Addr), RankedEntry, LeaderboardIndexes<'a>> {
+    let indexes = LeaderboardIndexes {
+        value: MultiIndex::new(
+            |_pk, ranked| (ranked.board.clone(), ranked.entry.value.i128()),
+            "leaderboard_entries",
+            "leaderboard_entries__value",
+        ),
+    };
+    IndexedMap::new("leaderboard_entries", indexes)
+}
+
+/// A leaderboard of one denom and type: all time, or one period
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoardScope {
+    AllTime,
+    Period(PeriodStore, u64), // Period index
+}
+
+/// Key of a leaderboard in [`leaderboard_entries`]. The denom comes last, as it may contain '/'.
+pub fn board_id(scope: BoardScope, leaderboard_type: &LeaderboardType, denom: &str) -> String {
+    let leaderboard_type = leaderboard_key(leaderboard_type);
+    match scope {
+        BoardScope::AllTime => format!("all/{leaderboard_type}/{denom}"),
+        BoardScope::Period(store, index) => {
+            format!("{}/{index}/{leaderboard_type}/{denom}", store.key())
+        }
+    }
+}
+
+/// A period with games, whose boards are kept in [`leaderboard_entries`]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PeriodLeaderboard {
+    pub last_reset: u64, // Timestamp of the period's first game
+}
+
+/// The UTC day in progress as the original contract stored it, with its sorted boards
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct LegacyDailyLeaderboard {
+    pub last_reset: u64,
     pub entries_best_wins: Vec<LeaderboardEntry>,
     pub entries_wagered: Vec<LeaderboardEntry>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
-pub struct DailyPlayerStats {
+pub struct PeriodPlayerStats {
     pub total_wagered: Uint128,
+    #[serde(default)]
+    pub total_won: Uint128,
     pub best_win_pnl: Uint128,
-    pub best_win_multiplier: String,
+    #[serde(deserialize_with = "legacy::multiplier")]
+    pub best_win_multiplier: Decimal,
+    #[serde(default)]
+    pub best_win_rows: u8,
+}
+
+/// A competition set by the admin, with its own leaderboards from `start` until `end`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Season {
+    pub name: String,
+    pub start: u64, // Seconds since the Unix epoch
+    pub end: u64,   // Exclusive
+}
+
+impl Season {
+    pub fn is_active(&self, now: u64) -> bool {
+        (self.start..self.end).contains(&now)
+    }
+}
+
+/// Where a kind of period keeps its leaderboards and player stats, keyed by denom and the
+/// period's index: days, weeks or months since the Unix epoch, or the season id
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeriodStore {
+    Daily,
+    Weekly,
+    Monthly,
+    Season,
+}
+
+impl PeriodStore {
+    pub fn key(self) -> &'static str {
+        match self {
+            PeriodStore::Daily => "daily",
+            PeriodStore::Weekly => "weekly",
+            PeriodStore::Monthly => "monthly",
+            PeriodStore::Season => "season",
+        }
+    }
+
+    pub fn leaderboards<'a>(self) -> Map<(&'a str, u64), PeriodLeaderboard> {
+        match self {
+            // Named when it only held past days
+            PeriodStore::Daily => Map::new("daily_leaderboard_archive"),
+            PeriodStore::Weekly => Map::new("weekly_leaderboards"),
+            PeriodStore::Monthly => Map::new("monthly_leaderboards"),
+            PeriodStore::Season => Map::new("season_leaderboards"),
+        }
+    }
+
+    pub fn player_stats<'a>(self) -> Map<(&'a str, u64, &'a Addr), PeriodPlayerStats> {
+        match self {
+            PeriodStore::Daily => Map::new("daily_player_stats_by_day"),
+            PeriodStore::Weekly => Map::new("weekly_player_stats"),
+            PeriodStore::Monthly => Map::new("monthly_player_stats"),
+            PeriodStore::Season => Map::new("season_player_stats"),
+        }
+    }
+}
+
+impl From<CalendarPeriod> for PeriodStore {
+    fn from(period: CalendarPeriod) -> Self {
+        match period {
+            CalendarPeriod::Daily => PeriodStore::Daily,
+            CalendarPeriod::Weekly => PeriodStore::Weekly,
+            CalendarPeriod::Monthly => PeriodStore::Monthly,
+        }
+    }
+}
+
+/// Hash of a server seed committed by the operator before any game uses it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SeedCommitment {
+    pub id: u64,
+    pub commitment: HexBinary,          // sha256(server_seed)
+    pub server_seed: Option<HexBinary>, // Set once revealed
+    pub committed_at: u64,
+    pub revealed_at: Option<u64>,
+}
+
+/// A bet placed against a commitment, settled when its server seed is revealed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingGame {
+    pub rows: u8,
+    pub denom: String,
+    pub risk_level: RiskLevel,
+    pub bet_amount: Uint128,
+    pub max_payout: Uint128, // Reserved from the house balance until settled
+    pub table_id: u64,       // Payout table version in place when the game was placed
+    pub client_seed: String,
+    pub placed_at: u64,
+    pub refund_height: u64, // Refundable from this block while the seed is unrevealed
+}
+
+/// Where a pending bet gets its randomness from, fixed when the bet is placed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum BetEntropy {
+    /// Height and time of the block at the settle height, which every settle transaction in that
+    /// block sees the same. Settled by anyone in that block only, and refunded after it.
+    #[default]
+    SettleBlock,
+    /// Operator BLS signature over (player, nonce, bet id), checked against the key at placement
+    /// and submitted by anyone. A BLS signature is unique per key and message, so the operator
+    /// cannot pick the outcome, only withhold it, which pays the player the reserved maximum
+    /// payout once the bet expires.
+    OperatorSignature { public_key: Binary },
+    /// Beacon of a drand round published after placement, checked against the key at placement.
+    /// Settled by anyone, and cancelled by the player only before `round_time`.
+    Drand {
+        round: u64,
+        round_time: u64,
+        public_key: HexBinary,
+    },
+}
+
+/// A bet escrowed by PlaceBet and settled with entropy from a later block
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingBet {
+    pub player: Addr,
+    pub rows: u8,
+    pub denom: String,
+    pub risk_level: RiskLevel,
+    pub bet_amount: Uint128,
+    pub max_payout: Uint128, // Reserved from the house balance until settled or refunded
+    pub table_id: u64,       // Payout table version in place when the bet was placed
+    pub nonce: u64,
+    pub placed_height: u64,
+    pub placed_at: u64,
+    #[serde(default)]
+    pub entropy: BetEntropy,
+    pub settle_height: u64,         // First block the bet can be settled in
+    pub refund_height: Option<u64>, // First block the bet has expired in, never for Drand bets
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const STATS: Item<Stats> = Item::new("stats");
 pub const GAME_HISTORY: Map<(&Addr, u64), GameRecord> = Map::new("game_history");
 pub const PLAYER_GAME_COUNT: Map<&Addr, u64> = Map::new("player_game_count");
 
-// User statistics
-pub const USER_STATS: Map<&Addr, UserStats> = Map::new("user_stats");
+// Accepted denoms and their bet bounds, which apply on every board
+pub const DENOMS: Map<&str, BetLimits> = Map::new("denoms");
+
+// House balance and statistics per denom
+pub const STATS: Map<&str, Stats> = Map::new("denom_stats");
+pub const USER_STATS: Map<(&str, &Addr), UserStats> = Map::new("denom_user_stats");
+
+// Daily, weekly, monthly and season leaderboards are kept in the maps of their PeriodStore
 
-// Global leaderboards (all-time)
-pub const GLOBAL_BEST_WINS: Item<Vec<LeaderboardEntry>> = Item::new("global_best_wins");
-pub const GLOBAL_TOTAL_WAGERED: Item<Vec<LeaderboardEntry>> = Item::new("global_total_wagered");
+// Admin-defined seasons, and the ids of those not yet ended
+pub const SEASONS: Map<u64, Season> = Map::new("seasons");
+pub const OPEN_SEASONS: Item<Vec<u64>> = Item::new("open_seasons");
+pub const NEXT_SEASON_ID: Item<u64> = Item::new("next_season_id");
 
-// Daily leaderboard (resets at 00:00 UTC)
-pub const DAILY_LEADERBOARD: Item<DailyLeaderboard> = Item::new("daily_leaderboard");
-pub const DAILY_PLAYER_STATS: Map<&Addr, DailyPlayerStats> = Map::new("daily_player_stats");
+// Where the default denom's entries were kept before denoms were separated.
+// They are only read, until the first write under the new keys shadows them.
+pub const LEGACY_STATS: Item<Stats> = Item::new("stats");
+pub const LEGACY_USER_STATS: Map<&Addr, UserStats> = Map::new("user_stats");
+pub const LEGACY_DAILY_PLAYER_STATS: Map<&Addr, PeriodPlayerStats> = Map::new("daily_player_stats");
+
+// The original contract's sorted boards, moved to leaderboard_entries by migrate
+pub const LEGACY_BEST_WINS: Item<Vec<LeaderboardEntry>> = Item::new("global_best_wins");
+pub const LEGACY_TOTAL_WAGERED: Item<Vec<LeaderboardEntry>> = Item::new("global_total_wagered");
+pub const LEGACY_DAILY_LEADERBOARD: Item<LegacyDailyLeaderboard> = Item::new("daily_leaderboard");
+
+// Commit-reveal server seeds
+pub const SEED_COMMITMENTS: Map<u64, SeedCommitment> = Map::new("seed_commitments");
+pub const NEXT_COMMITMENT_ID: Item<u64> = Item::new("next_commitment_id");
+pub const ACTIVE_COMMITMENT: Item<u64> = Item::new("active_commitment");
+// Unsettled games keyed by (commitment id, player, nonce)
+pub const PENDING_GAMES: Map<(u64, &Addr, u64), PendingGame> = Map::new("pending_games");
+
+/// An autobet session, funded by the player and advanced in batches by the player or a keeper
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AutobetSession {
+    pub rows: u8,
+    pub denom: String,
+    pub risk_level: RiskLevel,
+    pub bet_amount: Uint128, // Per drop
+    pub table_id: u64,       // Payout table version every drop is paid on
+    pub deposit: Uint128,
+    pub balance: Uint128, // Deposit plus winnings minus bets so far
+    pub drops_remaining: u32,
+    pub drops_played: u32,
+    pub stop_loss: Option<Uint128>, // Stop once this much of the deposit is lost
+    pub take_profit: Option<Uint128>, // Stop once the balance is this much above the deposit
+    pub started_at: u64,
+    pub commitment_id: u64, // Server seed commitment every drop is settled with
+    pub client_seed: String,
+    pub first_nonce: u64, // Nonces first_nonce.. are reserved for the drops
+}
+
+/// Payout multipliers, one per bucket
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(transparent)]
+pub struct PayoutTable(pub Vec<Decimal>);
+
+// Payout tables keyed by (rows, risk level)
+pub const PAYOUT_TABLES: Map<(u8, &str), PayoutTable> = Map::new("payout_tables");
+// Every payout table ever put in place, by id, so games verify against the table they were paid on
+pub const PAYOUT_TABLE_VERSIONS: Map<u64, PayoutTable> = Map::new("payout_table_versions");
+// Id of the table in PAYOUT_TABLES, keyed the same way
+pub const PAYOUT_TABLE_IDS: Map<(u8, &str), u64> = Map::new("payout_table_ids");
+pub const NEXT_PAYOUT_TABLE_ID: Item<u64> = Item::new("next_payout_table_id");
+// Unsettled games, bets and autobet sessions on each table version, which cannot be removed while
+// any are left
+pub const PAYOUT_TABLE_HOLDS: Map<u64, u64> = Map::new("payout_table_holds");
+
+/// Bet size bounds of a board and risk level, per ball or drop
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct BetLimits {
+    pub min_bet: Option<Uint128>,
+    pub max_bet: Option<Uint128>,
+}
+
+// Bet limits keyed by (denom, rows, risk level), boards without an entry are only capped by
+// the denom's limits and exposure
+pub const BET_LIMITS: Map<(&str, u8, &str), BetLimits> = Map::new("denom_bet_limits");
+
+/// Storage key of a risk level in PAYOUT_TABLES
+pub fn risk_key(risk_level: &RiskLevel) -> &'static str {
+    match risk_level {
+        RiskLevel::Low => "low",
+        RiskLevel::Medium => "medium",
+        RiskLevel::High => "high",
+    }
+}
+
+/// Name of a leaderboard type in board ids
+pub fn leaderboard_key(leaderboard_type: &LeaderboardType) -> &'static str {
+    match leaderboard_type {
+        LeaderboardType::BestWins => "best_wins",
+        LeaderboardType::TotalWagered => "total_wagered",
+        LeaderboardType::NetProfit => "net_profit",
+    }
+}
+
+/// Inverse of `risk_key`
+pub fn risk_from_key(key: &str) -> Option<RiskLevel> {
+    match key {
+        "low" => Some(RiskLevel::Low),
+        "medium" => Some(RiskLevel::Medium),
+        "high" => Some(RiskLevel::High),
+        _ => None,
+    }
+}
+
+// One autobet session per player
+pub const AUTOBET_SESSIONS: Map<&Addr, AutobetSession> = Map::new("autobet_sessions");
+
+// Two-phase bets keyed by bet id
+pub const PENDING_BETS: Map<u64, PendingBet> = Map::new("pending_bets");
+pub const NEXT_BET_ID: Item<u64> = Item::new("next_bet_id");
+
+/// Prefix of the denom a CW20 token is kept under, followed by the token contract address
+pub const CW20_DENOM_PREFIX: &str = "cw20:";
+
+/// Denom of a CW20 token
+pub fn cw20_denom(token: &Addr) -> String {
+    format!("{CW20_DENOM_PREFIX}{token}")
+}
+
+/// Token contract of a CW20 denom, None for native denoms
+pub fn cw20_address(denom: &str) -> Option<&str> {
+    denom.strip_prefix(CW20_DENOM_PREFIX)
+}
+
+/// Denom of an entry that may predate denoms being stored, when only the default denom existed
+pub fn stored_denom<'a>(denom: &'a str, config: &'a Config) -> &'a str {
+    if denom.is_empty() {
+        &config.token_denom
+    } else {
+        denom
+    }
+}
+
+/// Value kept under a denom, falling back for the default denom to its legacy entry
+fn or_legacy<T>(
+    config: &Config,
+    denom: &str,
+    current: Option<T>,
+    legacy: impl FnOnce() -> StdResult<Option<T>>,
+) -> StdResult<Option<T>> {
+    match current {
+        None if denom == config.token_denom => legacy(),
+        current => Ok(current),
+    }
+}
+
+/// Statistics of a denom, zero before its first bet or funding
+pub fn load_stats(storage: &dyn Storage, config: &Config, denom: &str) -> StdResult<Stats> {
+    let stats = or_legacy(config, denom, STATS.may_load(storage, denom)?, || {
+        LEGACY_STATS.may_load(storage)
+    })?;
+    Ok(stats.unwrap_or_default())
+}
+
+pub fn load_user_stats(
+    storage: &dyn Storage,
+    config: &Config,
+    denom: &str,
+    player: &Addr,
+) -> StdResult<UserStats> {
+    let user_stats = or_legacy(
+        config,
+        denom,
+        USER_STATS.may_load(storage, (denom, player))?,
+        || LEGACY_USER_STATS.may_load(storage, player),
+    )?;
+    Ok(user_stats.unwrap_or_default())
+}
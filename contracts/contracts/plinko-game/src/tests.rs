@@ -1,16 +1,45 @@
 #[cfg(test)]
 mod tests {
-    use crate::contract::{execute, instantiate, query};
+    use crate::contract::{execute, instantiate, migrate, query};
     use crate::error::ContractError;
+    use crate::leaderboard::{day_index, import_leaderboard};
     use crate::msg::{
-        ConfigResponse, Difficulty, ExecuteMsg, HistoryResponse, InstantiateMsg,
-        LeaderboardResponse, LeaderboardType, QueryMsg, RiskLevel, StatsResponse,
-        UserStatsResponse,
+        AutobetSessionResponse, BetLimitsResponse, CalendarPeriod, ConfigResponse,
+        DailyLeaderboardDaysResponse, DenomsResponse, Difficulty, DrandConfig, ExecuteMsg,
+        GameOddsResponse, HistoryResponse, InstantiateMsg, JackpotConfig, JackpotResponse,
+        JackpotTrigger, LeaderboardCursor, LeaderboardEntry as MsgLeaderboardEntry,
+        LeaderboardPeriod, LeaderboardPeriodsResponse, LeaderboardResponse, LeaderboardType,
+        MigrateMsg, PayoutTableResponse, PayoutTableVersionResponse, PayoutTablesResponse,
+        PendingBetResponse, PendingBetsResponse, PeriodLeaderboardResponse, PeriodResponse,
+        PlayerRankResponse, QueryMsg, RandomnessMode, ReceiveMsg, RiskLevel, SeasonsResponse,
+        SeedCommitmentResponse, SeedInputs, StatsResponse, UserStatsResponse, VerifyGameResponse,
+    };
+    use crate::rng::{
+        commitment_seed, derive_seed, drand_seed, generate_path_from_seed, hash_server_seed,
+        operator_message, signature_seed, verify_bls_signature, verify_drand_beacon, RNG_VERSION,
+    };
+    use crate::state::{
+        board_id, BoardScope, LeaderboardEntry, PayoutTable, PeriodPlayerStats, PeriodStore,
+        CONFIG, DEFAULT_REFUND_TIMEOUT_BLOCKS, DEFAULT_SETTLE_DELAY_BLOCKS, GAME_HISTORY,
+        LEGACY_BEST_WINS, LEGACY_DAILY_LEADERBOARD, LEGACY_DAILY_PLAYER_STATS, LEGACY_STATS,
+        LEGACY_TOTAL_WAGERED, LEGACY_USER_STATS, PAYOUT_TABLE_HOLDS, PAYOUT_TABLE_IDS,
+        PAYOUT_TABLE_VERSIONS, PLAYER_GAME_COUNT, STATS, USER_STATS,
     };
     use cosmwasm_std::testing::{message_info, mock_env, MockApi, MockQuerier, MockStorage};
     use cosmwasm_std::{
-        coin, coins, from_json, Addr, BankMsg, DepsMut, OwnedDeps, Response, Uint128,
+        coin, coins, from_json, to_json_binary, Addr, Api, BankMsg, Binary, ContractResult,
+        Decimal, Deps, DepsMut, Env, HashFunction, HexBinary, Int128, MessageInfo, OwnedDeps,
+        Response, SignedDecimal, SystemResult, Uint128, WasmMsg, WasmQuery,
     };
+    use cw20::{
+        BalanceResponse as Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg,
+    };
+    use plinko_fairness::play;
+    use plinko_fairness::rng::{BLS_G1_DST, RNG_V1_REHASH};
+
+    use ark_bls12_381::{Fr, G1Affine, G2Affine};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
 
     const TOKEN_DENOM: &str = "factory/inj1contract/plink";
 
@@ -30,18 +59,58 @@ mod tests {
         let msg = InstantiateMsg {
             token_denom: TOKEN_DENOM.to_string(),
             funder_address: admin.to_string(),
+            settle_delay_blocks: None,
+            refund_timeout_blocks: None,
+            max_rtp: None,
+            max_exposure: None,
         };
 
         let info = message_info(admin, &[]);
         instantiate(deps, mock_env(), info, msg)
     }
 
+    /// Values of `key` on every game a settlement settled, in order
+    fn settled(res: &Response, key: &str) -> Vec<String> {
+        res.events
+            .iter()
+            .filter(|event| event.ty == "settle_game")
+            .flat_map(|event| event.attributes.iter().filter(|attr| attr.key == key))
+            .map(|attr| attr.value.clone())
+            .collect()
+    }
+
+    /// Settle a Play the way the chain does: commit a server seed, play against it, reveal it
+    /// and settle its games. Returns the responses of the play and of the settlement.
+    fn play_settled(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        env: Env,
+        info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> Result<(Response, Response), ContractError> {
+        let admin = CONFIG.load(&deps.storage).unwrap().admin;
+        let server_seed = b"test server seed";
+        let commitment_id = commit_seed(deps.as_mut(), &admin, server_seed);
+
+        let play = execute(deps.as_mut(), env.clone(), info, msg)?;
+        let msg = ExecuteMsg::RevealServerSeed {
+            commitment_id,
+            server_seed: HexBinary::from(server_seed.to_vec()),
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&admin, &[]), msg)?;
+        let msg = ExecuteMsg::SettleGames {
+            commitment_id,
+            limit: Some(100),
+        };
+        let settle = execute(deps.as_mut(), env, message_info(&admin, &[]), msg)?;
+        Ok((play, settle))
+    }
+
     fn fund_contract(deps: DepsMut, amount: Uint128) {
         // Simulate contract receiving tokens (e.g., from purchase contract's fund_house)
         // In real scenario, this would be done via BankMsg from purchase contract
-        let mut stats = crate::state::STATS.load(deps.storage).unwrap();
+        let mut stats = STATS.load(deps.storage, TOKEN_DENOM).unwrap();
         stats.house_balance = stats.house_balance.checked_add(amount).unwrap();
-        crate::state::STATS.save(deps.storage, &stats).unwrap();
+        STATS.save(deps.storage, TOKEN_DENOM, &stats).unwrap();
     }
 
     #[test]
@@ -62,7 +131,7 @@ mod tests {
         assert_eq!(config.admin, admin);
 
         // Check stats
-        let query_msg = QueryMsg::Stats {};
+        let query_msg = QueryMsg::Stats { denom: None };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let stats: StatsResponse = from_json(&res).unwrap();
 
@@ -79,20 +148,23 @@ mod tests {
         let player = deps.api.addr_make("player");
         setup_contract(deps.as_mut(), &admin).unwrap();
         // Fund contract (simulating purchase contract's fund_house)
-        fund_contract(deps.as_mut(), Uint128::new(100_000_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(100_000_000_000_000_000_000_000));
 
         let msg = ExecuteMsg::Play {
-            difficulty: Difficulty::Easy,
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
             risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
         };
         let info = message_info(&player, &coins(100_000000000000000000, TOKEN_DENOM));
-        let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let (_, res) = play_settled(&mut deps, mock_env(), info, msg).unwrap();
 
         // Should have 1 message for sending winnings (if any)
         assert!(res.messages.len() <= 1);
 
         // Check stats updated
-        let query_msg = QueryMsg::Stats {};
+        let query_msg = QueryMsg::Stats { denom: None };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let stats: StatsResponse = from_json(&res).unwrap();
 
@@ -103,6 +175,7 @@ mod tests {
         // Check user stats
         let query_msg = QueryMsg::UserStats {
             player: player.to_string(),
+            denom: None,
         };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let user_stats: UserStatsResponse = from_json(&res).unwrap();
@@ -122,8 +195,11 @@ mod tests {
 
         setup_contract(deps.as_mut(), &admin).unwrap();
         let msg = ExecuteMsg::Play {
-            difficulty: Difficulty::Easy,
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
             risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
         };
         let info = message_info(&player, &[]);
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
@@ -139,13 +215,21 @@ mod tests {
 
         setup_contract(deps.as_mut(), &admin).unwrap();
         let msg = ExecuteMsg::Play {
-            difficulty: Difficulty::Easy,
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
             risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
         };
         let info = message_info(&player, &coins(100, "wrong_denom"));
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
 
-        assert!(matches!(err, ContractError::InvalidBetAmount {}));
+        assert_eq!(
+            err,
+            ContractError::UnsupportedDenom {
+                denom: "wrong_denom".to_string()
+            }
+        );
     }
 
     #[test]
@@ -154,21 +238,24 @@ mod tests {
         let admin = deps.api.addr_make("admin");
         setup_contract(deps.as_mut(), &admin).unwrap();
         // Fund contract
-        fund_contract(deps.as_mut(), Uint128::new(500_000_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(500_000_000_000_000_000_000_000));
 
         // Play multiple games with different players
         for i in 0..5 {
             let player = Addr::unchecked(format!("player{}", i));
             let msg = ExecuteMsg::Play {
-                difficulty: Difficulty::Easy,
+                difficulty: Some(Difficulty::Easy),
+                rows: None,
                 risk_level: RiskLevel::Low,
+                client_seed: None,
+                ball_count: None,
             };
             let info = message_info(&player, &coins(100_000000000000000000, TOKEN_DENOM));
-            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            play_settled(&mut deps, mock_env(), info, msg).unwrap();
         }
 
         // Check stats
-        let query_msg = QueryMsg::Stats {};
+        let query_msg = QueryMsg::Stats { denom: None };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let stats: StatsResponse = from_json(&res).unwrap();
 
@@ -182,23 +269,27 @@ mod tests {
         let admin = deps.api.addr_make("admin");
         setup_contract(deps.as_mut(), &admin).unwrap();
         // Fund contract
-        fund_contract(deps.as_mut(), Uint128::new(500_000_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(500_000_000_000_000_000_000_000));
 
         // Play multiple games with different players
         for i in 0..5 {
             let player = Addr::unchecked(format!("player{}", i));
             let msg = ExecuteMsg::Play {
-                difficulty: Difficulty::Easy,
+                difficulty: Some(Difficulty::Easy),
+                rows: None,
                 risk_level: RiskLevel::Low,
+                client_seed: None,
+                ball_count: None,
             };
             let info = message_info(&player, &coins(100_000000000000000000, TOKEN_DENOM));
-            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            play_settled(&mut deps, mock_env(), info, msg).unwrap();
         }
 
         // Query global leaderboard
         let query_msg = QueryMsg::GlobalLeaderboard {
             leaderboard_type: LeaderboardType::BestWins,
             limit: Some(10),
+            denom: None,
         };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
@@ -216,7 +307,7 @@ mod tests {
         let admin = deps.api.addr_make("admin");
         setup_contract(deps.as_mut(), &admin).unwrap();
         // Fund contract
-        fund_contract(deps.as_mut(), Uint128::new(500_000_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(500_000_000_000_000_000_000_000));
 
         let player1 = Addr::unchecked("player1");
         let player2 = Addr::unchecked("player2");
@@ -224,25 +315,32 @@ mod tests {
         // Player 1 plays multiple games
         for _ in 0..3 {
             let msg = ExecuteMsg::Play {
-                difficulty: Difficulty::Easy,
+                difficulty: Some(Difficulty::Easy),
+                rows: None,
                 risk_level: RiskLevel::Low,
+                client_seed: None,
+                ball_count: None,
             };
             let info = message_info(&player1, &coins(100_000000000000000000, TOKEN_DENOM));
-            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            play_settled(&mut deps, mock_env(), info, msg).unwrap();
         }
 
         // Player 2 plays one game
         let msg = ExecuteMsg::Play {
-            difficulty: Difficulty::Easy,
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
             risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
         };
         let info = message_info(&player2, &coins(50_000000000000000000, TOKEN_DENOM));
-        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        play_settled(&mut deps, mock_env(), info, msg).unwrap();
 
         // Query global leaderboard
         let query_msg = QueryMsg::GlobalLeaderboard {
             leaderboard_type: LeaderboardType::TotalWagered,
             limit: Some(10),
+            denom: None,
         };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
@@ -252,7 +350,7 @@ mod tests {
         assert_eq!(leaderboard.entries[0].player, player1);
         assert_eq!(
             leaderboard.entries[0].value,
-            Uint128::new(300_000000000000000000)
+            Int128::new(300_000000000000000000)
         );
     }
 
@@ -262,24 +360,28 @@ mod tests {
         let admin = deps.api.addr_make("admin");
         setup_contract(deps.as_mut(), &admin).unwrap();
         // Fund contract
-        fund_contract(deps.as_mut(), Uint128::new(500_000_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(500_000_000_000_000_000_000_000));
 
         let player1 = Addr::unchecked("player1");
         let player2 = Addr::unchecked("player2");
 
         // Play a game
         let msg = ExecuteMsg::Play {
-            difficulty: Difficulty::Easy,
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
             risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
         };
         let info = message_info(&player1, &coins(100_000000000000000000, TOKEN_DENOM));
         let mut env = mock_env();
-        execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap();
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
 
         // Query daily leaderboard
         let query_msg = QueryMsg::DailyLeaderboard {
             leaderboard_type: LeaderboardType::TotalWagered,
             limit: Some(10),
+            denom: None,
         };
         let res = query(deps.as_ref(), env.clone(), query_msg.clone()).unwrap();
         let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
@@ -295,12 +397,13 @@ mod tests {
 
         // Play another game after reset
         let info = message_info(&player2, &coins(100_000000000000000000, TOKEN_DENOM));
-        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        play_settled(&mut deps, env.clone(), info, msg).unwrap();
 
         // Query again - should have new entry
         let query_msg = QueryMsg::DailyLeaderboard {
             leaderboard_type: LeaderboardType::TotalWagered,
             limit: Some(10),
+            denom: None,
         };
         let res = query(deps.as_ref(), env, query_msg).unwrap();
         let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
@@ -316,21 +419,25 @@ mod tests {
 
         setup_contract(deps.as_mut(), &admin).unwrap();
         // Fund contract
-        fund_contract(deps.as_mut(), Uint128::new(500_000_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(500_000_000_000_000_000_000_000));
 
         // Play multiple games
         for _ in 0..3 {
             let msg = ExecuteMsg::Play {
-                difficulty: Difficulty::Easy,
+                difficulty: Some(Difficulty::Easy),
+                rows: None,
                 risk_level: RiskLevel::Low,
+                client_seed: None,
+                ball_count: None,
             };
             let info = message_info(&player, &coins(100_000000000000000000, TOKEN_DENOM));
-            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            play_settled(&mut deps, mock_env(), info, msg).unwrap();
         }
 
         // Query user stats
         let query_msg = QueryMsg::UserStats {
             player: player.to_string(),
+            denom: None,
         };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let user_stats: UserStatsResponse = from_json(&res).unwrap();
@@ -341,7 +448,7 @@ mod tests {
             Uint128::new(300_000000000000000000)
         );
         assert!(user_stats.best_win_pnl >= Uint128::zero());
-        assert_ne!(user_stats.best_win_multiplier, "0.0x");
+        assert!(user_stats.best_win_multiplier <= Decimal::from_ratio(1000u128, 1u128));
     }
 
     #[test]
@@ -352,19 +459,22 @@ mod tests {
 
         setup_contract(deps.as_mut(), &admin).unwrap();
         // Fund contract
-        fund_contract(deps.as_mut(), Uint128::new(150_000_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(150_000_000_000_000_000_000_000));
 
         // Play multiple games
         for i in 0..5 {
             let msg = ExecuteMsg::Play {
-                difficulty: Difficulty::Easy,
+                difficulty: Some(Difficulty::Easy),
+                rows: None,
                 risk_level: RiskLevel::Low,
+                client_seed: None,
+                ball_count: None,
             };
             let info = message_info(
                 &player,
                 &coins((i + 1) * 10_000000000000000000, TOKEN_DENOM),
             );
-            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            play_settled(&mut deps, mock_env(), info, msg).unwrap();
         }
 
         // Query history
@@ -384,8 +494,12 @@ mod tests {
                 game.bet_amount,
                 Uint128::new((i as u128 + 1) * 10_000000000000000000)
             );
-            // PnL should be calculated (win_amount - bet_amount)
-            assert_eq!(game.pnl, game.win_amount.saturating_sub(game.bet_amount));
+            // PnL should be calculated (win_amount - bet_amount), negative on a loss
+            assert_eq!(
+                game.pnl,
+                Int128::try_from(game.win_amount).unwrap()
+                    - Int128::try_from(game.bet_amount).unwrap()
+            );
         }
     }
 
@@ -397,20 +511,23 @@ mod tests {
 
         setup_contract(deps.as_mut(), &admin).unwrap();
         // Fund contract
-        fund_contract(deps.as_mut(), Uint128::new(500_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
 
         // Play some games to build house balance
         for _ in 0..5 {
             let msg = ExecuteMsg::Play {
-                difficulty: Difficulty::Easy,
+                difficulty: Some(Difficulty::Easy),
+                rows: None,
                 risk_level: RiskLevel::Low,
+                client_seed: None,
+                ball_count: None,
             };
             let info = message_info(&player, &coins(100_000000000000000000, TOKEN_DENOM));
-            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            play_settled(&mut deps, mock_env(), info, msg).unwrap();
         }
 
         // Get house balance
-        let query_msg = QueryMsg::Stats {};
+        let query_msg = QueryMsg::Stats { denom: None };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let stats: StatsResponse = from_json(&res).unwrap();
         let house_balance = stats.house_balance;
@@ -421,6 +538,7 @@ mod tests {
             let withdraw_amount = house_balance.checked_div(Uint128::new(2)).unwrap();
             let msg = ExecuteMsg::WithdrawHouse {
                 amount: withdraw_amount,
+                denom: None,
             };
             let info = message_info(&admin, &[]);
             let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -437,7 +555,7 @@ mod tests {
             }
 
             // Check updated balance
-            let query_msg = QueryMsg::Stats {};
+            let query_msg = QueryMsg::Stats { denom: None };
             let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
             let stats: StatsResponse = from_json(&res).unwrap();
             assert_eq!(
@@ -454,6 +572,7 @@ mod tests {
         setup_contract(deps.as_mut(), &admin).unwrap();
         let msg = ExecuteMsg::WithdrawHouse {
             amount: Uint128::new(1000_000000000000000000),
+            denom: None,
         };
         let info = message_info(&admin, &[]);
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
@@ -469,6 +588,7 @@ mod tests {
         let player = Addr::unchecked("player");
         let msg = ExecuteMsg::WithdrawHouse {
             amount: Uint128::new(100_000000000000000000),
+            denom: None,
         };
         let info = message_info(&player, &[]);
         let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
@@ -489,15 +609,18 @@ mod tests {
         // Play game where house should profit (low multiplier expected on average)
         for _ in 0..10 {
             let msg = ExecuteMsg::Play {
-                difficulty: Difficulty::Easy,
+                difficulty: Some(Difficulty::Easy),
+                rows: None,
                 risk_level: RiskLevel::Low,
+                client_seed: None,
+                ball_count: None,
             };
             let info = message_info(&player, &coins(100_000000000000000000, TOKEN_DENOM));
-            execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            play_settled(&mut deps, mock_env(), info, msg).unwrap();
         }
 
         // Check stats
-        let query_msg = QueryMsg::Stats {};
+        let query_msg = QueryMsg::Stats { denom: None };
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let stats: StatsResponse = from_json(&res).unwrap();
 
@@ -509,71 +632,754 @@ mod tests {
 
         assert_eq!(stats.house_balance, expected_house_balance);
     }
-    
+
     #[test]
     fn test_daily_leaderboard_logic_and_reset() {
         let mut deps = mock_deps();
         let admin = deps.api.addr_make("admin");
         setup_contract(deps.as_mut(), &admin).unwrap();
-        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
-    
+        fund_contract(deps.as_mut(), Uint128::new(2000_000000000000000000));
+
         let player1 = Addr::unchecked("player1");
         let player2 = Addr::unchecked("player2");
         let mut env = mock_env();
-    
+
         // --- Day 1 ---
         // Player 1 plays, wagering 100
-        let msg = ExecuteMsg::Play { difficulty: Difficulty::Easy, risk_level: RiskLevel::Low };
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
         let info = message_info(&player1, &coins(100_000000000000000000, TOKEN_DENOM));
-        execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap();
-    
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+
         // Player 1 plays again, wagering 50
         let info = message_info(&player1, &coins(50_000000000000000000, TOKEN_DENOM));
-        execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap();
-    
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+
         // Check Daily Leaderboard for Day 1
         let query_daily_msg = QueryMsg::DailyLeaderboard {
             leaderboard_type: LeaderboardType::TotalWagered,
             limit: Some(10),
+            denom: None,
         };
         let res = query(deps.as_ref(), env.clone(), query_daily_msg.clone()).unwrap();
         let daily_lb: LeaderboardResponse = from_json(&res).unwrap();
         assert_eq!(daily_lb.entries.len(), 1);
         assert_eq!(daily_lb.entries[0].player, player1);
-        assert_eq!(daily_lb.entries[0].value, Uint128::new(150_000000000000000000)); // 100 + 50
-    
+        assert_eq!(
+            daily_lb.entries[0].value,
+            Int128::new(150_000000000000000000)
+        ); // 100 + 50
+
         // --- Advance time by 1 day ---
         env.block.time = env.block.time.plus_seconds(86401); // 1 day + 1 second
-    
+
         // Querying before a new play should show an empty board because the query itself checks for reset
         let res = query(deps.as_ref(), env.clone(), query_daily_msg.clone()).unwrap();
         let daily_lb: LeaderboardResponse = from_json(&res).unwrap();
         assert_eq!(daily_lb.entries.len(), 0);
-    
+
         // --- Day 2 ---
         // Player 2 plays, wagering 200. This tx will trigger the state-changing reset.
         let info = message_info(&player2, &coins(200_000000000000000000, TOKEN_DENOM));
-        execute(deps.as_mut(), env.clone(), info, msg.clone()).unwrap();
-    
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+
         // Check Daily Leaderboard for Day 2
         let res = query(deps.as_ref(), env.clone(), query_daily_msg.clone()).unwrap();
         let daily_lb: LeaderboardResponse = from_json(&res).unwrap();
         assert_eq!(daily_lb.entries.len(), 1);
         assert_eq!(daily_lb.entries[0].player, player2);
-        assert_eq!(daily_lb.entries[0].value, Uint128::new(200_000000000000000000)); // Only player2's score
-    
+        assert_eq!(
+            daily_lb.entries[0].value,
+            Int128::new(200_000000000000000000)
+        ); // Only player2's score
+
         // Check that Global Leaderboard was NOT reset
         let query_global_msg = QueryMsg::GlobalLeaderboard {
             leaderboard_type: LeaderboardType::TotalWagered,
             limit: Some(10),
+            denom: None,
         };
         let res = query(deps.as_ref(), env.clone(), query_global_msg).unwrap();
         let global_lb: LeaderboardResponse = from_json(&res).unwrap();
         assert_eq!(global_lb.entries.len(), 2);
         assert_eq!(global_lb.entries[0].player, player2); // Player 2 has wagered more overall now
-        assert_eq!(global_lb.entries[0].value, Uint128::new(200_000000000000000000));
+        assert_eq!(
+            global_lb.entries[0].value,
+            Int128::new(200_000000000000000000)
+        );
         assert_eq!(global_lb.entries[1].player, player1); // Player 1 is second
-        assert_eq!(global_lb.entries[1].value, Uint128::new(150_000000000000000000));
+        assert_eq!(
+            global_lb.entries[1].value,
+            Int128::new(150_000000000000000000)
+        );
+    }
+
+    #[test]
+    fn test_daily_stats_start_clean_for_every_player() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(2000_000000000000000000));
+
+        let player1 = Addr::unchecked("player1");
+        let player2 = Addr::unchecked("player2");
+        let mut env = mock_env();
+        let day1 = day_index(env.block.time.seconds());
+
+        // Stats left by the original contract are dropped, not carried into today
+        let stale = PeriodPlayerStats {
+            total_wagered: Uint128::new(999_000000000000000000),
+            ..PeriodPlayerStats::default()
+        };
+        LEGACY_DAILY_PLAYER_STATS
+            .save(deps.as_mut().storage, &player2, &stale)
+            .unwrap();
+
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        let play = |deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                    env: &cosmwasm_std::Env,
+                    player: &Addr,
+                    amount: u128| {
+            let info = message_info(player, &coins(amount, TOKEN_DENOM));
+            play_settled(deps, env.clone(), info, msg.clone()).unwrap();
+        };
+        let daily_wagered = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                             env: &cosmwasm_std::Env| {
+            let query_msg = QueryMsg::DailyLeaderboard {
+                leaderboard_type: LeaderboardType::TotalWagered,
+                limit: None,
+                denom: None,
+            };
+            let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+            let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+            leaderboard
+                .entries
+                .into_iter()
+                .map(|entry| (entry.player, entry.value))
+                .collect::<Vec<_>>()
+        };
+
+        // --- Day 1: both players wager ---
+        play(&mut deps, &env, &player1, 100_000000000000000000);
+        play(&mut deps, &env, &player2, 50_000000000000000000);
+        assert_eq!(
+            daily_wagered(&deps, &env),
+            vec![
+                (player1.clone(), Int128::new(100_000000000000000000)),
+                (player2.clone(), Int128::new(50_000000000000000000)),
+            ]
+        );
+        assert!(!LEGACY_DAILY_PLAYER_STATS.has(&deps.storage, &player2));
+
+        // --- Day 2: player2's game resets the board, then player1 plays ---
+        env.block.time = env.block.time.plus_seconds(86400);
+        let day2 = day_index(env.block.time.seconds());
+        play(&mut deps, &env, &player2, 10_000000000000000000);
+        play(&mut deps, &env, &player1, 20_000000000000000000);
+
+        // Neither player carries yesterday's totals
+        assert_eq!(
+            daily_wagered(&deps, &env),
+            vec![
+                (player1.clone(), Int128::new(20_000000000000000000)),
+                (player2.clone(), Int128::new(10_000000000000000000)),
+            ]
+        );
+        let stats = PeriodStore::Daily
+            .player_stats()
+            .load(&deps.storage, (TOKEN_DENOM, day2, &player1))
+            .unwrap();
+        assert_eq!(stats.total_wagered, Uint128::new(20_000000000000000000));
+
+        // Day 1 entries were pruned by the day 2 games
+        assert!(!PeriodStore::Daily
+            .player_stats()
+            .has(&deps.storage, (TOKEN_DENOM, day1, &player1)));
+        assert!(!PeriodStore::Daily
+            .player_stats()
+            .has(&deps.storage, (TOKEN_DENOM, day1, &player2)));
+        assert!(PeriodStore::Daily
+            .player_stats()
+            .has(&deps.storage, (TOKEN_DENOM, day2, &player2)));
+    }
+
+    #[test]
+    fn test_daily_leaderboard_archive() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(2000_000000000000000000));
+
+        let player1 = Addr::unchecked("player1");
+        let player2 = Addr::unchecked("player2");
+        let mut env = mock_env();
+        let day1 = day_index(env.block.time.seconds());
+
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        let day_board = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                         env: &cosmwasm_std::Env,
+                         day: u64| {
+            let query_msg = QueryMsg::DailyLeaderboardForDay {
+                day,
+                leaderboard_type: LeaderboardType::TotalWagered,
+                limit: None,
+                denom: None,
+            };
+            let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+            let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+            leaderboard
+                .entries
+                .into_iter()
+                .map(|entry| (entry.player, entry.value))
+                .collect::<Vec<_>>()
+        };
+        let days = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                    start_after: Option<u64>,
+                    limit: Option<u32>| {
+            let query_msg = QueryMsg::DailyLeaderboardDays {
+                denom: None,
+                start_after,
+                limit,
+            };
+            let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+            let days: DailyLeaderboardDaysResponse = from_json(&res).unwrap();
+            assert_eq!(days.denom, TOKEN_DENOM);
+            days.days
+        };
+
+        // --- Day 1 ---
+        let info = message_info(&player1, &coins(100_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+        let info = message_info(&player2, &coins(50_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+        let day1_board = vec![
+            (player1.clone(), Int128::new(100_000000000000000000)),
+            (player2.clone(), Int128::new(50_000000000000000000)),
+        ];
+        assert_eq!(day_board(&deps, &env, day1), day1_board);
+        assert_eq!(days(&deps, None, None), vec![day1]);
+
+        // Past days stay readable after midnight
+        env.block.time = env.block.time.plus_seconds(86400);
+        assert_eq!(day_board(&deps, &env, day1), day1_board);
+
+        // --- Day 2 ---
+        let info = message_info(&player2, &coins(10_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+        assert_eq!(day_board(&deps, &env, day1), day1_board);
+        assert_eq!(
+            day_board(&deps, &env, day1 + 1),
+            vec![(player2.clone(), Int128::new(10_000000000000000000))]
+        );
+
+        // --- Day 5, after two days nobody played ---
+        env.block.time = env.block.time.plus_seconds(3 * 86400);
+        let info = message_info(&player1, &coins(10_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg).unwrap();
+
+        assert_eq!(days(&deps, None, None), vec![day1, day1 + 1, day1 + 4]);
+        assert_eq!(days(&deps, None, Some(1)), vec![day1]);
+        assert_eq!(days(&deps, Some(day1), None), vec![day1 + 1, day1 + 4]);
+        assert_eq!(day_board(&deps, &env, day1 + 2), vec![]);
+        assert_eq!(day_board(&deps, &env, day1), day1_board);
+
+        // Best wins are archived too
+        let query_msg = QueryMsg::DailyLeaderboardForDay {
+            day: day1,
+            leaderboard_type: LeaderboardType::BestWins,
+            limit: None,
+            denom: None,
+        };
+        let res = query(deps.as_ref(), env, query_msg).unwrap();
+        let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+        assert_eq!(leaderboard.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_period_leaderboards() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(2000_000000000000000000));
+
+        let player1 = Addr::unchecked("player1");
+        let player2 = Addr::unchecked("player2");
+        let mut env = mock_env(); // Wednesday 2019-10-23 02:23:39 UTC
+        let now = env.block.time.seconds();
+        let monday = 1571616000; // 2019-10-21 00:00:00 UTC
+
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        let board = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+                     env: &cosmwasm_std::Env,
+                     period: LeaderboardPeriod,
+                     index: Option<u64>| {
+            let query_msg = QueryMsg::Leaderboard {
+                period,
+                index,
+                leaderboard_type: LeaderboardType::TotalWagered,
+                start_after: None,
+                limit: None,
+                denom: None,
+            };
+            let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+            from_json::<PeriodLeaderboardResponse>(&res).unwrap()
+        };
+        let wagered = |response: &PeriodLeaderboardResponse| {
+            response
+                .entries
+                .iter()
+                .map(|entry| (entry.player.clone(), entry.value))
+                .collect::<Vec<_>>()
+        };
+
+        // Seasons are admin only and must end after they start, in the future
+        let season = |id: Option<u64>, start: u64, end: u64| ExecuteMsg::SetSeason {
+            id,
+            name: "Q4".to_string(),
+            start,
+            end,
+        };
+        let info = message_info(&player1, &[]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            season(None, now + 100, now + 864000),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        let info = message_info(&admin, &[]);
+        for (start, end) in [(now + 100, now + 100), (now - 200, now - 100)] {
+            let err = execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                season(None, start, end),
+            )
+            .unwrap_err();
+            assert!(matches!(err, ContractError::InvalidSeason {}));
+        }
+        let res = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            season(None, now + 100, now + 864000),
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "season_id" && attr.value == "1"));
+
+        // Before the season starts only the calendar periods count the game
+        let info = message_info(&player1, &coins(100_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+        let season_board = board(&deps, &env, LeaderboardPeriod::Season { id: 1 }, None);
+        assert!(season_board.entries.is_empty());
+        assert_eq!(season_board.index, Some(1));
+        assert_eq!(season_board.start, Some(now + 100));
+        assert_eq!(season_board.end, Some(now + 864000));
+
+        env.block.time = env.block.time.plus_seconds(200);
+        let info = message_info(&player2, &coins(50_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+
+        let weekly = board(&deps, &env, LeaderboardPeriod::Weekly, None);
+        assert_eq!(weekly.index, Some(2599));
+        assert_eq!(weekly.start, Some(monday));
+        assert_eq!(weekly.end, Some(monday + 7 * 86400));
+        assert_eq!(
+            wagered(&weekly),
+            vec![
+                (player1.clone(), Int128::new(100_000000000000000000)),
+                (player2.clone(), Int128::new(50_000000000000000000)),
+            ]
+        );
+        assert_eq!(
+            wagered(&board(
+                &deps,
+                &env,
+                LeaderboardPeriod::Season { id: 1 },
+                None
+            )),
+            vec![(player2.clone(), Int128::new(50_000000000000000000))]
+        );
+        let all_time = board(&deps, &env, LeaderboardPeriod::AllTime, None);
+        assert_eq!(all_time.index, None);
+        assert_eq!(all_time.start, None);
+        assert_eq!(wagered(&all_time), wagered(&weekly));
+
+        // A started season keeps its start but can be extended, and cannot be removed
+        let info = message_info(&admin, &[]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            season(Some(1), now + 150, now + 864000),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::SeasonStarted { id: 1 }));
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::RemoveSeason { id: 1 },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::SeasonStarted { id: 1 }));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            season(Some(1), now + 100, now + 2 * 864000),
+        )
+        .unwrap();
+
+        // A second season that has not started can be removed
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            season(None, now + 864000, now + 2 * 864000),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::RemoveSeason { id: 2 },
+        )
+        .unwrap();
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Seasons {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let seasons: SeasonsResponse = from_json(&res).unwrap();
+        assert_eq!(seasons.seasons.len(), 1);
+        assert_eq!(seasons.seasons[0].id, 1);
+        assert_eq!(seasons.seasons[0].end, now + 2 * 864000);
+
+        // --- Next Tuesday: a new week, still October ---
+        env.block.time = env.block.time.plus_seconds(6 * 86400);
+        let info = message_info(&player2, &coins(10_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg.clone()).unwrap();
+
+        let weekly = board(&deps, &env, LeaderboardPeriod::Weekly, None);
+        assert_eq!(weekly.index, Some(2600));
+        assert_eq!(
+            wagered(&weekly),
+            vec![(player2.clone(), Int128::new(10_000000000000000000))]
+        );
+        let last_week = board(&deps, &env, LeaderboardPeriod::Weekly, Some(2599));
+        assert_eq!(last_week.entries.len(), 2);
+        let monthly = board(&deps, &env, LeaderboardPeriod::Monthly, None);
+        assert_eq!(monthly.index, Some(597)); // October 2019
+        assert_eq!(monthly.start, Some(1569888000));
+        assert_eq!(
+            wagered(&monthly),
+            vec![
+                (player1.clone(), Int128::new(100_000000000000000000)),
+                (player2.clone(), Int128::new(60_000000000000000000)),
+            ]
+        );
+        assert_eq!(
+            wagered(&board(
+                &deps,
+                &env,
+                LeaderboardPeriod::Season { id: 1 },
+                None
+            )),
+            vec![(player2.clone(), Int128::new(60_000000000000000000))]
+        );
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::LeaderboardPeriods {
+                period: CalendarPeriod::Weekly,
+                denom: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let periods: LeaderboardPeriodsResponse = from_json(&res).unwrap();
+        assert_eq!(
+            periods.periods,
+            vec![
+                PeriodResponse {
+                    index: 2599,
+                    start: monday,
+                    end: monday + 7 * 86400,
+                },
+                PeriodResponse {
+                    index: 2600,
+                    start: monday + 7 * 86400,
+                    end: monday + 14 * 86400,
+                },
+            ]
+        );
+
+        // Days can start in another time zone
+        let info = message_info(&admin, &[]);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ExecuteMsg::SetLeaderboardUtcOffset {
+                utc_offset_minutes: 900,
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::InvalidUtcOffset {}));
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ExecuteMsg::SetLeaderboardUtcOffset {
+                utc_offset_minutes: -240,
+            },
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_json(&res).unwrap();
+        assert_eq!(config.leaderboard_utc_offset_minutes, -240);
+
+        // 02:27 UTC on Tuesday is still Monday at UTC-4
+        let daily = board(&deps, &env, LeaderboardPeriod::Daily, None);
+        let tuesday = day_index(env.block.time.seconds());
+        assert_eq!(daily.index, Some(tuesday - 1));
+        assert_eq!(daily.start, Some((tuesday - 1) * 86400 + 4 * 3600));
+
+        // The change applies mid-period: the next game counts on Monday's board, while the game
+        // already on Tuesday's board stays there
+        let info = message_info(&player1, &coins(20_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg).unwrap();
+        assert_eq!(
+            wagered(&board(&deps, &env, LeaderboardPeriod::Daily, None)),
+            vec![(player1.clone(), Int128::new(20_000000000000000000))]
+        );
+        assert_eq!(
+            wagered(&board(&deps, &env, LeaderboardPeriod::Daily, Some(tuesday))),
+            vec![(player2.clone(), Int128::new(10_000000000000000000))]
+        );
+    }
+
+    #[test]
+    fn test_leaderboard_pagination() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(2000_000000000000000000));
+
+        // A hundred entries on the all-time board, pairs of players tied
+        let mut entries: Vec<LeaderboardEntry> = (0..100)
+            .map(|i| LeaderboardEntry {
+                player: deps.api.addr_make(&format!("player{i}")),
+                value: Int128::new((201 - 2 * (i / 2)) * 1_000000000000000000),
+                multiplier: None,
+                rows: None,
+            })
+            .collect();
+        let board = board_id(
+            BoardScope::AllTime,
+            &LeaderboardType::TotalWagered,
+            TOKEN_DENOM,
+        );
+        import_leaderboard(deps.as_mut().storage, &board, entries.clone()).unwrap();
+
+        // Every entry of the board, following the cursor of each page
+        let ranking = |deps: Deps, page_size: u32| {
+            let mut ranking: Vec<Addr> = vec![];
+            let mut start_after = None;
+            loop {
+                let query_msg = QueryMsg::Leaderboard {
+                    period: LeaderboardPeriod::AllTime,
+                    index: None,
+                    leaderboard_type: LeaderboardType::TotalWagered,
+                    start_after,
+                    limit: Some(page_size),
+                    denom: None,
+                };
+                let res = query(deps, mock_env(), query_msg).unwrap();
+                let page: PeriodLeaderboardResponse = from_json(&res).unwrap();
+                let Some(last) = page.entries.last() else {
+                    return ranking;
+                };
+                start_after = Some(LeaderboardCursor {
+                    value: last.value,
+                    player: last.player.to_string(),
+                });
+                ranking.extend(page.entries.into_iter().map(|e| e.player));
+            }
+        };
+        let expected = |entries: &mut Vec<LeaderboardEntry>| {
+            entries.sort_by(|a, b| (b.value, &b.player).cmp(&(a.value, &a.player)));
+            entries.iter().map(|e| e.player.clone()).collect::<Vec<_>>()
+        };
+
+        // Ties ranked by descending address, whatever the page size
+        assert_eq!(ranking(deps.as_ref(), 30), expected(&mut entries));
+
+        let player = deps.api.addr_make("newcomer");
+        let info = message_info(&player, &coins(150_000000000000000000, TOKEN_DENOM));
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        play_settled(&mut deps, mock_env(), info, msg).unwrap();
+
+        entries.push(LeaderboardEntry {
+            player,
+            value: Int128::new(150_000000000000000000),
+            multiplier: None,
+            rows: None,
+        });
+        let moved = ranking(deps.as_ref(), 100);
+        assert_eq!(moved.len(), 101);
+        assert_eq!(moved, expected(&mut entries));
+
+        // Boards are no longer capped at 100 entries
+        let query_msg = QueryMsg::GlobalLeaderboard {
+            leaderboard_type: LeaderboardType::TotalWagered,
+            limit: Some(10),
+            denom: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+        let top: Vec<Addr> = leaderboard.entries.into_iter().map(|e| e.player).collect();
+        assert_eq!(top, moved[..10]);
+
+        let query_msg = QueryMsg::Leaderboard {
+            period: LeaderboardPeriod::AllTime,
+            index: None,
+            leaderboard_type: LeaderboardType::TotalWagered,
+            start_after: Some(LeaderboardCursor {
+                value: Int128::zero(),
+                player: "not an address".to_string(),
+            }),
+            limit: None,
+            denom: None,
+        };
+        assert!(query(deps.as_ref(), mock_env(), query_msg).is_err());
+    }
+
+    #[test]
+    fn test_player_rank() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(2000_000000000000000000));
+
+        // Player i wagers (i + 1) * 10
+        let players: Vec<Addr> = (0..5)
+            .map(|i| deps.api.addr_make(&format!("player{i}")))
+            .collect();
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        for (i, player) in players.iter().enumerate() {
+            let amount = (i as u128 + 1) * 10_000000000000000000;
+            let info = message_info(player, &coins(amount, TOKEN_DENOM));
+            play_settled(&mut deps, mock_env(), info, msg.clone()).unwrap();
+        }
+
+        let player_rank = |deps: Deps, player: &Addr, scope, neighbours| {
+            let query_msg = QueryMsg::PlayerRank {
+                player: player.to_string(),
+                leaderboard_type: LeaderboardType::TotalWagered,
+                scope,
+                index: None,
+                neighbours,
+                denom: None,
+            };
+            let res = query(deps, mock_env(), query_msg).unwrap();
+            from_json::<PlayerRankResponse>(&res).unwrap()
+        };
+        let names = |entries: Vec<MsgLeaderboardEntry>| {
+            entries.into_iter().map(|e| e.player).collect::<Vec<_>>()
+        };
+
+        let rank = player_rank(
+            deps.as_ref(),
+            &players[2],
+            LeaderboardPeriod::AllTime,
+            Some(1),
+        );
+        assert_eq!(rank.rank, Some(3));
+        assert_eq!(rank.value, Some(Int128::new(30_000000000000000000)));
+        assert_eq!(rank.gap, Some(Int128::new(10_000000000000000000)));
+        assert_eq!(rank.index, None);
+        assert_eq!(names(rank.above), vec![players[3].clone()]);
+        assert_eq!(names(rank.below), vec![players[1].clone()]);
+
+        // Nobody to catch at the top
+        let rank = player_rank(
+            deps.as_ref(),
+            &players[4],
+            LeaderboardPeriod::AllTime,
+            Some(1),
+        );
+        assert_eq!(rank.rank, Some(1));
+        assert_eq!(rank.gap, None);
+        assert!(rank.above.is_empty());
+
+        let rank = player_rank(deps.as_ref(), &players[0], LeaderboardPeriod::Daily, None);
+        assert_eq!(rank.rank, Some(5));
+        assert_eq!(rank.index, Some(day_index(mock_env().block.time.seconds())));
+        let ahead: Vec<Addr> = players[1..].iter().rev().cloned().collect();
+        assert_eq!(names(rank.above), ahead);
+        assert!(rank.below.is_empty());
+
+        let outsider = deps.api.addr_make("outsider");
+        let rank = player_rank(deps.as_ref(), &outsider, LeaderboardPeriod::AllTime, None);
+        assert_eq!((rank.rank, rank.value, rank.gap), (None, None, None));
+        assert!(rank.above.is_empty() && rank.below.is_empty());
+
+        let query_msg = QueryMsg::PlayerRank {
+            player: "not an address".to_string(),
+            leaderboard_type: LeaderboardType::TotalWagered,
+            scope: LeaderboardPeriod::AllTime,
+            index: None,
+            neighbours: None,
+            denom: None,
+        };
+        assert!(query(deps.as_ref(), mock_env(), query_msg).is_err());
     }
 
     #[test]
@@ -581,22 +1387,32 @@ mod tests {
         let mut deps = mock_deps();
         let admin = deps.api.addr_make("admin");
         setup_contract(deps.as_mut(), &admin).unwrap();
-        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
+        fund_contract(deps.as_mut(), Uint128::new(2000_000000000000000000));
 
         let player1 = Addr::unchecked("player1");
         let player2 = Addr::unchecked("player2");
 
         // P1 wagers 100
-        let msg = ExecuteMsg::Play { difficulty: Difficulty::Easy, risk_level: RiskLevel::Low };
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
         let info = message_info(&player1, &coins(100_000000000000000000, TOKEN_DENOM));
-        execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+        play_settled(&mut deps, mock_env(), info, msg.clone()).unwrap();
 
         // P2 wagers 200
         let info = message_info(&player2, &coins(200_000000000000000000, TOKEN_DENOM));
-        execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+        play_settled(&mut deps, mock_env(), info, msg.clone()).unwrap();
 
         // Check leaderboard - P2 should be first
-        let query_msg = QueryMsg::GlobalLeaderboard { leaderboard_type: LeaderboardType::TotalWagered, limit: Some(10) };
+        let query_msg = QueryMsg::GlobalLeaderboard {
+            leaderboard_type: LeaderboardType::TotalWagered,
+            limit: Some(10),
+            denom: None,
+        };
         let res = query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap();
         let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
         assert_eq!(leaderboard.entries.len(), 2);
@@ -605,15 +1421,2785 @@ mod tests {
 
         // P1 wagers another 150, for a total of 250
         let info = message_info(&player1, &coins(150_000000000000000000, TOKEN_DENOM));
-        execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+        play_settled(&mut deps, mock_env(), info, msg.clone()).unwrap();
 
         // Check leaderboard again - P1 should now be first
         let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
         let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
         assert_eq!(leaderboard.entries.len(), 2);
         assert_eq!(leaderboard.entries[0].player, player1);
-        assert_eq!(leaderboard.entries[0].value, Uint128::new(250_000000000000000000));
+        assert_eq!(
+            leaderboard.entries[0].value,
+            Int128::new(250_000000000000000000)
+        );
         assert_eq!(leaderboard.entries[1].player, player2);
-        assert_eq!(leaderboard.entries[1].value, Uint128::new(200_000000000000000000));
+        assert_eq!(
+            leaderboard.entries[1].value,
+            Int128::new(200_000000000000000000)
+        );
+    }
+
+    fn commit_seed(deps: DepsMut, admin: &Addr, server_seed: &[u8]) -> u64 {
+        let msg = ExecuteMsg::CommitServerSeed {
+            commitment: HexBinary::from(hash_server_seed(server_seed)),
+        };
+        let res = execute(deps, mock_env(), message_info(admin, &[]), msg).unwrap();
+        res.attributes
+            .iter()
+            .find(|attr| attr.key == "commitment_id")
+            .unwrap()
+            .value
+            .parse()
+            .unwrap()
+    }
+
+    fn reveal_seed(deps: DepsMut, admin: &Addr, commitment_id: u64, server_seed: &[u8]) {
+        let msg = ExecuteMsg::RevealServerSeed {
+            commitment_id,
+            server_seed: HexBinary::from(server_seed.to_vec()),
+        };
+        execute(deps, mock_env(), message_info(admin, &[]), msg).unwrap();
+    }
+
+    #[test]
+    fn test_commit_server_seed_unauthorized() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+
+        let msg = ExecuteMsg::CommitServerSeed {
+            commitment: HexBinary::from(hash_server_seed(b"server seed")),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&player, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        let msg = ExecuteMsg::CommitServerSeed {
+            commitment: HexBinary::from(b"too short".to_vec()),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::InvalidCommitment {}));
+    }
+
+    #[test]
+    fn test_play_committed_requires_active_commitment() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
+
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: Some("lucky".to_string()),
+            ball_count: None,
+        };
+        let info = message_info(&player, &coins(100_000000000000000000, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+
+        assert!(matches!(err, ContractError::NoActiveCommitment {}));
+    }
+
+    #[test]
+    fn test_commit_reveal_settles_pending_games() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(4000_000000000000000000));
+
+        let server_seed = b"operator secret seed";
+        let commitment_id = commit_seed(deps.as_mut(), &admin, server_seed);
+
+        // Place two bets against the commitment; nothing settles yet
+        for _ in 0..2 {
+            let msg = ExecuteMsg::Play {
+                difficulty: Some(Difficulty::Medium),
+                rows: None,
+                risk_level: RiskLevel::High,
+                client_seed: Some("lucky".to_string()),
+                ball_count: None,
+            };
+            let info = message_info(&player, &coins(10_000000000000000000, TOKEN_DENOM));
+            let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+            assert_eq!(res.messages.len(), 0);
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.total_games, 0);
+        assert_eq!(stats.house_balance, Uint128::new(4020_000000000000000000));
+        // 170x is the top multiplier on Medium/High
+        assert_eq!(
+            stats.reserved_balance,
+            Uint128::new(3400_000000000000000000)
+        );
+
+        // A wrong seed is rejected
+        let msg = ExecuteMsg::RevealServerSeed {
+            commitment_id,
+            server_seed: HexBinary::from(b"not the seed".to_vec()),
+        };
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidServerSeed { commitment_id });
+
+        // Nothing settles before the reveal
+        let stranger = deps.api.addr_make("stranger");
+        let settle = ExecuteMsg::SettleGames {
+            commitment_id,
+            limit: Some(1),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&stranger, &[]),
+            settle.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::CommitmentNotRevealed { commitment_id });
+
+        let msg = ExecuteMsg::RevealServerSeed {
+            commitment_id,
+            server_seed: HexBinary::from(server_seed.to_vec()),
+        };
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(res.events.len(), 0);
+
+        // Anyone settles the revealed games, a page at a time
+        for remaining in ["true", "false"] {
+            let info = message_info(&stranger, &[]);
+            let res = execute(deps.as_mut(), mock_env(), info, settle.clone()).unwrap();
+            assert_eq!(res.events.len(), 1);
+            assert_eq!(res.messages.len(), 1);
+            assert!(res.attributes.contains(&("remaining", remaining).into()));
+        }
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.total_games, 2);
+        assert_eq!(stats.reserved_balance, Uint128::zero());
+
+        // Every settled game can be replayed from the revealed seed
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        assert_eq!(history.games.len(), 2);
+        for (nonce, game) in history.games.iter().enumerate() {
+            assert_eq!(game.nonce, nonce as u64);
+            assert_eq!(
+                game.seed_inputs,
+                Some(SeedInputs::Commitment {
+                    commitment_id,
+                    server_seed: HexBinary::from(server_seed.to_vec()),
+                    client_seed: "lucky".to_string(),
+                })
+            );
+            let seed = commitment_seed(server_seed, "lucky", player.as_str(), nonce as u64);
+            let path: Vec<bool> = generate_path_from_seed(seed, 12)
+                .into_iter()
+                .map(|b| b != 0)
+                .collect();
+            assert_eq!(game.path, path);
+        }
+
+        // The commitment is closed and cannot be revealed twice
+        let query_msg = QueryMsg::SeedCommitment {
+            id: Some(commitment_id),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let commitment: SeedCommitmentResponse = from_json(&res).unwrap();
+        assert!(!commitment.active);
+        assert_eq!(
+            commitment.server_seed,
+            Some(HexBinary::from(server_seed.to_vec()))
+        );
+
+        let err = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::CommitmentAlreadyRevealed { commitment_id }
+        );
+    }
+
+    #[test]
+    fn test_refund_unrevealed_game() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        let server_seed = b"withheld seed";
+        let commitment_id = commit_seed(deps.as_mut(), &admin, server_seed);
+        let msg = ExecuteMsg::Play {
+            difficulty: None,
+            rows: Some(8),
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: Some(2),
+        };
+        let info = message_info(&player, &coins(2_000, TOKEN_DENOM));
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+        let refund = |nonce| ExecuteMsg::RefundGame {
+            commitment_id,
+            nonce,
+        };
+        let mut env = mock_env();
+        let refund_height = env.block.height + DEFAULT_REFUND_TIMEOUT_BLOCKS;
+
+        // Only the player, and only once the operator had time to reveal
+        let info = message_info(&player, &[]);
+        let err = execute(deps.as_mut(), env.clone(), info, refund(0)).unwrap_err();
+        assert_eq!(err, ContractError::BetNotExpired { refund_height });
+
+        env.block.height = refund_height;
+        let info = message_info(&admin, &[]);
+        assert!(execute(deps.as_mut(), env.clone(), info, refund(0)).is_err());
+
+        let info = message_info(&player, &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, refund(0)).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        assert!(res.attributes.contains(&("amount", "1000").into()));
+
+        // A late reveal settles the rest, which can no longer be refunded
+        let msg = ExecuteMsg::RevealServerSeed {
+            commitment_id,
+            server_seed: HexBinary::from(server_seed.to_vec()),
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&admin, &[]), msg).unwrap();
+        let info = message_info(&player, &[]);
+        let err = execute(deps.as_mut(), env.clone(), info, refund(1)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::CommitmentAlreadyRevealed { commitment_id }
+        );
+
+        let msg = ExecuteMsg::SettleGames {
+            commitment_id,
+            limit: None,
+        };
+        let res = execute(deps.as_mut(), env, message_info(&player, &[]), msg).unwrap();
+        assert!(res.attributes.contains(&("settled", "1").into()));
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.total_games, 1);
+        assert_eq!(stats.reserved_balance, Uint128::zero());
+    }
+
+    #[test]
+    fn test_place_and_settle_bet() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        let anyone = deps.api.addr_make("anyone");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
+
+        let msg = ExecuteMsg::PlaceBet {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Medium,
+            randomness: None,
+        };
+        let info = message_info(&player, &coins(10_000000000000000000, TOKEN_DENOM));
+        let mut env = mock_env();
+        let res = execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        assert_eq!(res.messages.len(), 0);
+
+        // The bet and its 13x maximum payout are held by the house
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.house_balance, Uint128::new(1010_000000000000000000));
+        assert_eq!(stats.reserved_balance, Uint128::new(130_000000000000000000));
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PendingBet { bet_id: 0 },
+        )
+        .unwrap();
+        let pending: PendingBetResponse = from_json(&res).unwrap();
+        assert_eq!(pending.player, player);
+        assert_eq!(pending.settle_height, env.block.height + 1);
+        assert_eq!(pending.refund_height, Some(env.block.height + 2));
+
+        // Settling in the placement block is not allowed
+        let msg = ExecuteMsg::SettleBet {
+            bet_id: 0,
+            signature: None,
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&anyone, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetNotReady {
+                settle_height: env.block.height + 1
+            }
+        );
+
+        // The window was fixed at placement
+        let info = message_info(&admin, &[]);
+        let update = ExecuteMsg::UpdateSettlementConfig {
+            settle_delay_blocks: Some(5),
+            refund_timeout_blocks: None,
+        };
+        execute(deps.as_mut(), env.clone(), info, update).unwrap();
+
+        // The block at the settle height seeds the bet, so it settles in no later block
+        let mut late = env.clone();
+        late.block.height += 2;
+        let info = message_info(&anyone, &[]);
+        let err = execute(deps.as_mut(), late, info, msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::BetExpired { bet_id: 0 });
+
+        // Anyone settles it in that block, to the same seed whichever transaction does
+        env.block.height += 1;
+        let mut other_tx = env.clone();
+        other_tx.transaction = Some(cosmwasm_std::TransactionInfo { index: 7 });
+        let expected = derive_seed(
+            &crate::rng::settle_block_seed_inputs(&other_tx, 0, env.block.height - 1),
+            player.as_str(),
+            0,
+        );
+        execute(deps.as_mut(), env.clone(), message_info(&anyone, &[]), msg).unwrap();
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.total_games, 1);
+        assert_eq!(stats.reserved_balance, Uint128::zero());
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        assert_eq!(history.games.len(), 1);
+        let seed_inputs = history.games[0].seed_inputs.as_ref().unwrap();
+        assert!(matches!(
+            seed_inputs,
+            SeedInputs::SettleBlock { bet_id: 0, .. }
+        ));
+        assert_eq!(derive_seed(seed_inputs, player.as_str(), 0), expected);
+
+        let res = query(
+            deps.as_ref(),
+            env,
+            QueryMsg::PendingBets {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap();
+        let pending: PendingBetsResponse = from_json(&res).unwrap();
+        assert!(pending.bets.is_empty());
+    }
+
+    #[test]
+    fn test_refund_expired_bet() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
+
+        let msg = ExecuteMsg::PlaceBet {
+            difficulty: Some(Difficulty::Hard),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            randomness: None,
+        };
+        let info = message_info(&player, &coins(10_000000000000000000, TOKEN_DENOM));
+        let mut env = mock_env();
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PendingBet { bet_id: 0 },
+        )
+        .unwrap();
+        let pending: PendingBetResponse = from_json(&res).unwrap();
+
+        // Too early to refund
+        let msg = ExecuteMsg::RefundBet { bet_id: 0 };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&player, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetNotExpired {
+                refund_height: pending.refund_height.unwrap()
+            }
+        );
+
+        // Once expired the bet can no longer be settled, only refunded by its player
+        env.block.height = pending.refund_height.unwrap();
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            ExecuteMsg::SettleBet {
+                bet_id: 0,
+                signature: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::BetExpired { bet_id: 0 });
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+
+        // Nobody settled the bet in its block, so it returns the bet
+        let res = execute(deps.as_mut(), env.clone(), message_info(&player, &[]), msg).unwrap();
+        assert_eq!(res.messages.len(), 1);
+        match &res.messages[0].msg {
+            cosmwasm_std::CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                assert_eq!(to_address, &player.to_string());
+                assert_eq!(amount, &coins(10_000000000000000000, TOKEN_DENOM));
+            }
+            _ => panic!("Expected BankMsg::Send"),
+        }
+
+        let res = query(deps.as_ref(), env, QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.total_games, 0);
+        assert_eq!(stats.house_balance, Uint128::new(1000_000000000000000000));
+        assert_eq!(stats.reserved_balance, Uint128::zero());
+    }
+
+    /// 96 byte G2 public key of the operator secret `secret`
+    fn operator_public_key(secret: u64) -> Binary {
+        let point = (G2Affine::generator() * Fr::from(secret)).into_affine();
+        let mut public_key = Vec::new();
+        point.serialize_compressed(&mut public_key).unwrap();
+        Binary::from(public_key)
+    }
+
+    /// 48 byte G1 signature over `message` by the operator secret `secret`
+    fn operator_sign(api: &MockApi, secret: u64, message: &[u8]) -> Binary {
+        let message_point = api
+            .bls12_381_hash_to_g1(HashFunction::Sha256, message, BLS_G1_DST)
+            .unwrap();
+        let message_point = G1Affine::deserialize_compressed(&message_point[..]).unwrap();
+        let mut signature = Vec::new();
+        (message_point * Fr::from(secret))
+            .into_affine()
+            .serialize_compressed(&mut signature)
+            .unwrap();
+        Binary::from(signature)
+    }
+
+    #[test]
+    fn test_operator_signed_bet() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
+
+        let place_msg = ExecuteMsg::PlaceBet {
+            difficulty: Some(Difficulty::Hard),
+            rows: None,
+            risk_level: RiskLevel::Medium,
+            randomness: Some(RandomnessMode::OperatorSignature),
+        };
+        let info = message_info(&player, &coins(1_000000000000000000, TOKEN_DENOM));
+        let mut env = mock_env();
+
+        // No key registered yet
+        let err = execute(deps.as_mut(), env.clone(), info.clone(), place_msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::NoOperatorKey {});
+
+        // Only a G2 point is a valid key
+        let msg = ExecuteMsg::SetOperatorKey {
+            public_key: Some(Binary::from([7u8; 32])),
+        };
+        let err = execute(deps.as_mut(), env.clone(), message_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidOperatorKey {});
+
+        let msg = ExecuteMsg::SetOperatorKey {
+            public_key: Some(operator_public_key(7)),
+        };
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&player, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Unauthorized {}));
+        execute(deps.as_mut(), env.clone(), message_info(&admin, &[]), msg).unwrap();
+
+        execute(deps.as_mut(), env.clone(), info, place_msg).unwrap();
+        env.block.height += 1;
+
+        // Rotating the key does not affect the bet already placed
+        let msg = ExecuteMsg::SetOperatorKey {
+            public_key: Some(operator_public_key(9)),
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&admin, &[]), msg).unwrap();
+
+        let message = operator_message(env.contract.address.as_str(), player.as_str(), 0, 0);
+        let signature = operator_sign(&deps.api, 7, &message);
+        assert!(
+            verify_bls_signature(&deps.api, &operator_public_key(7), &message, &signature).unwrap()
+        );
+        assert!(
+            !verify_bls_signature(&deps.api, &operator_public_key(9), &message, &signature)
+                .unwrap()
+        );
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            ExecuteMsg::SettleBet {
+                bet_id: 0,
+                signature: None,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::MissingSignature { bet_id: 0 });
+
+        // A signature over a different bet is rejected
+        let wrong_message = operator_message(env.contract.address.as_str(), player.as_str(), 0, 1);
+        let wrong_signature = operator_sign(&deps.api, 7, &wrong_message);
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            ExecuteMsg::SettleBet {
+                bet_id: 0,
+                signature: Some(wrong_signature),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature { bet_id: 0 });
+
+        // The signature is the only one for the bet, so the player can submit it as well
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&player, &[]),
+            ExecuteMsg::SettleBet {
+                bet_id: 0,
+                signature: Some(signature.clone()),
+            },
+        )
+        .unwrap();
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        let game = &history.games[0];
+        assert!(matches!(
+            &game.seed_inputs,
+            Some(SeedInputs::OperatorSignature { signature: s, .. }) if *s == signature
+        ));
+        let path: Vec<bool> = generate_path_from_seed(signature_seed(&signature), 16)
+            .into_iter()
+            .map(|b| b != 0)
+            .collect();
+        assert_eq!(game.path, path);
+
+        // A withheld signature costs the house the reserved best case
+        let place_msg = ExecuteMsg::PlaceBet {
+            difficulty: Some(Difficulty::Hard),
+            rows: None,
+            risk_level: RiskLevel::Medium,
+            randomness: Some(RandomnessMode::OperatorSignature),
+        };
+        let info = message_info(&player, &coins(1_000000000000000000, TOKEN_DENOM));
+        execute(deps.as_mut(), env.clone(), info, place_msg).unwrap();
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PendingBet { bet_id: 1 },
+        )
+        .unwrap();
+        let pending: PendingBetResponse = from_json(&res).unwrap();
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+
+        env.block.height = pending.refund_height.unwrap();
+        let msg = ExecuteMsg::RefundBet { bet_id: 1 };
+        let res = execute(deps.as_mut(), env, message_info(&player, &[]), msg).unwrap();
+        let amount = stats.reserved_balance.to_string();
+        assert!(res.attributes.contains(&("amount", amount.as_str()).into()));
+    }
+
+    // bls-unchained-g1-rfc9380 test network, the same scheme drand's quicknet uses
+    const DRAND_PUBLIC_KEY: &str = "a1ee12542360bf75742bcade13d6134e7d5283d9eb782887c47d3d9725f05805d37b0106b7f744395bf82c175dd7434a169e998f188a657a030d588892c0cd2c01f996aaf331c4d8bc5b9734bbe261d09e7d2d39ef88b635077f262bd7bbb30f";
+    const DRAND_ROUND_3_SIGNATURE: &str = "b98dae74f6a9d2ec79d75ba273dcfda86a45d589412860eb4c0fd056b00654dbf667c1b6884987c9aee0d43f8ba9db52";
+    const DRAND_ROUND_4_SIGNATURE: &str = "962c2b2969e8f3351cf5cc457b04ecbf0c65bd79f4c1ee3bd0205f581368aaaa0cdeb1531a0709d39ef06a8ba1e1bb93";
+
+    #[test]
+    fn test_verify_drand_beacon() {
+        let deps = mock_deps();
+        let public_key = HexBinary::from_hex(DRAND_PUBLIC_KEY).unwrap();
+        let signature = HexBinary::from_hex(DRAND_ROUND_3_SIGNATURE).unwrap();
+
+        assert!(verify_drand_beacon(&deps.api, &public_key, 3, &signature).unwrap());
+        assert!(!verify_drand_beacon(&deps.api, &public_key, 4, &signature).unwrap());
+    }
+
+    #[test]
+    fn test_drand_bet() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
+
+        let mut env = mock_env();
+        let drand = DrandConfig {
+            public_key: HexBinary::from_hex(DRAND_PUBLIC_KEY).unwrap(),
+            // Puts round 3 just past the minimum lead time
+            genesis_time: env.block.time.seconds() + 5,
+            period_seconds: 3,
+        };
+
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            ExecuteMsg::SetDrandConfig {
+                drand: Some(DrandConfig {
+                    period_seconds: 0,
+                    ..drand.clone()
+                }),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidDrandConfig {});
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&admin, &[]),
+            ExecuteMsg::SetDrandConfig { drand: Some(drand) },
+        )
+        .unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            difficulty: Some(Difficulty::Medium),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            randomness: Some(RandomnessMode::Drand),
+        };
+        let info = message_info(&player, &coins(1_000000000000000000, TOKEN_DENOM));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+
+        let res = query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PendingBet { bet_id: 0 },
+        )
+        .unwrap();
+        let pending: PendingBetResponse = from_json(&res).unwrap();
+        assert_eq!(pending.randomness, RandomnessMode::Drand);
+        assert_eq!(pending.drand_round, Some(3));
+        assert_eq!(
+            pending.drand_round_time,
+            Some(env.block.time.seconds() + 11)
+        );
+        assert_eq!(pending.refund_height, None);
+
+        // The player can cancel until the round is published, never after
+        let msg = ExecuteMsg::PlaceBet {
+            difficulty: Some(Difficulty::Medium),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            randomness: Some(RandomnessMode::Drand),
+        };
+        let info = message_info(&player, &coins(1_000000000000000000, TOKEN_DENOM));
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let refund = ExecuteMsg::RefundBet { bet_id: 1 };
+        let info = message_info(&player, &[]);
+        let res = execute(deps.as_mut(), env.clone(), info, refund).unwrap();
+        assert!(res
+            .attributes
+            .contains(&("amount", "1000000000000000000").into()));
+
+        env.block.time = env.block.time.plus_seconds(11);
+        let refund = ExecuteMsg::RefundBet { bet_id: 0 };
+        let info = message_info(&player, &[]);
+        let err = execute(deps.as_mut(), env.clone(), info, refund).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DrandRoundPublished {
+                bet_id: 0,
+                round: 3
+            }
+        );
+
+        // Drand bets never expire, since anyone can fetch the beacon and settle
+        env.block.height += 1 + DEFAULT_REFUND_TIMEOUT_BLOCKS;
+
+        // The beacon of any other round is rejected
+        let err = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&player, &[]),
+            ExecuteMsg::SettleBet {
+                bet_id: 0,
+                signature: Some(Binary::from(
+                    HexBinary::from_hex(DRAND_ROUND_4_SIGNATURE)
+                        .unwrap()
+                        .to_vec(),
+                )),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature { bet_id: 0 });
+
+        let signature = Binary::from(
+            HexBinary::from_hex(DRAND_ROUND_3_SIGNATURE)
+                .unwrap()
+                .to_vec(),
+        );
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&player, &[]),
+            ExecuteMsg::SettleBet {
+                bet_id: 0,
+                signature: Some(signature.clone()),
+            },
+        )
+        .unwrap();
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env, query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        let game = &history.games[0];
+        assert!(matches!(
+            &game.seed_inputs,
+            Some(SeedInputs::Drand { round: 3, signature: s, .. }) if *s == signature
+        ));
+        let path: Vec<bool> =
+            generate_path_from_seed(drand_seed(&signature, 0, player.as_str(), 0), 12)
+                .into_iter()
+                .map(|b| b != 0)
+                .collect();
+        assert_eq!(game.path, path);
+
+        // The beacon is checked again when verifying
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::VerifyGame {
+                player: player.to_string(),
+                game_index: 0,
+            },
+        )
+        .unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(verification.verified);
+        assert_eq!(verification.path, path);
+    }
+
+    /// History the off-chain verifier is tested against, one game per kind of seed inputs
+    const HISTORY_FIXTURE: &str =
+        include_str!("../../../tools/plinko-verify/testdata/history.json");
+    /// The payout table versions those games were paid on
+    const TABLES_FIXTURE: &str = include_str!("../../../tools/plinko-verify/testdata/tables.json");
+
+    #[test]
+    fn test_verifier_fixture() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1000_000000000000000000));
+        let mut env = mock_env();
+
+        let msg = ExecuteMsg::Play {
+            difficulty: None,
+            rows: Some(12),
+            risk_level: RiskLevel::High,
+            client_seed: Some("fixture".to_string()),
+            ball_count: None,
+        };
+        let info = message_info(&player, &coins(1_000000000000000000, TOKEN_DENOM));
+        play_settled(&mut deps, env.clone(), info, msg).unwrap();
+
+        let key = ExecuteMsg::SetOperatorKey {
+            public_key: Some(operator_public_key(7)),
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&admin, &[]), key).unwrap();
+        for (bet_id, randomness) in [
+            RandomnessMode::SettleBlock,
+            RandomnessMode::OperatorSignature,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let msg = ExecuteMsg::PlaceBet {
+                difficulty: None,
+                rows: Some(10),
+                risk_level: RiskLevel::Medium,
+                randomness: Some(randomness),
+            };
+            let info = message_info(&player, &coins(2_000000000000000000, TOKEN_DENOM));
+            execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+            env.block.height += 1;
+            env.block.time = env.block.time.plus_seconds(5);
+
+            let bet_id = bet_id as u64;
+            let nonce = bet_id + 1;
+            let message = operator_message(
+                env.contract.address.as_str(),
+                player.as_str(),
+                nonce,
+                bet_id,
+            );
+            let signature = (bet_id == 1).then(|| operator_sign(&deps.api, 7, &message));
+            let msg = ExecuteMsg::SettleBet { bet_id, signature };
+            execute(deps.as_mut(), env.clone(), message_info(&player, &[]), msg).unwrap();
+        }
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let history = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&history).unwrap();
+        let mut tables: Vec<PayoutTableVersionResponse> = vec![];
+        for table_id in history.games.iter().filter_map(|game| game.table_id) {
+            let msg = QueryMsg::PayoutTableVersion { table_id };
+            let table = from_json(query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+            if !tables.contains(&table) {
+                tables.push(table);
+            }
+        }
+        // Regenerate the fixtures from this test whenever the records change on purpose
+        assert_eq!(
+            history,
+            from_json::<HistoryResponse>(HISTORY_FIXTURE).unwrap()
+        );
+        assert_eq!(
+            tables,
+            from_json::<Vec<PayoutTableVersionResponse>>(TABLES_FIXTURE).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_verify_game() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        let env = mock_env();
+        let info = message_info(&player, &coins(1_000, TOKEN_DENOM));
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Medium),
+            rows: None,
+            risk_level: RiskLevel::High,
+            client_seed: None,
+            ball_count: None,
+        };
+        play_settled(&mut deps, env.clone(), info, msg).unwrap();
+
+        let verify_msg = QueryMsg::VerifyGame {
+            player: player.to_string(),
+            game_index: 0,
+        };
+        let res = query(deps.as_ref(), env.clone(), verify_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        let record = GAME_HISTORY
+            .load(deps.as_ref().storage, (&player, 0))
+            .unwrap();
+
+        assert!(verification.verified);
+        assert_eq!(verification.rng_version, RNG_VERSION);
+        assert_eq!(record.rng_version, RNG_VERSION);
+        assert_eq!(verification.path, record.path);
+        assert_eq!(verification.win_amount, record.win_amount);
+        assert_eq!(verification.multiplier, record.multiplier);
+
+        // The table a game was paid on can be fetched for offline verification
+        let query_msg = QueryMsg::PayoutTableVersion {
+            table_id: record.table_id.unwrap(),
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let version: PayoutTableVersionResponse = from_json(&res).unwrap();
+        assert_eq!(version.multipliers[0], Decimal::from_ratio(170u128, 1u128));
+
+        // A new table, or none at all, leaves older games on the version they were paid on
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::SetPayoutTable {
+            rows: 12,
+            risk_level: RiskLevel::High,
+            multipliers: vec![Decimal::one(); 13],
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let res = query(deps.as_ref(), env.clone(), verify_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(verification.verified);
+        assert_eq!(verification.multiplier, record.multiplier);
+
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::RemovePayoutTable {
+            rows: 12,
+            risk_level: RiskLevel::High,
+        };
+        execute(deps.as_mut(), env.clone(), info, msg).unwrap();
+        let res = query(deps.as_ref(), env.clone(), verify_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(verification.verified);
+
+        // Games from before tables were versioned check the win against their own multiplier
+        let mut unversioned = record.clone();
+        unversioned.table_id = None;
+        GAME_HISTORY
+            .save(deps.as_mut().storage, (&player, 0), &unversioned)
+            .unwrap();
+        let res = query(deps.as_ref(), env.clone(), verify_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(verification.verified);
+        assert_eq!(verification.path, record.path);
+        unversioned.win_amount += Uint128::one();
+        GAME_HISTORY
+            .save(deps.as_mut().storage, (&player, 0), &unversioned)
+            .unwrap();
+        let res = query(deps.as_ref(), env.clone(), verify_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(!verification.verified);
+
+        // Stored inputs reproduce the original seed derivation bit for bit
+        let Some(SeedInputs::Commitment {
+            server_seed,
+            client_seed,
+            ..
+        }) = record.seed_inputs.clone()
+        else {
+            panic!("expected commitment seed inputs");
+        };
+        let seed = commitment_seed(server_seed.as_slice(), &client_seed, player.as_str(), 0);
+        let path: Vec<bool> = generate_path_from_seed(seed, 12)
+            .into_iter()
+            .map(|b| b != 0)
+            .collect();
+        assert_eq!(record.path, path);
+
+        // A record that does not match its inputs fails verification
+        let mut tampered = record.clone();
+        tampered.path = tampered.path.iter().map(|b| !b).collect();
+        GAME_HISTORY
+            .save(deps.as_mut().storage, (&player, 0), &tampered)
+            .unwrap();
+        let res = query(deps.as_ref(), env.clone(), verify_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(!verification.verified);
+
+        // Games played with the previous RNG still replay with it
+        let seed = derive_seed(record.seed_inputs.as_ref().unwrap(), player.as_str(), 0);
+        let outcome = play(
+            seed,
+            RNG_V1_REHASH,
+            &Difficulty::Medium,
+            &RiskLevel::High,
+            1_000,
+        )
+        .unwrap();
+        let mut v1 = record.clone();
+        v1.rng_version = RNG_V1_REHASH;
+        v1.path = outcome.path.iter().map(|&b| b != 0).collect();
+        v1.multiplier = outcome.multiplier;
+        v1.win_amount = Uint128::new(outcome.win_amount);
+        GAME_HISTORY
+            .save(deps.as_mut().storage, (&player, 0), &v1)
+            .unwrap();
+        let res = query(deps.as_ref(), env.clone(), verify_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(verification.verified);
+        assert_eq!(verification.rng_version, RNG_V1_REHASH);
+
+        // Games recorded before seed inputs were stored cannot be verified
+        let mut legacy = record;
+        legacy.rng_version = 0;
+        legacy.seed_inputs = None;
+        GAME_HISTORY
+            .save(deps.as_mut().storage, (&player, 0), &legacy)
+            .unwrap();
+        assert!(query(deps.as_ref(), env, verify_msg).is_err());
+    }
+
+    #[test]
+    fn test_play_multiple_balls() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        // Funds must split evenly between a valid number of balls
+        let play = |ball_count| ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Medium,
+            client_seed: None,
+            ball_count: Some(ball_count),
+        };
+        let info = message_info(&player, &coins(1_001, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, play(2)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidBetAmount {});
+        let info = message_info(&player, &coins(1_000, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, play(0)).unwrap_err();
+        assert_eq!(err, ContractError::InvalidBallCount { max: 100 });
+
+        let info = message_info(&player, &coins(5_000, TOKEN_DENOM));
+        let (_, res) = play_settled(&mut deps, mock_env(), info, play(5)).unwrap();
+        assert_eq!(res.events.len(), 5);
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res_history = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res_history).unwrap();
+        assert_eq!(history.games.len(), 5);
+
+        // Every ball is its own game with its own nonce and path
+        let mut nonces: Vec<u64> = history.games.iter().map(|game| game.nonce).collect();
+        nonces.sort();
+        assert_eq!(nonces, vec![0, 1, 2, 3, 4]);
+        assert!(history
+            .games
+            .iter()
+            .all(|game| game.bet_amount == Uint128::new(1_000)));
+
+        // Winnings of all balls are paid out in one transfer
+        let total_win: Uint128 = history.games.iter().map(|game| game.win_amount).sum();
+        assert_eq!(
+            res.messages
+                .iter()
+                .map(|msg| msg.msg.clone())
+                .collect::<Vec<_>>(),
+            vec![BankMsg::Send {
+                to_address: player.to_string(),
+                amount: coins(total_win.u128(), TOKEN_DENOM),
+            }
+            .into()]
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.total_games, 5);
+        assert_eq!(stats.total_wagered, Uint128::new(5_000));
+        assert_eq!(stats.total_won, total_win);
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UserStats {
+                player: player.to_string(),
+                denom: None,
+            },
+        )
+        .unwrap();
+        let user_stats: UserStatsResponse = from_json(&res).unwrap();
+        assert_eq!(user_stats.total_games, 5);
+
+        for game_index in 0..5 {
+            let res = query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::VerifyGame {
+                    player: player.to_string(),
+                    game_index,
+                },
+            )
+            .unwrap();
+            let verification: VerifyGameResponse = from_json(&res).unwrap();
+            assert!(verification.verified);
+        }
+    }
+
+    fn autobet_session(
+        deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+        player: &Addr,
+    ) -> Option<AutobetSessionResponse> {
+        query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AutobetSession {
+                player: player.to_string(),
+            },
+        )
+        .ok()
+        .map(|res| from_json(&res).unwrap())
+    }
+
+    #[test]
+    fn test_autobet_session() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        let keeper = deps.api.addr_make("keeper");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        let start = ExecuteMsg::StartAutobet {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            bet_amount: Uint128::new(1_000),
+            drops: 5,
+            stop_loss: None,
+            take_profit: None,
+            client_seed: Some("autobet".to_string()),
+        };
+        let info = message_info(&player, &coins(500, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, start.clone()).unwrap_err();
+        assert_eq!(err, ContractError::InvalidAutobetConfig {});
+
+        // Drops are bound to a server seed committed before the session starts
+        let info = message_info(&player, &coins(10_000, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info.clone(), start.clone()).unwrap_err();
+        assert_eq!(err, ContractError::NoActiveCommitment {});
+        let server_seed = b"autobet server seed";
+        let commitment_id = commit_seed(deps.as_mut(), &admin, server_seed);
+
+        execute(deps.as_mut(), mock_env(), info.clone(), start.clone()).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), info, start).unwrap_err();
+        assert_eq!(err, ContractError::AutobetSessionActive {});
+
+        // Deposits held for players are not house surplus
+        deps.querier.bank.update_balance(
+            mock_env().contract.address,
+            coins(1_000_010_000, TOKEN_DENOM),
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            ExecuteMsg::SyncBalance { denom: None },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "funds_recovered" && attr.value == "0"));
+
+        // Nothing drops before the seed is revealed
+        let advance = |max_drops| ExecuteMsg::AdvanceAutobet {
+            player: player.to_string(),
+            max_drops,
+        };
+        let info = message_info(&keeper, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, advance(None)).unwrap_err();
+        assert_eq!(err, ContractError::CommitmentNotRevealed { commitment_id });
+        reveal_seed(deps.as_mut(), &admin, commitment_id, server_seed);
+
+        // Anyone runs the first batch; winnings stay in the session
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&keeper, &[]),
+            advance(Some(2)),
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(res.events.len(), 2);
+        let session = autobet_session(&deps, &player).unwrap();
+        assert_eq!(session.drops_played, 2);
+        assert_eq!(session.drops_remaining, 3);
+
+        // The revealed seed fixes every remaining drop, so the player can no longer stop
+        let info = message_info(&player, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, ExecuteMsg::StopAutobet {}).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::CommitmentAlreadyRevealed { commitment_id }
+        );
+
+        // The player finishes it and gets the balance back
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&player, &[]),
+            advance(None),
+        )
+        .unwrap();
+        assert_eq!(res.events.len(), 3);
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "stop_reason" && attr.value == "completed"));
+        assert!(autobet_session(&deps, &player).is_none());
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res_history = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res_history).unwrap();
+        assert_eq!(history.games.len(), 5);
+        for (nonce, game) in history.games.iter().enumerate() {
+            let seed = commitment_seed(server_seed, "autobet", player.as_str(), nonce as u64);
+            let path: Vec<bool> = generate_path_from_seed(seed, 8)
+                .into_iter()
+                .map(|b| b != 0)
+                .collect();
+            assert_eq!(game.path, path);
+        }
+        let won: Uint128 = history.games.iter().map(|game| game.win_amount).sum();
+        let balance = Uint128::new(10_000) - Uint128::new(5_000) + won;
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: player.to_string(),
+                amount: coins(balance.u128(), TOKEN_DENOM),
+            }
+            .into()
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.autobet_balance, Uint128::zero());
+        assert_eq!(
+            stats.house_balance,
+            Uint128::new(1_000_000_000 + 5_000) - won
+        );
+    }
+
+    #[test]
+    fn test_autobet_limits() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        // Any loss at all trips the stop-loss
+        let commitment_id = commit_seed(deps.as_mut(), &admin, b"first seed");
+        let info = message_info(&player, &coins(100_000, TOKEN_DENOM));
+        let start = ExecuteMsg::StartAutobet {
+            difficulty: Some(Difficulty::Hard),
+            rows: None,
+            risk_level: RiskLevel::High,
+            bet_amount: Uint128::new(1_000),
+            drops: 100,
+            stop_loss: Some(Uint128::new(1)),
+            take_profit: None,
+            client_seed: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, start).unwrap();
+        reveal_seed(deps.as_mut(), &admin, commitment_id, b"first seed");
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&player, &[]),
+            ExecuteMsg::AdvanceAutobet {
+                player: player.to_string(),
+                max_drops: None,
+            },
+        )
+        .unwrap();
+        assert!(res
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "stop_reason" && attr.value == "stop_loss"));
+        assert!(res.events.len() < 100);
+        assert!(autobet_session(&deps, &player).is_none());
+
+        // Stopping before the reveal returns the untouched deposit
+        let commitment_id = commit_seed(deps.as_mut(), &admin, b"second seed");
+        let info = message_info(&player, &coins(3_000, TOKEN_DENOM));
+        let start = ExecuteMsg::StartAutobet {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            bet_amount: Uint128::new(1_000),
+            drops: 3,
+            stop_loss: None,
+            take_profit: Some(Uint128::new(500)),
+            client_seed: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), start.clone()).unwrap();
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&player, &[]),
+            ExecuteMsg::StopAutobet {},
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: player.to_string(),
+                amount: coins(3_000, TOKEN_DENOM),
+            }
+            .into()
+        );
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&player, &[]),
+            ExecuteMsg::StopAutobet {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoAutobetSession {});
+
+        // Limits are checked again before every drop
+        execute(deps.as_mut(), mock_env(), info, start).unwrap();
+        let msg = ExecuteMsg::SetMaxExposure {
+            max_exposure: Decimal::from_ratio(1u128, 1_000_000u128),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        reveal_seed(deps.as_mut(), &admin, commitment_id, b"second seed");
+        let msg = ExecuteMsg::AdvanceAutobet {
+            player: player.to_string(),
+            max_drops: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&player, &[]), msg).unwrap();
+        assert!(res.events.is_empty());
+        assert!(res
+            .attributes
+            .contains(&("stop_reason", "bet_limits").into()));
+        assert!(res.attributes.contains(&("refund", "3000").into()));
+    }
+
+    #[test]
+    fn test_payout_tables() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        // The built-in tables are installed at instantiate
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PayoutTables {}).unwrap();
+        let tables: PayoutTablesResponse = from_json(&res).unwrap();
+        assert_eq!(tables.tables.len(), 27);
+        let query_msg = QueryMsg::PayoutTable {
+            rows: 8,
+            risk_level: RiskLevel::Low,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let table: PayoutTableResponse = from_json(&res).unwrap();
+        assert_eq!(table.multipliers[0], Decimal::permille(5600));
+        assert_eq!(table.rtp, "0.98984375".parse::<Decimal>().unwrap());
+
+        let set_table = |multipliers: Vec<Decimal>| ExecuteMsg::SetPayoutTable {
+            rows: 8,
+            risk_level: RiskLevel::Low,
+            multipliers,
+        };
+        let flat = vec![Decimal::one(); 9];
+
+        let info = message_info(&player, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, set_table(flat.clone())).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let info = message_info(&admin, &[]);
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            set_table(vec![Decimal::one(); 8]),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::PayoutTableLength { expected: 9 });
+
+        let mut lopsided = flat.clone();
+        lopsided[0] = Decimal::percent(200);
+        let info = message_info(&admin, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, set_table(lopsided)).unwrap_err();
+        assert_eq!(err, ContractError::PayoutTableAsymmetric {});
+
+        // Multipliers can have any number of decimals
+        let mut fine = flat.clone();
+        fine[0] = "0.999".parse().unwrap();
+        fine[8] = fine[0];
+        let info = message_info(&admin, &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, set_table(fine)).unwrap();
+        assert!(res.attributes.contains(&("rtp", "0.9999921875").into()));
+
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::SetPayoutTable {
+            rows: 17,
+            risk_level: RiskLevel::Low,
+            multipliers: vec![Decimal::one(); 18],
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidRows { rows: 17 });
+
+        // A flat 1x table returns exactly the bet, which the default ceiling allows
+        let info = message_info(&admin, &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, set_table(flat.clone())).unwrap();
+        assert!(res.attributes.contains(&("rtp", "1").into()));
+
+        let play = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: Some(5),
+        };
+        let info = message_info(&player, &coins(500, TOKEN_DENOM));
+        let (_, res) = play_settled(&mut deps, mock_env(), info, play.clone()).unwrap();
+        assert_eq!(settled(&res, "win_amount"), vec!["100"; 5]);
+
+        // Lowering the ceiling rejects tables above it
+        for max_rtp in [Decimal::zero(), Decimal::percent(101)] {
+            let info = message_info(&admin, &[]);
+            let msg = ExecuteMsg::SetMaxRtp { max_rtp };
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidMaxRtp {});
+        }
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::SetMaxRtp {
+            max_rtp: Decimal::percent(97),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_json(&res).unwrap();
+        assert_eq!(config.max_rtp, Decimal::percent(97));
+
+        let info = message_info(&admin, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, set_table(flat)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RtpAboveCeiling {
+                rtp: Decimal::one(),
+                max_rtp: Decimal::percent(97),
+            }
+        );
+
+        // Removing a table stops play on it
+        let remove = ExecuteMsg::RemovePayoutTable {
+            rows: 8,
+            risk_level: RiskLevel::Low,
+        };
+        let info = message_info(&admin, &[]);
+        execute(deps.as_mut(), mock_env(), info, remove.clone()).unwrap();
+        let info = message_info(&admin, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, remove).unwrap_err();
+        let not_found = ContractError::PayoutTableNotFound {
+            rows: 8,
+            risk_level: "low".to_string(),
+        };
+        assert_eq!(err, not_found);
+
+        let info = message_info(&player, &coins(500, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, play).unwrap_err();
+        assert_eq!(err, not_found);
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::PayoutTables {}).unwrap();
+        let tables: PayoutTablesResponse = from_json(&res).unwrap();
+        assert_eq!(tables.tables.len(), 26);
+    }
+
+    #[test]
+    fn test_payout_table_fixed_at_play() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        let server_seed = b"table seed";
+        let commitment_id = commit_seed(deps.as_mut(), &admin, server_seed);
+        let play = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: Some(2),
+        };
+        let info = message_info(&player, &coins(200, TOKEN_DENOM));
+        execute(deps.as_mut(), mock_env(), info, play).unwrap();
+        let table_id = PAYOUT_TABLE_IDS
+            .load(deps.as_ref().storage, (8, "low"))
+            .unwrap();
+        let table = PAYOUT_TABLE_VERSIONS
+            .load(deps.as_ref().storage, table_id)
+            .unwrap();
+
+        // The table the balls were placed on cannot be removed before they settle
+        let remove = ExecuteMsg::RemovePayoutTable {
+            rows: 8,
+            risk_level: RiskLevel::Low,
+        };
+        let info = message_info(&admin, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, remove.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::PayoutTableInUse {
+                rows: 8,
+                risk_level: "low".to_string(),
+                pending: 2,
+            }
+        );
+
+        // A replacement nobody played on can be set and removed before the reveal
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::SetPayoutTable {
+            rows: 8,
+            risk_level: RiskLevel::Low,
+            multipliers: vec![Decimal::zero(); 9],
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let info = message_info(&admin, &[]);
+        execute(deps.as_mut(), mock_env(), info, remove).unwrap();
+
+        // The balls still settle, on the table they were placed on
+        reveal_seed(deps.as_mut(), &admin, commitment_id, server_seed);
+        let msg = ExecuteMsg::SettleGames {
+            commitment_id,
+            limit: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&player, &[]), msg).unwrap();
+        let buckets = settled(&res, "bucket");
+        let wins = settled(&res, "win_amount");
+        assert_eq!(wins.len(), 2);
+        for (bucket, win) in buckets.iter().zip(&wins) {
+            let multiplier = table.0[bucket.parse::<usize>().unwrap()];
+            assert_eq!(*win, Uint128::new(100).mul_floor(multiplier).to_string());
+        }
+        for nonce in 0..2 {
+            let record = GAME_HISTORY
+                .load(deps.as_ref().storage, (&player, nonce))
+                .unwrap();
+            assert_eq!(record.table_id, Some(table_id));
+        }
+        assert!(PAYOUT_TABLE_HOLDS.is_empty(deps.as_ref().storage));
+    }
+
+    #[test]
+    fn test_play_rows() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        let play = |difficulty: Option<Difficulty>, rows: Option<u8>| ExecuteMsg::Play {
+            difficulty,
+            rows,
+            risk_level: RiskLevel::High,
+            client_seed: None,
+            ball_count: None,
+        };
+
+        // Exactly one board, or a difficulty and its own row count
+        for (difficulty, rows) in [(None, None), (Some(Difficulty::Easy), Some(10))] {
+            let info = message_info(&player, &coins(1_000, TOKEN_DENOM));
+            let err = execute(deps.as_mut(), mock_env(), info, play(difficulty, rows)).unwrap_err();
+            assert_eq!(err, ContractError::InvalidBoard {});
+        }
+        for rows in [7, 17] {
+            let info = message_info(&player, &coins(1_000, TOKEN_DENOM));
+            let err = execute(deps.as_mut(), mock_env(), info, play(None, Some(rows))).unwrap_err();
+            assert_eq!(err, ContractError::InvalidRows { rows });
+        }
+
+        // Any row count from 8 to 16, with the difficulties as aliases
+        for rows in 8..=16 {
+            let info = message_info(&player, &coins(1_000, TOKEN_DENOM));
+            play_settled(&mut deps, mock_env(), info, play(None, Some(rows))).unwrap();
+        }
+        let info = message_info(&player, &coins(1_000, TOKEN_DENOM));
+        let msg = play(Some(Difficulty::Medium), Some(12));
+        play_settled(&mut deps, mock_env(), info, msg).unwrap();
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: Some(10),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        assert_eq!(history.games.len(), 10);
+        for game in &history.games {
+            assert_eq!(game.path.len(), game.rows as usize);
+            assert_eq!(game.difficulty, Difficulty::from_rows(game.rows));
+        }
+        let mut rows: Vec<u8> = history.games.iter().map(|game| game.rows).collect();
+        rows.sort();
+        assert_eq!(rows, vec![8, 9, 10, 11, 12, 12, 13, 14, 15, 16]);
+
+        // The best win keeps the board it was won on
+        let query_msg = QueryMsg::UserStats {
+            player: player.to_string(),
+            denom: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let user_stats: UserStatsResponse = from_json(&res).unwrap();
+        let best = history
+            .games
+            .iter()
+            .filter(|game| game.pnl == Int128::try_from(user_stats.best_win_pnl).unwrap())
+            .map(|game| game.rows)
+            .collect::<Vec<_>>();
+        if !user_stats.best_win_pnl.is_zero() {
+            assert!(best.contains(&user_stats.best_win_rows));
+            let query_msg = QueryMsg::GlobalLeaderboard {
+                leaderboard_type: LeaderboardType::BestWins,
+                limit: None,
+                denom: None,
+            };
+            let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+            let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+            assert_eq!(leaderboard.entries[0].rows, Some(user_stats.best_win_rows));
+        }
+
+        // Placed bets keep their row count until they settle
+        let info = message_info(&player, &coins(1_000, TOKEN_DENOM));
+        let msg = ExecuteMsg::PlaceBet {
+            difficulty: None,
+            rows: Some(11),
+            risk_level: RiskLevel::Low,
+            randomness: None,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PendingBet { bet_id: 0 },
+        )
+        .unwrap();
+        let pending: PendingBetResponse = from_json(&res).unwrap();
+        assert_eq!(pending.rows, 11);
+        assert_eq!(pending.difficulty, None);
+
+        let mut env = mock_env();
+        env.block.height += 1;
+        let msg = ExecuteMsg::SettleBet {
+            bet_id: 0,
+            signature: None,
+        };
+        execute(deps.as_mut(), env.clone(), message_info(&admin, &[]), msg).unwrap();
+        let game = GAME_HISTORY.load(&deps.storage, (&player, 10)).unwrap();
+        assert_eq!(game.rows, 11);
+        assert_eq!(game.path.len(), 11);
+
+        let query_msg = QueryMsg::VerifyGame {
+            player: player.to_string(),
+            game_index: 10,
+        };
+        let res = query(deps.as_ref(), env, query_msg).unwrap();
+        let verified: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(verified.verified);
+    }
+
+    #[test]
+    fn test_game_odds() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+
+        let query_msg = QueryMsg::GameOdds {
+            difficulty: Some(Difficulty::Hard),
+            rows: None,
+            risk_level: RiskLevel::High,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let odds: GameOddsResponse = from_json(&res).unwrap();
+        assert_eq!(odds.rows, 16);
+        assert_eq!(odds.max_multiplier, Decimal::from_ratio(1000u128, 1u128));
+        assert!(odds.rtp > Decimal::percent(98) && odds.rtp < Decimal::one());
+        assert!(odds.house_edge > SignedDecimal::zero());
+        assert!(odds.variance > Decimal::from_ratio(40u128, 1u128));
+
+        // Every board and risk level has a house edge
+        for rows in 8..=16 {
+            for risk_level in [RiskLevel::Low, RiskLevel::Medium, RiskLevel::High] {
+                let query_msg = QueryMsg::GameOdds {
+                    difficulty: None,
+                    rows: Some(rows),
+                    risk_level,
+                };
+                let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+                let odds: GameOddsResponse = from_json(&res).unwrap();
+                assert!(odds.house_edge > SignedDecimal::zero());
+            }
+        }
+
+        // Odds follow the table the admin puts in place
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::SetPayoutTable {
+            rows: 8,
+            risk_level: RiskLevel::Low,
+            multipliers: vec![Decimal::one(); 9],
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let query_msg = QueryMsg::GameOdds {
+            difficulty: None,
+            rows: Some(8),
+            risk_level: RiskLevel::Low,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let odds: GameOddsResponse = from_json(&res).unwrap();
+        assert_eq!(odds.rtp, Decimal::one());
+        assert!(odds.house_edge.is_zero());
+        assert_eq!(odds.variance, Decimal::zero());
+        assert_eq!(odds.max_multiplier, Decimal::one());
+    }
+
+    #[test]
+    fn test_migrate_from_original_layout() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        let env = mock_env();
+
+        // State as the original contract left it, which stored no contract version
+        let storage = deps.as_mut().storage;
+        let config = format!(
+            r#"{{"token_denom":"{TOKEN_DENOM}","admin":"{admin}","funder_address":"{admin}"}}"#
+        );
+        storage.set(CONFIG.as_slice(), config.as_bytes());
+        let stats = r#"{"total_games":1,"total_wagered":"1000","total_won":"500","house_balance":"1000500"}"#;
+        storage.set(LEGACY_STATS.as_slice(), stats.as_bytes());
+        let user_stats = r#"{"total_games":1,"total_wagered":"1000","total_won":"500","best_win_pnl":"0","best_win_multiplier":"0.0x"}"#;
+        storage.set(&LEGACY_USER_STATS.key(&player), user_stats.as_bytes());
+        let wagered = format!(r#"[{{"player":"{player}","value":"1000","multiplier":null}}]"#);
+        storage.set(LEGACY_TOTAL_WAGERED.as_slice(), wagered.as_bytes());
+        let best_wins = format!(r#"[{{"player":"{admin}","value":"4600","multiplier":"5.6x"}}]"#);
+        storage.set(LEGACY_BEST_WINS.as_slice(), best_wins.as_bytes());
+        let daily = format!(
+            r#"{{"last_reset":{},"entries_best_wins":[],"entries_wagered":{wagered}}}"#,
+            env.block.time.seconds()
+        );
+        storage.set(LEGACY_DAILY_LEADERBOARD.as_slice(), daily.as_bytes());
+        let record = format!(
+            r#"{{"player":"{player}","difficulty":"easy","risk_level":"low","bet_amount":"1000","multiplier":"0.5x","win_amount":"500","pnl":"0","timestamp":{},"path":[true,false,true,false,true,false,true,false]}}"#,
+            env.block.time.seconds()
+        );
+        storage.set(&GAME_HISTORY.key((&player, 0)), record.as_bytes());
+        storage.set(&PLAYER_GAME_COUNT.key(&player), b"1");
+
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        assert!(res.attributes.contains(&("from_version", "").into()));
+        let version = cw2::get_contract_version(deps.as_ref().storage).unwrap();
+        assert_eq!(version.contract, "crates.io:plinko-game");
+        assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_json(&res).unwrap();
+        assert_eq!(config.admin, admin);
+        assert_eq!(config.settle_delay_blocks, DEFAULT_SETTLE_DELAY_BLOCKS);
+        let res = query(deps.as_ref(), env.clone(), QueryMsg::PayoutTables {}).unwrap();
+        let tables: PayoutTablesResponse = from_json(&res).unwrap();
+        assert_eq!(tables.tables.len(), 27);
+
+        // The original boards moved to the indexed map
+        let storage = deps.as_ref().storage;
+        assert!(!LEGACY_BEST_WINS.exists(storage));
+        assert!(!LEGACY_TOTAL_WAGERED.exists(storage));
+        assert!(!LEGACY_DAILY_LEADERBOARD.exists(storage));
+        let query_msg = QueryMsg::GlobalLeaderboard {
+            leaderboard_type: LeaderboardType::BestWins,
+            limit: None,
+            denom: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+        assert_eq!(leaderboard.entries[0].player, admin);
+        assert_eq!(
+            leaderboard.entries[0].multiplier,
+            Some(Decimal::permille(5600))
+        );
+        assert_eq!(leaderboard.entries[0].rows, None);
+
+        // The original stats, leaderboards and history read as they were
+        let query_msg = QueryMsg::Stats { denom: None };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.house_balance, Uint128::new(1_000_500));
+        let query_msg = QueryMsg::DailyLeaderboard {
+            leaderboard_type: LeaderboardType::TotalWagered,
+            limit: None,
+            denom: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+        assert_eq!(leaderboard.entries[0].player, player);
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        assert_eq!(history.games[0].multiplier, Decimal::percent(50));
+        assert_eq!(history.games[0].board_rows(), 8);
+
+        // Play picks up from the original game count, stats and boards
+        let play = ExecuteMsg::Play {
+            difficulty: None,
+            rows: Some(8),
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        let info = message_info(&player, &coins(1_000, TOKEN_DENOM));
+        let (_, res) = play_settled(&mut deps, env.clone(), info, play).unwrap();
+        assert_eq!(settled(&res, "nonce"), vec!["1"]);
+        let query_msg = QueryMsg::UserStats {
+            player: player.to_string(),
+            denom: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let user_stats: UserStatsResponse = from_json(&res).unwrap();
+        assert_eq!(user_stats.total_games, 2);
+        assert_eq!(user_stats.total_wagered, Uint128::new(2_000));
+        let query_msg = QueryMsg::GlobalLeaderboard {
+            leaderboard_type: LeaderboardType::TotalWagered,
+            limit: None,
+            denom: None,
+        };
+        let res = query(deps.as_ref(), env.clone(), query_msg).unwrap();
+        let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+        assert_eq!(leaderboard.entries.len(), 1);
+        assert_eq!(leaderboard.entries[0].value, Int128::new(2_000));
+
+        // Migrating again keeps the version; another contract or a newer version is refused
+        let res = migrate(deps.as_mut(), env.clone(), MigrateMsg {}).unwrap();
+        let from_version = env!("CARGO_PKG_VERSION");
+        assert!(res
+            .attributes
+            .contains(&("from_version", from_version).into()));
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:plinko-game", "99.0.0")
+            .unwrap();
+        assert!(migrate(deps.as_mut(), env.clone(), MigrateMsg {}).is_err());
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:other", "0.0.1").unwrap();
+        assert!(migrate(deps.as_mut(), env, MigrateMsg {}).is_err());
+    }
+
+    #[test]
+    fn test_legacy_multipliers() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000));
+
+        // Entries written before multipliers were Decimal, played on a quarter table
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::SetPayoutTable {
+            rows: 8,
+            risk_level: RiskLevel::Low,
+            multipliers: vec![Decimal::percent(25); 9],
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let storage = deps.as_mut().storage;
+        let user_stats = r#"{"total_games":0,"total_wagered":"0","total_won":"0","best_win_pnl":"0","best_win_multiplier":"0.0x"}"#;
+        storage.set(&LEGACY_USER_STATS.key(&player), user_stats.as_bytes());
+
+        let res = query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::PayoutTable {
+                rows: 8,
+                risk_level: RiskLevel::Low,
+            },
+        )
+        .unwrap();
+        let table: PayoutTableResponse = from_json(&res).unwrap();
+        assert_eq!(table.multipliers, vec![Decimal::percent(25); 9]);
+        assert_eq!(table.rtp, Decimal::percent(25));
+
+        // Play on the legacy table, then store the game the way it used to be recorded
+        let play = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        let info = message_info(&player, &coins(1000, TOKEN_DENOM));
+        let (_, res) = play_settled(&mut deps, mock_env(), info, play).unwrap();
+        assert_eq!(settled(&res, "multiplier"), vec!["0.25"]);
+        assert_eq!(settled(&res, "win_amount"), vec!["250"]);
+
+        let mut record = GAME_HISTORY
+            .load(deps.as_ref().storage, (&player, 0))
+            .unwrap();
+        record.table_id = None;
+        GAME_HISTORY
+            .save(deps.as_mut().storage, (&player, 0), &record)
+            .unwrap();
+        let key = GAME_HISTORY.key((&player, 0));
+        let record = String::from_utf8(deps.as_ref().storage.get(&key).unwrap()).unwrap();
+        let record = record.replace(r#""multiplier":"0.25""#, r#""multiplier":"0.2x""#);
+        deps.as_mut().storage.set(&key, record.as_bytes());
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        assert_eq!(history.games[0].multiplier, Decimal::percent(20));
+
+        // Without a table version the win is checked against the one decimal the record kept
+        let query_msg = QueryMsg::VerifyGame {
+            player: player.to_string(),
+            game_index: 0,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(!verification.verified);
+        assert_eq!(verification.multiplier, Decimal::percent(20));
+        assert_eq!(verification.win_amount, Uint128::new(200));
+
+        // A game paid on a table version must carry the exact multiplier, not one decimal of it
+        let table = PayoutTable(vec![Decimal::percent(25); 9]);
+        PAYOUT_TABLE_VERSIONS
+            .save(deps.as_mut().storage, 100, &table)
+            .unwrap();
+        let mut record = GAME_HISTORY
+            .load(deps.as_ref().storage, (&player, 0))
+            .unwrap();
+        record.table_id = Some(100);
+        GAME_HISTORY
+            .save(deps.as_mut().storage, (&player, 0), &record)
+            .unwrap();
+        let res = query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(!verification.verified);
+        assert_eq!(verification.multiplier, Decimal::percent(25));
+
+        record.multiplier = Decimal::percent(25);
+        GAME_HISTORY
+            .save(deps.as_mut().storage, (&player, 0), &record)
+            .unwrap();
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let verification: VerifyGameResponse = from_json(&res).unwrap();
+        assert!(verification.verified);
+
+        // Stats saved since are written as Decimal, under the denom
+        let query_msg = QueryMsg::UserStats {
+            player: player.to_string(),
+            denom: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let user_stats: UserStatsResponse = from_json(&res).unwrap();
+        assert_eq!(user_stats.total_games, 1);
+        assert_eq!(user_stats.best_win_multiplier, Decimal::zero());
+        let raw = deps
+            .as_ref()
+            .storage
+            .get(&USER_STATS.key((TOKEN_DENOM, &player)))
+            .unwrap();
+        assert!(!String::from_utf8(raw).unwrap().contains('x'));
+    }
+
+    #[test]
+    fn test_net_pnl_stats() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player1 = deps.api.addr_make("player1");
+        let player2 = deps.api.addr_make("player2");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        // Every drop on a flat 0.5x table loses half the bet
+        let info = message_info(&admin, &[]);
+        let msg = ExecuteMsg::SetPayoutTable {
+            rows: 8,
+            risk_level: RiskLevel::Low,
+            multipliers: vec![Decimal::percent(50); 9],
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        let msg = ExecuteMsg::Play {
+            difficulty: None,
+            rows: Some(8),
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: Some(2),
+        };
+        let info = message_info(&player1, &coins(2000, TOKEN_DENOM));
+        let (_, res) = play_settled(&mut deps, mock_env(), info, msg).unwrap();
+        assert_eq!(settled(&res, "win_amount"), vec!["500", "500"]);
+
+        let query_msg = QueryMsg::UserStats {
+            player: player1.to_string(),
+            denom: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let stats1: UserStatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats1.net_pnl, Int128::new(-1000));
+        assert_eq!(stats1.rtp, Decimal::percent(50));
+        assert_eq!((stats1.wins, stats1.losses), (0, 2));
+        assert_eq!(stats1.biggest_loss, Uint128::new(500));
+        assert_eq!(stats1.best_win_pnl, Uint128::zero());
+
+        let query_msg = QueryMsg::History {
+            player: player1.to_string(),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        assert!(history.games.iter().all(|g| g.pnl == Int128::new(-500)));
+
+        // Mixed results on the 1000x board add up to the recorded games
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Hard),
+            rows: None,
+            risk_level: RiskLevel::High,
+            client_seed: None,
+            ball_count: Some(20),
+        };
+        let info = message_info(&player2, &coins(20_000, TOKEN_DENOM));
+        play_settled(&mut deps, mock_env(), info, msg).unwrap();
+
+        let query_msg = QueryMsg::History {
+            player: player2.to_string(),
+            limit: Some(20),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        let net: Int128 = history.games.iter().map(|g| g.pnl).sum();
+        let wins = history
+            .games
+            .iter()
+            .filter(|g| g.pnl > Int128::zero())
+            .count() as u64;
+        let losses = history.games.iter().filter(|g| g.pnl.is_negative()).count() as u64;
+        let biggest_loss = history
+            .games
+            .iter()
+            .map(|g| g.bet_amount.saturating_sub(g.win_amount))
+            .max()
+            .unwrap();
+        assert!(losses > 0);
+
+        let query_msg = QueryMsg::UserStats {
+            player: player2.to_string(),
+            denom: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let stats2: UserStatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats2.net_pnl, net);
+        assert_eq!((stats2.wins, stats2.losses), (wins, losses));
+        assert_eq!(stats2.biggest_loss, biggest_loss);
+        assert_eq!(
+            stats2.rtp,
+            Decimal::from_ratio(stats2.total_won, stats2.total_wagered)
+        );
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.net_pnl, net - Int128::new(1000));
+        assert_eq!((stats.wins, stats.losses), (wins, losses + 2));
+        assert_eq!(stats.biggest_loss, biggest_loss.max(Uint128::new(500)));
+
+        // Both players rank by net result, losses included
+        for query_msg in [
+            QueryMsg::GlobalLeaderboard {
+                leaderboard_type: LeaderboardType::NetProfit,
+                limit: None,
+                denom: None,
+            },
+            QueryMsg::DailyLeaderboard {
+                leaderboard_type: LeaderboardType::NetProfit,
+                limit: None,
+                denom: None,
+            },
+        ] {
+            let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+            let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+            assert_eq!(leaderboard.entries.len(), 2);
+            assert!(leaderboard.entries[0].value >= leaderboard.entries[1].value);
+            let entry1 = leaderboard
+                .entries
+                .iter()
+                .find(|e| e.player == player1)
+                .unwrap();
+            assert_eq!(entry1.value, Int128::new(-1000));
+        }
+    }
+
+    #[test]
+    fn test_bet_limits() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000));
+
+        let limits = |deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>| -> BetLimitsResponse {
+            let query_msg = QueryMsg::BetLimits {
+                denom: None,
+                difficulty: None,
+                rows: Some(8),
+                risk_level: RiskLevel::Low,
+            };
+            from_json(query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap()
+        };
+        let play = |ball_count: Option<u32>| ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count,
+        };
+
+        // Without configured limits a 5.6x win may take the whole house balance
+        let initial = limits(&deps);
+        assert_eq!(initial.min_bet, Uint128::one());
+        assert_eq!(initial.configured_max_bet, None);
+        assert_eq!(initial.exposure_max_bet, Uint128::new(178_571));
+        assert_eq!(initial.max_bet, Uint128::new(178_571));
+
+        let info = message_info(&player, &coins(178_572, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, play(None)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetAboveMaximum {
+                max_bet: Uint128::new(178_571)
+            }
+        );
+
+        // Balls of one Play share the cap, even when each would fit alone
+        let info = message_info(&player, &coins(200_000, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, play(Some(2))).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetAboveMaximum {
+                max_bet: Uint128::new(89_285)
+            }
+        );
+
+        // The exposure share is bounded and admin only
+        for max_exposure in [Decimal::zero(), Decimal::percent(101)] {
+            let info = message_info(&admin, &[]);
+            let msg = ExecuteMsg::SetMaxExposure { max_exposure };
+            let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidMaxExposure {});
+        }
+        let msg = ExecuteMsg::SetMaxExposure {
+            max_exposure: Decimal::percent(10),
+        };
+        let info = message_info(&player, &[]);
+        let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let info = message_info(&admin, &[]);
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+        assert_eq!(limits(&deps).max_bet, Uint128::new(17_857));
+
+        // Configured bounds on top of the exposure cap
+        let set_limits = |min_bet: Option<u128>, max_bet: Option<u128>| ExecuteMsg::SetBetLimits {
+            denom: None,
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            min_bet: min_bet.map(Uint128::new),
+            max_bet: max_bet.map(Uint128::new),
+        };
+        for (min_bet, max_bet) in [(Some(200), Some(100)), (Some(0), None), (None, Some(0))] {
+            let info = message_info(&admin, &[]);
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                info,
+                set_limits(min_bet, max_bet),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::InvalidBetLimits {});
+        }
+        let info = message_info(&admin, &[]);
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            set_limits(Some(100), Some(10_000)),
+        )
+        .unwrap();
+        let configured = limits(&deps);
+        assert_eq!(configured.min_bet, Uint128::new(100));
+        assert_eq!(configured.max_bet, Uint128::new(10_000));
+        assert_eq!(configured.configured_max_bet, Some(Uint128::new(10_000)));
+
+        let info = message_info(&player, &coins(99, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, play(None)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetBelowMinimum {
+                min_bet: Uint128::new(100)
+            }
+        );
+        let info = message_info(&player, &coins(10_001, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, play(None)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetAboveMaximum {
+                max_bet: Uint128::new(10_000)
+            }
+        );
+        let info = message_info(&player, &coins(10_000, TOKEN_DENOM));
+        play_settled(&mut deps, mock_env(), info, play(None)).unwrap();
+
+        // Limits apply per ball and per drop, on every way to bet
+        let info = message_info(&player, &coins(198, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, play(Some(2))).unwrap_err();
+        assert!(matches!(err, ContractError::BetBelowMinimum { .. }));
+        let info = message_info(&player, &coins(200, TOKEN_DENOM));
+        play_settled(&mut deps, mock_env(), info, play(Some(2))).unwrap();
+
+        let msg = ExecuteMsg::PlaceBet {
+            difficulty: None,
+            rows: Some(8),
+            risk_level: RiskLevel::Low,
+            randomness: None,
+        };
+        let info = message_info(&player, &coins(20_000, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::BetAboveMaximum { .. }));
+
+        let msg = ExecuteMsg::StartAutobet {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            bet_amount: Uint128::new(50),
+            drops: 10,
+            stop_loss: None,
+            take_profit: None,
+            client_seed: None,
+        };
+        let info = message_info(&player, &coins(500, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert!(matches!(err, ContractError::BetBelowMinimum { .. }));
+
+        // Clearing both bounds leaves only the exposure cap
+        let info = message_info(&admin, &[]);
+        execute(deps.as_mut(), mock_env(), info, set_limits(None, None)).unwrap();
+        let cleared = limits(&deps);
+        assert_eq!(cleared.min_bet, Uint128::one());
+        assert_eq!(cleared.configured_max_bet, None);
+        assert_eq!(cleared.max_bet, cleared.exposure_max_bet);
+    }
+
+    #[test]
+    fn test_multiple_denoms() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000));
+
+        let set_denom = ExecuteMsg::SetDenom {
+            denom: "uatom".to_string(),
+            min_bet: Some(Uint128::new(10)),
+            max_bet: Some(Uint128::new(1000)),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&player, &[]),
+            set_denom.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            set_denom,
+        )
+        .unwrap();
+
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Denoms {}).unwrap();
+        let denoms: DenomsResponse = from_json(&res).unwrap();
+        assert_eq!(denoms.denoms.len(), 2);
+        assert!(denoms
+            .denoms
+            .iter()
+            .any(|denom| denom.denom == TOKEN_DENOM && denom.default));
+        assert!(denoms.denoms.iter().any(|denom| denom.denom == "uatom"
+            && !denom.default
+            && denom.min_bet == Some(Uint128::new(10))));
+
+        // The new denom's house starts empty, whatever the default denom holds
+        let play = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        let bet = |amount: u128| message_info(&player, &coins(amount, "uatom"));
+        let err = execute(deps.as_mut(), mock_env(), bet(100), play.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetAboveMaximum {
+                max_bet: Uint128::zero()
+            }
+        );
+
+        let fund = ExecuteMsg::FundHouse {
+            denom: Some("uatom".to_string()),
+        };
+        let info = message_info(&admin, &coins(1_000_000, TOKEN_DENOM));
+        let err = execute(deps.as_mut(), mock_env(), info, fund.clone()).unwrap_err();
+        assert_eq!(err, ContractError::NoFundsSent {});
+        let info = message_info(&admin, &coins(1_000_000, "uatom"));
+        execute(deps.as_mut(), mock_env(), info, fund).unwrap();
+
+        // The denom's bounds apply on every board
+        let err = execute(deps.as_mut(), mock_env(), bet(5), play.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetBelowMinimum {
+                min_bet: Uint128::new(10)
+            }
+        );
+        let err = execute(deps.as_mut(), mock_env(), bet(2000), play.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BetAboveMaximum {
+                max_bet: Uint128::new(1000)
+            }
+        );
+        let info = message_info(&player, &[coin(100, "uatom"), coin(100, TOKEN_DENOM)]);
+        let err = execute(deps.as_mut(), mock_env(), info, play.clone()).unwrap_err();
+        assert_eq!(err, ContractError::MultipleDenoms {});
+
+        // Winnings are paid in the denom of the bet
+        let (res, settle) = play_settled(&mut deps, mock_env(), bet(100), play.clone()).unwrap();
+        assert!(res.attributes.contains(&("denom", "uatom").into()));
+        assert_eq!(settled(&settle, "denom"), vec!["uatom"]);
+        match &settle.messages[0].msg {
+            cosmwasm_std::CosmosMsg::Bank(BankMsg::Send { amount, .. }) => {
+                assert_eq!(amount[0].denom, "uatom")
+            }
+            _ => panic!("Expected a bank send"),
+        }
+
+        // Stats and leaderboards are kept apart per denom
+        let stats_of = |deps: &OwnedDeps<_, _, _>, denom: Option<&str>| -> StatsResponse {
+            let query_msg = QueryMsg::Stats {
+                denom: denom.map(str::to_string),
+            };
+            from_json(query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap()
+        };
+        let atom_stats = stats_of(&deps, Some("uatom"));
+        assert_eq!(atom_stats.denom, "uatom");
+        assert_eq!(atom_stats.total_games, 1);
+        assert_eq!(atom_stats.total_wagered, Uint128::new(100));
+        assert_eq!(
+            atom_stats.house_balance,
+            Uint128::new(1_000_100) - atom_stats.total_won
+        );
+        let default_stats = stats_of(&deps, None);
+        assert_eq!(default_stats.denom, TOKEN_DENOM);
+        assert_eq!(default_stats.total_games, 0);
+        assert_eq!(default_stats.house_balance, Uint128::new(1_000_000));
+
+        let query_msg = QueryMsg::UserStats {
+            player: player.to_string(),
+            denom: Some("uatom".to_string()),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let user_stats: UserStatsResponse = from_json(&res).unwrap();
+        assert_eq!(user_stats.total_games, 1);
+
+        for (denom, expected) in [(Some("uatom".to_string()), 1), (None, 0)] {
+            let query_msg = QueryMsg::GlobalLeaderboard {
+                leaderboard_type: LeaderboardType::TotalWagered,
+                limit: None,
+                denom: denom.clone(),
+            };
+            let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+            let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+            assert_eq!(leaderboard.entries.len(), expected);
+
+            let query_msg = QueryMsg::DailyLeaderboard {
+                leaderboard_type: LeaderboardType::TotalWagered,
+                limit: None,
+                denom,
+            };
+            let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+            let leaderboard: LeaderboardResponse = from_json(&res).unwrap();
+            assert_eq!(leaderboard.entries.len(), expected);
+        }
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: None,
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg).unwrap();
+        let history: HistoryResponse = from_json(&res).unwrap();
+        assert_eq!(history.games[0].denom, "uatom");
+
+        // A removed denom takes no bets, but its house balance can still be withdrawn
+        let info = message_info(&admin, &[]);
+        let remove = |denom: &str| ExecuteMsg::RemoveDenom {
+            denom: denom.to_string(),
+        };
+        let err =
+            execute(deps.as_mut(), mock_env(), info.clone(), remove(TOKEN_DENOM)).unwrap_err();
+        assert_eq!(err, ContractError::CannotRemoveDefaultDenom {});
+        execute(deps.as_mut(), mock_env(), info.clone(), remove("uatom")).unwrap();
+        let err = execute(deps.as_mut(), mock_env(), bet(100), play).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedDenom {
+                denom: "uatom".to_string()
+            }
+        );
+
+        let withdraw = ExecuteMsg::WithdrawHouse {
+            amount: atom_stats.house_balance,
+            denom: Some("uatom".to_string()),
+        };
+        let res = execute(deps.as_mut(), mock_env(), info, withdraw).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: admin.to_string(),
+                amount: coins(atom_stats.house_balance.u128(), "uatom"),
+            }
+            .into()
+        );
+        assert!(stats_of(&deps, Some("uatom")).house_balance.is_zero());
+        assert_eq!(stats_of(&deps, None).house_balance, Uint128::new(1_000_000));
+    }
+
+    #[test]
+    fn test_cw20_betting() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        let token = deps.api.addr_make("token");
+        let denom = format!("cw20:{token}");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+
+        let receive = |sender: &Addr, amount: u128, msg: &ReceiveMsg| {
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: sender.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(msg).unwrap(),
+            })
+        };
+        let token_info = message_info(&token, &[]);
+
+        // Tokens are only taken once the admin lists them
+        let fund = receive(&admin, 1_000_000, &ReceiveMsg::FundHouse {});
+        let err = execute(deps.as_mut(), mock_env(), token_info.clone(), fund.clone()).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedDenom {
+                denom: denom.clone()
+            }
+        );
+
+        let msg = ExecuteMsg::SetDenom {
+            denom: denom.clone(),
+            min_bet: None,
+            max_bet: None,
+        };
+        execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        execute(deps.as_mut(), mock_env(), token_info.clone(), fund).unwrap();
+
+        // Only the funder can fund the house, whichever way the tokens come in
+        let fund = receive(&player, 1000, &ReceiveMsg::FundHouse {});
+        let err = execute(deps.as_mut(), mock_env(), token_info.clone(), fund).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let play = ReceiveMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        let (res, settle) = play_settled(
+            &mut deps,
+            mock_env(),
+            token_info,
+            receive(&player, 100, &play),
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&("player", player.as_str()).into()));
+        assert!(res.attributes.contains(&("denom", denom.as_str()).into()));
+
+        // Winnings go back with a transfer on the token contract
+        let win_amount = settled(&settle, "win_amount")[0].parse::<u128>().unwrap();
+        assert_eq!(
+            settle.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: player.to_string(),
+                    amount: Uint128::new(win_amount),
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()
+        );
+
+        let query_msg = QueryMsg::Stats {
+            denom: Some(denom.clone()),
+        };
+        let res = query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        let house_balance = 1_000_100 - win_amount;
+        assert_eq!(stats.total_games, 1);
+        assert_eq!(stats.house_balance, Uint128::new(house_balance));
+
+        // A native coin cannot pass itself off as the token
+        let info = message_info(&player, &coins(100, denom.as_str()));
+        let msg = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnsupportedDenom {
+                denom: denom.clone()
+            }
+        );
+
+        // Syncing reads the token balance from the token contract
+        let contract = mock_env().contract.address;
+        let token_balance = Uint128::new(house_balance + 500);
+        deps.querier.update_wasm(move |request| match request {
+            WasmQuery::Smart { msg, .. } => {
+                let query: Cw20QueryMsg = from_json(msg).unwrap();
+                assert_eq!(
+                    query,
+                    Cw20QueryMsg::Balance {
+                        address: contract.to_string()
+                    }
+                );
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&Cw20BalanceResponse {
+                        balance: token_balance,
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => panic!("Unexpected query"),
+        });
+        let msg = ExecuteMsg::SyncBalance {
+            denom: Some(denom.clone()),
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert!(res.attributes.contains(&("funds_recovered", "500").into()));
+
+        let msg = ExecuteMsg::WithdrawHouse {
+            amount: token_balance,
+            denom: Some(denom),
+        };
+        let res = execute(deps.as_mut(), mock_env(), message_info(&admin, &[]), msg).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            WasmMsg::Execute {
+                contract_addr: token.to_string(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: admin.to_string(),
+                    amount: token_balance,
+                })
+                .unwrap(),
+                funds: vec![],
+            }
+            .into()
+        );
+    }
+
+    #[test]
+    fn test_jackpot() {
+        let mut deps = mock_deps();
+        let admin = deps.api.addr_make("admin");
+        let player = deps.api.addr_make("player");
+        setup_contract(deps.as_mut(), &admin).unwrap();
+        fund_contract(deps.as_mut(), Uint128::new(1_000_000_000));
+
+        // Every bucket but the centre of the 8 row low risk board hits
+        let jackpot = JackpotConfig {
+            contribution_bps: 100,
+            payout_bps: 5000,
+            trigger: Some(JackpotTrigger {
+                rows: 8,
+                risk_level: RiskLevel::Low,
+                edge_buckets: 4,
+            }),
+        };
+        let set_jackpot = |jackpot: JackpotConfig| ExecuteMsg::SetJackpot {
+            jackpot: Some(jackpot),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&player, &[]),
+            set_jackpot(jackpot.clone()),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        for invalid in [
+            JackpotConfig {
+                payout_bps: 0,
+                ..jackpot.clone()
+            },
+            JackpotConfig {
+                contribution_bps: 10_001,
+                ..jackpot.clone()
+            },
+            JackpotConfig {
+                trigger: Some(JackpotTrigger {
+                    rows: 8,
+                    risk_level: RiskLevel::Low,
+                    edge_buckets: 5,
+                }),
+                ..jackpot.clone()
+            },
+        ] {
+            let err = execute(
+                deps.as_mut(),
+                mock_env(),
+                message_info(&admin, &[]),
+                set_jackpot(invalid),
+            )
+            .unwrap_err();
+            assert_eq!(err, ContractError::InvalidJackpotConfig {});
+        }
+
+        // Without a trigger the outer buckets of Hard/High pay
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            set_jackpot(JackpotConfig {
+                trigger: None,
+                ..jackpot.clone()
+            }),
+        )
+        .unwrap();
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Jackpot { denom: None }).unwrap();
+        let response: JackpotResponse = from_json(&res).unwrap();
+        assert_eq!(
+            response.jackpot.unwrap().trigger,
+            Some(JackpotTrigger {
+                rows: 16,
+                risk_level: RiskLevel::High,
+                edge_buckets: 1,
+            })
+        );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            set_jackpot(jackpot),
+        )
+        .unwrap();
+
+        let play = ExecuteMsg::Play {
+            difficulty: Some(Difficulty::Easy),
+            rows: None,
+            risk_level: RiskLevel::Low,
+            client_seed: None,
+            ball_count: Some(20),
+        };
+        let info = message_info(&player, &coins(20_000, TOKEN_DENOM));
+        let (_, res) = play_settled(&mut deps, mock_env(), info, play).unwrap();
+
+        let query_msg = QueryMsg::History {
+            player: player.to_string(),
+            limit: Some(20),
+        };
+        let history: HistoryResponse =
+            from_json(query(deps.as_ref(), mock_env(), query_msg).unwrap()).unwrap();
+        let mut table_wins = Uint128::zero();
+        let mut jackpot_wins = Uint128::zero();
+        for game in &history.games {
+            assert_eq!(game.jackpot_contribution, Uint128::new(10));
+            table_wins += game.win_amount;
+            jackpot_wins += game.jackpot_win;
+        }
+        assert!(!jackpot_wins.is_zero());
+
+        // Each contribution and payout has its event, and jackpots are paid with the winnings
+        let count = |kind: &str| res.events.iter().filter(|event| event.ty == kind).count();
+        assert_eq!(count("jackpot_contribution"), 20);
+        assert_eq!(
+            count("jackpot_payout"),
+            history
+                .games
+                .iter()
+                .filter(|game| !game.jackpot_win.is_zero())
+                .count()
+        );
+        assert_eq!(
+            res.messages[0].msg,
+            BankMsg::Send {
+                to_address: player.to_string(),
+                amount: coins((table_wins + jackpot_wins).u128(), TOKEN_DENOM),
+            }
+            .into()
+        );
+
+        // The pool is kept apart from the house balance
+        let pool = Uint128::new(200) - jackpot_wins;
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Jackpot { denom: None }).unwrap();
+        let response: JackpotResponse = from_json(&res).unwrap();
+        assert_eq!(response.balance, pool);
+        let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats { denom: None }).unwrap();
+        let stats: StatsResponse = from_json(&res).unwrap();
+        assert_eq!(stats.jackpot_balance, pool);
+        assert_eq!(
+            stats.house_balance,
+            Uint128::new(1_000_020_000) - table_wins - Uint128::new(200)
+        );
+
+        // The house and jackpot ledgers together reconcile with the bank balance
+        deps.querier.bank.update_balance(
+            mock_env().contract.address,
+            coins((stats.house_balance + pool).u128(), TOKEN_DENOM),
+        );
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&admin, &[]),
+            ExecuteMsg::SyncBalance { denom: None },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&("funds_recovered", "0").into()));
     }
 }
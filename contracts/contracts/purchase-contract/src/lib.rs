@@ -4,4 +4,5 @@ pub mod msg;
 pub mod state;
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests;
@@ -135,7 +135,7 @@ mod tests {
             res.attributes,
             vec![
                 ("action", "purchase"),
-                ("buyer", &buyer.to_string()),
+                ("buyer", buyer.as_ref()),
                 ("inj_amount", "10000000000000000000"),
                 ("token_amount", "1000000000000000000000"),
             ]
@@ -242,7 +242,7 @@ mod tests {
             res.attributes,
             vec![
                 ("action", "fund_house"),
-                ("game_contract", &game.to_string()),
+                ("game_contract", game.as_ref()),
                 ("amount", "1000000000000000000000"),
             ]
         );
@@ -417,7 +417,7 @@ mod tests {
             res.attributes,
             vec![
                 ("action", "purchase"),
-                ("buyer", &buyer.to_string()),
+                ("buyer", buyer.as_ref()),
                 ("inj_amount", "10000000000000000000"),
                 ("token_amount", "2000000000000000000000"),
             ]
@@ -477,7 +477,7 @@ mod tests {
             res.attributes,
             vec![
                 ("action", "update_treasury"),
-                ("new_treasury", &new_treasury.to_string()),
+                ("new_treasury", new_treasury.as_ref()),
             ]
         );
 
@@ -0,0 +1,248 @@
+//! WebAssembly bindings of `plinko-fairness` for the frontend.
+//!
+//! Build the npm package with `wasm-pack build --target web --scope plinko`.
+//! u64 arguments are JS bigints and token amounts are decimal strings, as in the contract's JSON.
+
+use std::str::FromStr;
+
+use cosmwasm_std::{from_json, Decimal};
+use plinko_fairness::rng::{self, BlockEntropy};
+use plinko_fairness::verify::SeedInputs;
+use plinko_fairness::{Difficulty, RiskLevel, TableError};
+use wasm_bindgen::prelude::*;
+
+/// Generate the path (0 = left, 1 = right) of a ball dropped from a 32 byte seed
+/// `rng_version` defaults to the current one; pass `GameRecord.rng_version` to replay a game.
+#[wasm_bindgen]
+pub fn generate_ball_path(
+    seed: &[u8],
+    rows: u8,
+    rng_version: Option<u8>,
+) -> Result<Vec<u8>, JsError> {
+    let rng_version = rng_version.unwrap_or(rng::RNG_VERSION);
+    rng::generate_path(to_seed(seed)?, rows, rng_version)
+        .ok_or_else(|| JsError::new(&format!("unsupported RNG version {rng_version}")))
+}
+
+/// Bucket a path lands in
+#[wasm_bindgen]
+pub fn calculate_bucket_index(path: &[u8]) -> usize {
+    rng::calculate_bucket_index(path)
+}
+
+/// Official payout table, one multiplier per bucket from left to right
+#[wasm_bindgen]
+pub fn get_multipliers(difficulty: &str, risk_level: &str) -> Result<Vec<f64>, JsError> {
+    let multipliers =
+        plinko_fairness::get_multipliers(&parse_difficulty(difficulty)?, &parse_risk(risk_level)?);
+    Ok(to_f64(multipliers))
+}
+
+/// Official payout table of a board with `rows` rows (8 to 16)
+#[wasm_bindgen]
+pub fn get_table(rows: u8, risk_level: &str) -> Result<Vec<f64>, JsError> {
+    let multipliers = plinko_fairness::table_for_rows(rows, &parse_risk(risk_level)?)
+        .ok_or_else(|| JsError::new(&format!("no payout table for {rows} rows")))?;
+    Ok(to_f64(multipliers))
+}
+
+fn to_f64<T: ToString>(multipliers: Vec<T>) -> Vec<f64> {
+    multipliers
+        .into_iter()
+        .map(|multiplier| multiplier.to_string().parse().unwrap_or(f64::NAN))
+        .collect()
+}
+
+/// Number of peg rows of a difficulty
+#[wasm_bindgen]
+pub fn get_rows(difficulty: &str) -> Result<u8, JsError> {
+    Ok(plinko_fairness::get_rows(&parse_difficulty(difficulty)?))
+}
+
+/// Outcome of a replayed drop
+#[wasm_bindgen]
+pub struct Outcome {
+    path: Vec<u8>,
+    bucket: usize,
+    multiplier: String, // Decimal, like "5.6"
+    win_amount: u128,
+}
+
+#[wasm_bindgen]
+impl Outcome {
+    #[wasm_bindgen(getter)]
+    pub fn path(&self) -> Vec<u8> {
+        self.path.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn bucket(&self) -> usize {
+        self.bucket
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn multiplier(&self) -> String {
+        self.multiplier.clone()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn win_amount(&self) -> String {
+        self.win_amount.to_string()
+    }
+}
+
+/// Drop a ball from a seed down `rows` rows and pay it out on `table` exactly like the contract
+/// does. To replay a game, pass its `rows` and `rng_version` and the multipliers of its
+/// `table_id` from the `PayoutTableVersion` query.
+#[wasm_bindgen]
+pub fn play(
+    seed: &[u8],
+    rows: u8,
+    rng_version: u8,
+    table: Vec<String>,
+    bet_amount: &str,
+) -> Result<Outcome, JsError> {
+    let outcome = plinko_fairness::play_table(
+        to_seed(seed)?,
+        rng_version,
+        &parse_table(rows, &table)?,
+        parse_amount(bet_amount)?,
+    )
+    .map_err(|e| JsError::new(&format!("{e:?}")))?;
+
+    Ok(Outcome {
+        path: outcome.path,
+        bucket: outcome.bucket,
+        multiplier: outcome.multiplier.to_string(),
+        win_amount: outcome.win_amount,
+    })
+}
+
+/// Seed of a game from its `GameRecord.seed_inputs`, passed as JSON
+#[wasm_bindgen]
+pub fn derive_seed(seed_inputs: &str, player: &str, nonce: u64) -> Result<Vec<u8>, JsError> {
+    let inputs: SeedInputs =
+        from_json(seed_inputs).map_err(|e| JsError::new(&format!("invalid seed inputs: {e}")))?;
+    Ok(plinko_fairness::verify::derive_seed(&inputs, player, nonce).to_vec())
+}
+
+/// Seed of a `block` game
+#[wasm_bindgen]
+pub fn block_seed(
+    tx_index: u32,
+    height: u64,
+    time_nanos: u64,
+    contract: &str,
+    player: &str,
+    nonce: u64,
+    funds_amount: Option<String>,
+) -> Result<Vec<u8>, JsError> {
+    let block = BlockEntropy {
+        tx_index,
+        height,
+        time_nanos,
+        contract,
+    };
+    let funds_amount = funds_amount.as_deref().map(parse_amount).transpose()?;
+    Ok(rng::block_seed(&block, player, nonce, funds_amount).to_vec())
+}
+
+/// Seed of a `settle_block` game
+#[wasm_bindgen]
+pub fn settle_block_seed(
+    contract: &str,
+    bet_id: u64,
+    player: &str,
+    nonce: u64,
+    placed_height: u64,
+    height: u64,
+    time_nanos: u64,
+) -> Vec<u8> {
+    rng::settle_block_seed(
+        contract,
+        bet_id,
+        player,
+        nonce,
+        placed_height,
+        height,
+        time_nanos,
+    )
+    .to_vec()
+}
+
+/// Seed of a `commitment` game
+#[wasm_bindgen]
+pub fn commitment_seed(server_seed: &[u8], client_seed: &str, player: &str, nonce: u64) -> Vec<u8> {
+    rng::commitment_seed(server_seed, client_seed, player, nonce).to_vec()
+}
+
+/// sha256 of a server seed, to compare with its on-chain commitment
+#[wasm_bindgen]
+pub fn hash_server_seed(server_seed: &[u8]) -> Vec<u8> {
+    rng::hash_server_seed(server_seed).to_vec()
+}
+
+/// Seed of an `operator_signature` game
+#[wasm_bindgen]
+pub fn signature_seed(signature: &[u8]) -> Vec<u8> {
+    rng::signature_seed(signature).to_vec()
+}
+
+/// Seed of a `drand` game
+#[wasm_bindgen]
+pub fn drand_seed(signature: &[u8], bet_id: u64, player: &str, nonce: u64) -> Vec<u8> {
+    rng::drand_seed(signature, bet_id, player, nonce).to_vec()
+}
+
+/// RNG version of new games, as recorded in `GameRecord.rng_version`
+#[wasm_bindgen]
+pub fn rng_version() -> u8 {
+    rng::RNG_VERSION
+}
+
+fn to_seed(seed: &[u8]) -> Result<[u8; 32], JsError> {
+    seed.try_into()
+        .map_err(|_| JsError::new("seed must be 32 bytes"))
+}
+
+fn parse_amount(amount: &str) -> Result<u128, JsError> {
+    amount
+        .parse()
+        .map_err(|_| JsError::new(&format!("invalid amount: {amount}")))
+}
+
+/// Decimal multipliers of a board of `rows` rows, as the contract's JSON has them
+fn parse_table(rows: u8, table: &[String]) -> Result<Vec<Decimal>, JsError> {
+    let table = table
+        .iter()
+        .map(|multiplier| {
+            Decimal::from_str(multiplier)
+                .map_err(|_| JsError::new(&format!("invalid multiplier: {multiplier}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    plinko_fairness::validate_table(rows, &table).map_err(|e| match e {
+        TableError::WrongLength { expected } => JsError::new(&format!(
+            "a table of {rows} rows needs {expected} multipliers"
+        )),
+        TableError::Asymmetric => JsError::new("table is not symmetric"),
+    })?;
+    Ok(table)
+}
+
+fn parse_difficulty(difficulty: &str) -> Result<Difficulty, JsError> {
+    match difficulty {
+        "easy" => Ok(Difficulty::Easy),
+        "medium" => Ok(Difficulty::Medium),
+        "hard" => Ok(Difficulty::Hard),
+        _ => Err(JsError::new(&format!("invalid difficulty: {difficulty}"))),
+    }
+}
+
+fn parse_risk(risk_level: &str) -> Result<RiskLevel, JsError> {
+    match risk_level {
+        "low" => Ok(RiskLevel::Low),
+        "medium" => Ok(RiskLevel::Medium),
+        "high" => Ok(RiskLevel::High),
+        _ => Err(JsError::new(&format!("invalid risk level: {risk_level}"))),
+    }
+}
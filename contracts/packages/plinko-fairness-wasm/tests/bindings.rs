@@ -0,0 +1,135 @@
+//! Run with `cargo test --target wasm32-unknown-unknown -p plinko-fairness-wasm` and
+//! `CARGO_TARGET_WASM32_UNKNOWN_UNKNOWN_RUNNER=wasm-bindgen-test-runner`.
+#![cfg(target_arch = "wasm32")]
+
+use cosmwasm_std::{Binary, HexBinary};
+use plinko_fairness_wasm::{
+    calculate_bucket_index, commitment_seed, derive_seed, generate_ball_path, get_multipliers,
+    get_rows, get_table, play, settle_block_seed, signature_seed,
+};
+use serde_json::Value;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Games and tables the contract recorded in its test_verifier_fixture test
+const HISTORY: &str = include_str!("../../../tools/plinko-verify/testdata/history.json");
+const TABLES: &str = include_str!("../../../tools/plinko-verify/testdata/tables.json");
+
+/// Seed of a recorded game, from its seed inputs
+fn seed(game: &Value) -> Vec<u8> {
+    let player = game["player"].as_str().unwrap();
+    let nonce = game["nonce"].as_u64().unwrap();
+    let inputs = &game["seed_inputs"];
+    if let Some(commitment) = inputs.get("commitment") {
+        commitment_seed(
+            &HexBinary::from_hex(commitment["server_seed"].as_str().unwrap()).unwrap(),
+            commitment["client_seed"].as_str().unwrap(),
+            player,
+            nonce,
+        )
+    } else if let Some(block) = inputs.get("settle_block") {
+        settle_block_seed(
+            block["contract"].as_str().unwrap(),
+            block["bet_id"].as_u64().unwrap(),
+            player,
+            nonce,
+            block["placed_height"].as_u64().unwrap(),
+            block["height"].as_u64().unwrap(),
+            block["time_nanos"].as_u64().unwrap(),
+        )
+    } else if let Some(signed) = inputs.get("operator_signature") {
+        signature_seed(&Binary::from_base64(signed["signature"].as_str().unwrap()).unwrap())
+    } else {
+        panic!("no seed binding for {inputs}")
+    }
+}
+
+/// Multipliers of a table version
+fn table(tables: &Value, table_id: &Value) -> Vec<String> {
+    let table = tables
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|table| table["table_id"] == *table_id)
+        .unwrap();
+    table["multipliers"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|multiplier| multiplier.as_str().unwrap().to_string())
+        .collect()
+}
+
+#[wasm_bindgen_test]
+fn test_play_replays_contract_games() {
+    let history: Value = serde_json::from_str(HISTORY).unwrap();
+    let tables: Value = serde_json::from_str(TABLES).unwrap();
+
+    for game in history["games"].as_array().unwrap() {
+        let seed = seed(game);
+        assert_eq!(
+            derive_seed(
+                &game["seed_inputs"].to_string(),
+                game["player"].as_str().unwrap(),
+                game["nonce"].as_u64().unwrap(),
+            )
+            .unwrap(),
+            seed
+        );
+
+        let outcome = play(
+            &seed,
+            game["rows"].as_u64().unwrap() as u8,
+            game["rng_version"].as_u64().unwrap() as u8,
+            table(&tables, &game["table_id"]),
+            game["bet_amount"].as_str().unwrap(),
+        )
+        .unwrap();
+
+        let path: Vec<u8> = game["path"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|right| right.as_bool().unwrap() as u8)
+            .collect();
+        assert_eq!(outcome.path(), path);
+        assert_eq!(outcome.bucket(), calculate_bucket_index(&path));
+        assert_eq!(outcome.multiplier(), game["multiplier"].as_str().unwrap());
+        assert_eq!(outcome.win_amount(), game["win_amount"].as_str().unwrap());
+    }
+}
+
+#[wasm_bindgen_test]
+fn test_play_checks_table() {
+    let seed = [7u8; 32];
+    let table: Vec<String> = ["5.6", "2.1", "1.1", "1", "0.5", "1", "1.1", "2.1", "5.6"]
+        .map(String::from)
+        .to_vec();
+
+    let outcome = play(&seed, 8, 2, table.clone(), "1000").unwrap();
+    assert_eq!(
+        outcome.path(),
+        generate_ball_path(&seed, 8, Some(2)).unwrap()
+    );
+
+    // A table of another board, an asymmetric one or an unknown RNG version is refused
+    assert!(play(&seed, 12, 2, table.clone(), "1000").is_err());
+    let mut lopsided = table.clone();
+    lopsided[0] = "6".to_string();
+    assert!(play(&seed, 8, 2, lopsided, "1000").is_err());
+    assert!(play(&seed, 8, 9, table.clone(), "1000").is_err());
+    assert!(play(&seed, 8, 2, vec!["x".to_string(); 9], "1000").is_err());
+    assert!(play(&seed[..31], 8, 2, table, "1000").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_official_tables() {
+    assert_eq!(get_rows("hard").unwrap(), 16);
+    assert_eq!(
+        get_table(16, "high").unwrap(),
+        get_multipliers("hard", "high").unwrap()
+    );
+    assert_eq!(get_table(10, "low").unwrap().len(), 11);
+    assert!(get_table(17, "low").is_err());
+    assert!(get_rows("extreme").is_err());
+    assert!(derive_seed(r#"{"unknown":{}}"#, "player", 0).is_err());
+}
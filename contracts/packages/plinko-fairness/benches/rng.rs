@@ -0,0 +1,26 @@
+//! Native path generation time per RNG version: `cargo bench -p plinko-fairness`
+//!
+//! Version 1 hashes once per row, version 2 reads every row from the seed it already has.
+//! The gas of each version is metered in cosmwasm-vm by `tools/rng-gas/measure.sh`.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use plinko_fairness::rng::{expand_path, rehash_path};
+
+fn bench_paths(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate_path");
+    let seed = [42u8; 32];
+
+    for rows in [8u8, 12, 16] {
+        group.bench_with_input(BenchmarkId::new("v1_rehash", rows), &rows, |b, &rows| {
+            b.iter(|| rehash_path(black_box(seed), rows))
+        });
+        group.bench_with_input(BenchmarkId::new("v2_expand", rows), &rows, |b, &rows| {
+            b.iter(|| expand_path(black_box(seed), rows))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_paths);
+criterion_main!(benches);
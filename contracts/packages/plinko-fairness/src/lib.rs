@@ -0,0 +1,43 @@
+//! Path generation and payout tables of the Plinko game.
+//!
+//! The contract settles every game with this crate, so anyone can replay a recorded game
+//! off-chain and get exactly the result the chain produced.
+
+pub mod multipliers;
+pub mod odds;
+pub mod rng;
+pub mod verify;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub use multipliers::{
+    get_multipliers, get_rows, play, play_table, table_for_rows, validate_table, Outcome,
+    PlayError, TableError, MAX_ROWS, MIN_ROWS,
+};
+pub use odds::{expected_return, odds, Odds};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,   // 8 rows
+    Medium, // 12 rows
+    Hard,   // 16 rows
+}
+
+impl Difficulty {
+    /// The difficulty that is an alias for a board of `rows` rows, if any
+    pub fn from_rows(rows: u8) -> Option<Self> {
+        [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
+            .into_iter()
+            .find(|difficulty| get_rows(difficulty) == rows)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(deny_unknown_fields, rename_all = "snake_case")]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
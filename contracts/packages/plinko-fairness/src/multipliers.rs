@@ -0,0 +1,270 @@
+use cosmwasm_std::{Decimal, Uint128};
+
+use crate::rng::{calculate_bucket_index, generate_path};
+use crate::{Difficulty, RiskLevel};
+
+/// Payout table of a board with `rows` rows, None outside `MIN_ROWS..=MAX_ROWS`
+pub fn table_for_rows(rows: u8, risk_level: &RiskLevel) -> Option<Vec<Decimal>> {
+    // Multipliers in thousandths, from the leftmost bucket to the rightmost
+    let permille: &[u64] = match (rows, risk_level) {
+        (8, RiskLevel::Low) => &[5600, 2100, 1100, 1000, 500, 1000, 1100, 2100, 5600],
+        (8, RiskLevel::Medium) => &[13000, 3000, 1300, 700, 400, 700, 1300, 3000, 13000],
+        (8, RiskLevel::High) => &[29000, 4000, 1500, 300, 200, 300, 1500, 4000, 29000],
+        (9, RiskLevel::Low) => &[5600, 2000, 1600, 1000, 700, 700, 1000, 1600, 2000, 5600],
+        (9, RiskLevel::Medium) => &[18000, 4000, 1700, 900, 500, 500, 900, 1700, 4000, 18000],
+        (9, RiskLevel::High) => &[43000, 7000, 2000, 600, 200, 200, 600, 2000, 7000, 43000],
+        (10, RiskLevel::Low) => &[
+            8900, 3000, 1400, 1100, 1000, 500, 1000, 1100, 1400, 3000, 8900,
+        ],
+        (10, RiskLevel::Medium) => &[
+            22000, 5000, 2000, 1400, 600, 400, 600, 1400, 2000, 5000, 22000,
+        ],
+        (10, RiskLevel::High) => &[
+            76000, 10000, 3000, 900, 300, 200, 300, 900, 3000, 10000, 76000,
+        ],
+        (11, RiskLevel::Low) => &[
+            8400, 3000, 1900, 1300, 1000, 700, 700, 1000, 1300, 1900, 3000, 8400,
+        ],
+        (11, RiskLevel::Medium) => &[
+            24000, 6000, 3000, 1800, 700, 500, 500, 700, 1800, 3000, 6000, 24000,
+        ],
+        (11, RiskLevel::High) => &[
+            120000, 14000, 5200, 1400, 400, 200, 200, 400, 1400, 5200, 14000, 120000,
+        ],
+        (12, RiskLevel::Low) => &[
+            10000, 3000, 1600, 1400, 1100, 1000, 500, 1000, 1100, 1400, 1600, 3000, 10000,
+        ],
+        (12, RiskLevel::Medium) => &[
+            33000, 11000, 4000, 2000, 1100, 600, 300, 600, 1100, 2000, 4000, 11000, 33000,
+        ],
+        (12, RiskLevel::High) => &[
+            170000, 24000, 8100, 2000, 700, 200, 200, 200, 700, 2000, 8100, 24000, 170000,
+        ],
+        (13, RiskLevel::Low) => &[
+            8100, 4000, 3000, 1900, 1200, 900, 700, 700, 900, 1200, 1900, 3000, 4000, 8100,
+        ],
+        (13, RiskLevel::Medium) => &[
+            43000, 13000, 6000, 3000, 1300, 700, 400, 400, 700, 1300, 3000, 6000, 13000, 43000,
+        ],
+        (13, RiskLevel::High) => &[
+            260000, 37000, 11000, 4000, 1000, 200, 200, 200, 200, 1000, 4000, 11000, 37000, 260000,
+        ],
+        (14, RiskLevel::Low) => &[
+            7100, 4000, 1900, 1400, 1300, 1100, 1000, 500, 1000, 1100, 1300, 1400, 1900, 4000, 7100,
+        ],
+        (14, RiskLevel::Medium) => &[
+            58000, 15000, 7000, 4000, 1900, 1000, 500, 200, 500, 1000, 1900, 4000, 7000, 15000,
+            58000,
+        ],
+        (14, RiskLevel::High) => &[
+            420000, 56000, 18000, 5000, 1900, 300, 200, 200, 200, 300, 1900, 5000, 18000, 56000,
+            420000,
+        ],
+        (15, RiskLevel::Low) => &[
+            15000, 8000, 3000, 2000, 1500, 1100, 1000, 700, 700, 1000, 1100, 1500, 2000, 3000,
+            8000, 15000,
+        ],
+        (15, RiskLevel::Medium) => &[
+            88000, 18000, 11000, 5000, 3000, 1300, 500, 300, 300, 500, 1300, 3000, 5000, 11000,
+            18000, 88000,
+        ],
+        (15, RiskLevel::High) => &[
+            620000, 83000, 27000, 8000, 3000, 500, 200, 200, 200, 200, 500, 3000, 8000, 27000,
+            83000, 620000,
+        ],
+        (16, RiskLevel::Low) => &[
+            16000, 9000, 2000, 1400, 1400, 1200, 1100, 1000, 500, 1000, 1100, 1200, 1400, 1400,
+            2000, 9000, 16000,
+        ],
+        (16, RiskLevel::Medium) => &[
+            110000, 41000, 10000, 5000, 3000, 1500, 1000, 500, 300, 500, 1000, 1500, 3000, 5000,
+            10000, 41000, 110000,
+        ],
+        (16, RiskLevel::High) => &[
+            1000000, 130000, 26000, 9000, 4000, 2000, 200, 200, 200, 200, 200, 2000, 4000, 9000,
+            26000, 130000, 1000000,
+        ],
+        _ => return None,
+    };
+    Some(permille.iter().map(|&x| Decimal::permille(x)).collect())
+}
+
+/// Payout table of the board a difficulty plays on
+pub fn get_multipliers(difficulty: &Difficulty, risk_level: &RiskLevel) -> Vec<Decimal> {
+    table_for_rows(get_rows(difficulty), risk_level).expect("every difficulty has a table")
+}
+
+/// Smallest and largest boards with a payout table
+pub const MIN_ROWS: u8 = 8;
+pub const MAX_ROWS: u8 = 16;
+
+pub fn get_rows(difficulty: &Difficulty) -> u8 {
+    match difficulty {
+        Difficulty::Easy => 8,
+        Difficulty::Medium => 12,
+        Difficulty::Hard => 16,
+    }
+}
+
+/// Result of dropping a ball from a seed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub path: Vec<u8>, // 0 = left, 1 = right
+    pub bucket: usize,
+    pub multiplier: Decimal,
+    pub win_amount: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayError {
+    InvalidMultiplierIndex,
+    Overflow,
+    UnsupportedRngVersion(u8),
+}
+
+/// Why a payout table was rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableError {
+    WrongLength { expected: usize },
+    Asymmetric,
+}
+
+/// Check a payout table for a board of `rows` rows: one multiplier per bucket
+/// and the same multiplier on both sides of the centre
+pub fn validate_table(rows: u8, table: &[Decimal]) -> Result<(), TableError> {
+    let expected = rows as usize + 1;
+    if table.len() != expected {
+        return Err(TableError::WrongLength { expected });
+    }
+    if !table.iter().eq(table.iter().rev()) {
+        return Err(TableError::Asymmetric);
+    }
+
+    Ok(())
+}
+
+/// Drop a ball from `seed` with the RNG of `rng_version` and pay it out against the default
+/// table of `difficulty` and `risk_level`
+pub fn play(
+    seed: [u8; 32],
+    rng_version: u8,
+    difficulty: &Difficulty,
+    risk_level: &RiskLevel,
+    bet_amount: u128,
+) -> Result<Outcome, PlayError> {
+    play_table(
+        seed,
+        rng_version,
+        &get_multipliers(difficulty, risk_level),
+        bet_amount,
+    )
+}
+
+/// Drop a ball from `seed` down a board with one row less than `table` has buckets
+pub fn play_table(
+    seed: [u8; 32],
+    rng_version: u8,
+    table: &[Decimal],
+    bet_amount: u128,
+) -> Result<Outcome, PlayError> {
+    let rows = table.len().saturating_sub(1) as u8;
+    let path = generate_path(seed, rows, rng_version)
+        .ok_or(PlayError::UnsupportedRngVersion(rng_version))?;
+    let bucket = calculate_bucket_index(&path);
+    let (win_amount, multiplier) = payout_table(bet_amount, table, bucket)?;
+
+    Ok(Outcome {
+        path,
+        bucket,
+        multiplier,
+        win_amount,
+    })
+}
+
+/// Win amount and multiplier of a bet landing in `bucket` of the default table
+pub fn payout(
+    bet_amount: u128,
+    difficulty: &Difficulty,
+    risk_level: &RiskLevel,
+    bucket: usize,
+) -> Result<(u128, Decimal), PlayError> {
+    payout_table(bet_amount, &get_multipliers(difficulty, risk_level), bucket)
+}
+
+/// Win amount and multiplier of a bet landing in `bucket` of `table`.
+/// The win is rounded down to whole units.
+pub fn payout_table(
+    bet_amount: u128,
+    table: &[Decimal],
+    bucket: usize,
+) -> Result<(u128, Decimal), PlayError> {
+    let multiplier = *table.get(bucket).ok_or(PlayError::InvalidMultiplierIndex)?;
+    let win_amount = Uint128::new(bet_amount)
+        .checked_mul_floor(multiplier)
+        .map_err(|_| PlayError::Overflow)?;
+
+    Ok((win_amount.u128(), multiplier))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_table() {
+        let table = |values: &[u64]| {
+            values
+                .iter()
+                .map(|&x| Decimal::permille(x))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(validate_table(2, &table(&[2000, 500, 2000])), Ok(()));
+        assert_eq!(
+            validate_table(3, &table(&[2000, 500, 2000])),
+            Err(TableError::WrongLength { expected: 4 })
+        );
+        assert_eq!(
+            validate_table(2, &table(&[2000, 500, 3000])),
+            Err(TableError::Asymmetric)
+        );
+    }
+
+    #[test]
+    fn test_payout_table() {
+        // Fractional multipliers below 0.1x are paid exactly, rounded down to whole units
+        let table = [
+            Decimal::permille(1050),
+            Decimal::permille(250),
+            Decimal::permille(1050),
+        ];
+        assert_eq!(
+            payout_table(1_000, &table, 0),
+            Ok((1_050, Decimal::permille(1050)))
+        );
+        assert_eq!(
+            payout_table(1_001, &table, 1),
+            Ok((250, Decimal::permille(250)))
+        );
+        assert_eq!(
+            payout_table(1_000, &table, 3),
+            Err(PlayError::InvalidMultiplierIndex)
+        );
+        assert_eq!(payout_table(u128::MAX, &table, 0), Err(PlayError::Overflow));
+    }
+
+    #[test]
+    fn test_every_board_has_valid_tables() {
+        for rows in MIN_ROWS..=MAX_ROWS {
+            for risk_level in [RiskLevel::Low, RiskLevel::Medium, RiskLevel::High] {
+                let table = table_for_rows(rows, &risk_level).unwrap();
+                assert_eq!(
+                    validate_table(rows, &table),
+                    Ok(()),
+                    "{rows} rows {risk_level:?}"
+                );
+            }
+        }
+        assert_eq!(table_for_rows(MIN_ROWS - 1, &RiskLevel::Low), None);
+        assert_eq!(table_for_rows(MAX_ROWS + 1, &RiskLevel::Low), None);
+    }
+}
@@ -0,0 +1,140 @@
+//! Return to player, variance and top multiplier of a payout table.
+//!
+//! Bucket k of an n row board is reached by C(n, k) of the 2^n equally likely paths, so every
+//! moment of the multiplier is a finite sum. With 18 decimal places the RTP is exact for any
+//! table whose multipliers have at most 18 - n decimals, like every table in `multipliers.rs`.
+
+use cosmwasm_std::{Decimal, Uint128};
+
+/// Odds of a payout table, per unit bet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Odds {
+    pub rtp: Decimal,      // Expected multiplier
+    pub variance: Decimal, // Variance of the multiplier
+    pub max_multiplier: Decimal,
+}
+
+/// Return to player of a table, None if the sum overflows
+pub fn expected_return(table: &[Decimal]) -> Option<Decimal> {
+    weighted_mean(
+        table
+            .iter()
+            .map(|multiplier| Some(multiplier.atomics().u128())),
+    )
+}
+
+/// RTP, variance and largest multiplier of a table.
+/// None for an empty table or multipliers too large to square.
+pub fn odds(table: &[Decimal]) -> Option<Odds> {
+    let max_multiplier = *table.iter().max()?;
+    let rtp = expected_return(table)?;
+    let second_moment = weighted_mean(
+        table
+            .iter()
+            .map(|multiplier| Some(multiplier.checked_mul(*multiplier).ok()?.atomics().u128())),
+    )?;
+    let variance = second_moment.checked_sub(rtp.checked_mul(rtp).ok()?).ok()?;
+
+    Some(Odds {
+        rtp,
+        variance,
+        max_multiplier,
+    })
+}
+
+/// Mean of per-bucket values in Decimal atomics, each bucket weighted by its chance
+fn weighted_mean(values: impl ExactSizeIterator<Item = Option<u128>>) -> Option<Decimal> {
+    let rows = values.len().checked_sub(1)? as u32;
+    let mut paths: u128 = 1; // C(rows, k)
+    let mut total: u128 = 0;
+
+    for (k, value) in values.enumerate() {
+        total = total.checked_add(paths.checked_mul(value?)?)?;
+        paths = paths * (rows as u128 - k as u128) / (k as u128 + 1);
+    }
+
+    Some(Decimal::new(Uint128::new(total >> rows)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_multipliers, table_for_rows, Difficulty, RiskLevel, MAX_ROWS, MIN_ROWS};
+
+    /// Every shipped table must return between 98.5% and 99.5% of the bet
+    const TARGET_RTP: (Decimal, Decimal) = (Decimal::permille(985), Decimal::permille(995));
+
+    fn table(permille: &[u64]) -> Vec<Decimal> {
+        permille.iter().map(|&x| Decimal::permille(x)).collect()
+    }
+
+    #[test]
+    fn test_expected_return() {
+        // 2 rows: buckets hit 1/4, 1/2 and 1/4 of the time
+        assert_eq!(
+            expected_return(&table(&[2000, 0, 2000])),
+            Some(Decimal::one())
+        );
+        assert_eq!(expected_return(&[Decimal::one(); 17]), Some(Decimal::one()));
+        assert_eq!(
+            expected_return(&table(&[1500, 500, 1500])),
+            Some(Decimal::one())
+        );
+        assert_eq!(
+            expected_return(&table(&[1050, 250, 1050])),
+            Some(Decimal::permille(650))
+        );
+        assert_eq!(expected_return(&[]), None);
+    }
+
+    #[test]
+    fn test_odds() {
+        // 2x or 0x on a fair coin: E[M] = 1, E[M^2] = 2, variance 1
+        let coin = odds(&table(&[2000, 0])).unwrap();
+        assert_eq!(coin.rtp, Decimal::one());
+        assert_eq!(coin.variance, Decimal::one());
+        assert_eq!(coin.max_multiplier, Decimal::percent(200));
+
+        // A flat table never varies
+        let flat = odds(&[Decimal::one(); 9]).unwrap();
+        assert_eq!(flat.rtp, Decimal::one());
+        assert_eq!(flat.variance, Decimal::zero());
+
+        assert_eq!(odds(&[]), None);
+        assert_eq!(odds(&[Decimal::MAX; 17]), None);
+    }
+
+    #[test]
+    fn test_shipped_tables_in_target_band() {
+        for rows in MIN_ROWS..=MAX_ROWS {
+            let mut variances = vec![];
+            for risk_level in [RiskLevel::Low, RiskLevel::Medium, RiskLevel::High] {
+                let table = table_for_rows(rows, &risk_level).unwrap();
+                let odds = odds(&table).unwrap();
+                assert!(
+                    (TARGET_RTP.0..=TARGET_RTP.1).contains(&odds.rtp),
+                    "{rows} rows {risk_level:?}: RTP {}",
+                    odds.rtp
+                );
+                // The edges pay the most
+                assert_eq!(odds.max_multiplier, table[0]);
+                variances.push(odds.variance);
+            }
+            // Higher risk means a wider spread of outcomes
+            assert!(variances.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_difficulty_tables() {
+        // Easy/Low pays 0.5x in the centre and Hard/High 1000x at the edges, both within the band
+        let easy_low = odds(&get_multipliers(&Difficulty::Easy, &RiskLevel::Low)).unwrap();
+        assert_eq!(easy_low.rtp, "0.98984375".parse::<Decimal>().unwrap());
+        let hard_high = odds(&get_multipliers(&Difficulty::Hard, &RiskLevel::High)).unwrap();
+        assert_eq!(
+            hard_high.max_multiplier,
+            Decimal::from_ratio(1000u128, 1u128)
+        );
+        assert!(hard_high.rtp < Decimal::one());
+    }
+}
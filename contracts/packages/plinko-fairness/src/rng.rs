@@ -0,0 +1,295 @@
+use sha2::{Digest, Sha256};
+
+/// Path generation of new games, recorded with every game
+pub const RNG_VERSION: u8 = RNG_V2_EXPAND;
+
+/// One SHA-256 per row, keeping the low bit of each digest
+pub const RNG_V1_REHASH: u8 = 1;
+
+/// Every row read from the bits of the seed itself, expanded in counter mode past 256 rows
+pub const RNG_V2_EXPAND: u8 = 2;
+
+/// Block values mixed into seeds that come from the chain itself
+pub struct BlockEntropy<'a> {
+    pub tx_index: u32,
+    pub height: u64,
+    pub time_nanos: u64,
+    pub contract: &'a str,
+}
+
+/// Seed of a Play settled in the transaction that placed it
+/// None of these inputs are secret, so anyone can compute the outcome before sending the Play
+pub fn block_seed(
+    block: &BlockEntropy,
+    player: &str,
+    nonce: u64,
+    funds_amount: Option<u128>,
+) -> [u8; 32] {
+    // Create seed from multiple sources
+    let mut hasher = Sha256::new();
+    hasher.update(block.tx_index.to_be_bytes());
+    hasher.update(block.height.to_be_bytes());
+    hasher.update(block.time_nanos.to_be_bytes());
+    hasher.update(player.as_bytes());
+    hasher.update(nonce.to_be_bytes());
+    hasher.update(block.contract.as_bytes());
+
+    if let Some(amount) = funds_amount {
+        hasher.update(amount.to_be_bytes());
+    }
+
+    hasher.finalize().into()
+}
+
+/// Seed of a two-phase bet settled with the entropy of the block at its settle height
+/// The time of that block is not known when the bet is placed, and every transaction in it
+/// derives the same seed, so whoever settles the bet cannot pick its outcome.
+pub fn settle_block_seed(
+    contract: &str,
+    bet_id: u64,
+    player: &str,
+    nonce: u64,
+    placed_height: u64,
+    height: u64,
+    time_nanos: u64,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(bet_id.to_be_bytes());
+    hasher.update(player.as_bytes());
+    hasher.update(nonce.to_be_bytes());
+    hasher.update(placed_height.to_be_bytes());
+    hasher.update(height.to_be_bytes());
+    hasher.update(time_nanos.to_be_bytes());
+    hasher.update(contract.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Generate a path from an already derived 32 byte seed with the current RNG version
+pub fn generate_path_from_seed(seed: [u8; 32], rows: u8) -> Vec<u8> {
+    expand_path(seed, rows)
+}
+
+/// Generate a path with the algorithm of `rng_version`, so historical games can be replayed
+/// Returns None for unknown versions
+pub fn generate_path(seed: [u8; 32], rows: u8, rng_version: u8) -> Option<Vec<u8>> {
+    match rng_version {
+        RNG_V1_REHASH => Some(rehash_path(seed, rows)),
+        RNG_V2_EXPAND => Some(expand_path(seed, rows)),
+        _ => None,
+    }
+}
+
+/// Version 2: row i is bit i of the seed, least significant bit of each byte first
+/// The seed is already a SHA-256 digest, so a path of up to 256 rows needs no further hashing.
+pub fn expand_path(seed: [u8; 32], rows: u8) -> Vec<u8> {
+    expand_bits(seed, rows as usize)
+}
+
+/// Expand a seed into `count` uniform bits (0 or 1)
+/// Bits 0..256 come from the seed, bits 256 * k.. from sha256(seed | k) for the k-th extra block.
+pub fn expand_bits(seed: [u8; 32], count: usize) -> Vec<u8> {
+    let mut bits = Vec::with_capacity(count);
+    let mut block = seed;
+    let mut counter: u32 = 0;
+
+    while bits.len() < count {
+        if counter > 0 {
+            let mut hasher = Sha256::new();
+            hasher.update(seed);
+            hasher.update(counter.to_be_bytes());
+            block = hasher.finalize().into();
+        }
+
+        let take = (count - bits.len()).min(256);
+        bits.extend((0..take).map(|i| (block[i / 8] >> (i % 8)) & 1));
+        counter += 1;
+    }
+
+    bits
+}
+
+/// Version 1: each row rehashes the previous digest and uses the low bit of its first byte
+pub fn rehash_path(seed: [u8; 32], rows: u8) -> Vec<u8> {
+    let mut path = Vec::new();
+    let mut seed = seed;
+
+    // Generate path (0 = left, 1 = right)
+    for _ in 0..rows {
+        seed = Sha256::digest(seed).into();
+
+        // Use the low bit of the first byte to determine direction
+        let direction = seed[0] & 1;
+        path.push(direction);
+    }
+
+    path
+}
+
+/// Hash a server seed the way the operator commits it on-chain
+pub fn hash_server_seed(server_seed: &[u8]) -> [u8; 32] {
+    Sha256::digest(server_seed).into()
+}
+
+/// Derive the seed for a game played against a committed server seed
+/// Every variable length input is length prefixed so fields cannot be shifted into each other:
+/// sha256(len(server_seed) | server_seed | len(client_seed) | client_seed | player | nonce)
+pub fn commitment_seed(
+    server_seed: &[u8],
+    client_seed: &str,
+    player: &str,
+    nonce: u64,
+) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update((server_seed.len() as u64).to_be_bytes());
+    hasher.update(server_seed);
+    hasher.update((client_seed.len() as u64).to_be_bytes());
+    hasher.update(client_seed.as_bytes());
+    hasher.update(player.as_bytes());
+    hasher.update(nonce.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// Message the operator signs with its BLS key for an OperatorSignature bet:
+/// contract | len(player) | player | nonce | bet_id
+pub fn operator_message(contract: &str, player: &str, nonce: u64, bet_id: u64) -> Vec<u8> {
+    let mut message = Vec::new();
+    message.extend_from_slice(contract.as_bytes());
+    message.extend_from_slice(&(player.len() as u64).to_be_bytes());
+    message.extend_from_slice(player.as_bytes());
+    message.extend_from_slice(&nonce.to_be_bytes());
+    message.extend_from_slice(&bet_id.to_be_bytes());
+    message
+}
+
+/// Derive the seed for an OperatorSignature bet
+/// A BLS signature is unique per key and message, so the operator has exactly one valid seed
+/// per bet to offer and can only withhold it, not pick it
+pub fn signature_seed(signature: &[u8]) -> [u8; 32] {
+    Sha256::digest(signature).into()
+}
+
+/// Domain separation tag of G1 signatures: drand's bls-unchained-g1-rfc9380 scheme (quicknet),
+/// also used by the operator key
+pub const BLS_G1_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// Message signed by an unchained drand beacon: sha256(round)
+pub fn drand_message(round: u64) -> [u8; 32] {
+    Sha256::digest(round.to_be_bytes()).into()
+}
+
+/// Derive the seed for a drand bet
+/// The round randomness is sha256(signature), as published by drand. It is mixed with the bet
+/// so that bets tied to the same round still drop independently.
+pub fn drand_seed(signature: &[u8], bet_id: u64, player: &str, nonce: u64) -> [u8; 32] {
+    let randomness = Sha256::digest(signature);
+
+    let mut hasher = Sha256::new();
+    hasher.update(randomness);
+    hasher.update(bet_id.to_be_bytes());
+    hasher.update(player.as_bytes());
+    hasher.update(nonce.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// First drand round published at or after `time`
+pub fn drand_round_at(genesis_time: u64, period_seconds: u64, time: u64) -> u64 {
+    // Round r is published at genesis_time + (r - 1) * period_seconds
+    time.saturating_sub(genesis_time).div_ceil(period_seconds) + 1
+}
+
+/// Time drand publishes `round` at
+pub fn drand_round_time(genesis_time: u64, period_seconds: u64, round: u64) -> u64 {
+    genesis_time + round.saturating_sub(1) * period_seconds
+}
+
+/// Calculate final bucket index from path
+pub fn calculate_bucket_index(path: &[u8]) -> usize {
+    path.iter().filter(|&&x| x == 1).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: u32 = 100_000;
+
+    /// Count how often each row goes right over SAMPLES independent seeds
+    fn right_counts(rows: usize, path: impl Fn([u8; 32]) -> Vec<u8>) -> Vec<u32> {
+        let mut counts = vec![0u32; rows];
+        for i in 0..SAMPLES {
+            let seed: [u8; 32] = Sha256::digest(i.to_be_bytes()).into();
+            for (row, bit) in path(seed).into_iter().enumerate() {
+                counts[row] += bit as u32;
+            }
+        }
+        counts
+    }
+
+    /// Every row must be a fair coin: within 5 standard deviations of SAMPLES / 2
+    fn assert_unbiased(counts: &[u32]) {
+        let mean = SAMPLES as f64 / 2.0;
+        let sigma = (SAMPLES as f64 * 0.25).sqrt();
+        for (row, &count) in counts.iter().enumerate() {
+            let z = (count as f64 - mean) / sigma;
+            assert!(
+                z.abs() < 5.0,
+                "row {row} is biased: {count} rights, z = {z:.2}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_expand_path_rows_are_unbiased() {
+        assert_unbiased(&right_counts(16, |seed| expand_path(seed, 16)));
+    }
+
+    #[test]
+    fn test_rehash_path_rows_are_unbiased() {
+        assert_unbiased(&right_counts(16, |seed| rehash_path(seed, 16)));
+    }
+
+    #[test]
+    fn test_counter_mode_bits_are_unbiased() {
+        // 600 bits span the seed and two counter blocks
+        let counts = right_counts(600, |seed| expand_bits(seed, 600));
+        assert_unbiased(&counts);
+    }
+
+    #[test]
+    fn test_expand_bits() {
+        let seed = [0b1010_0101u8; 32];
+        assert_eq!(expand_bits(seed, 8), vec![1, 0, 1, 0, 0, 1, 0, 1]);
+        assert_eq!(expand_bits(seed, 256).len(), 256);
+
+        // Past 256 bits the seed itself is not repeated
+        let bits = expand_bits(seed, 512);
+        assert_eq!(&bits[..256], &expand_bits(seed, 256)[..]);
+        assert_ne!(&bits[256..], &bits[..256]);
+    }
+
+    #[test]
+    fn test_generate_path_versions() {
+        let seed = [7u8; 32];
+        assert_eq!(
+            generate_path(seed, 16, RNG_V1_REHASH),
+            Some(rehash_path(seed, 16))
+        );
+        assert_eq!(
+            generate_path(seed, 16, RNG_V2_EXPAND),
+            Some(expand_path(seed, 16))
+        );
+        assert_eq!(generate_path(seed, 16, 0), None);
+        assert_eq!(generate_path_from_seed(seed, 16), expand_path(seed, 16));
+    }
+
+    #[test]
+    fn test_drand_round_time() {
+        assert_eq!(drand_round_time(100, 3, 1), 100);
+        assert_eq!(drand_round_time(100, 3, 4), 109);
+        for time in [100, 108, 109, 110] {
+            let round = drand_round_at(100, 3, time);
+            assert!(drand_round_time(100, 3, round) >= time);
+            assert!(drand_round_time(100, 3, round - 1) < time || round == 1);
+        }
+    }
+}
@@ -0,0 +1,407 @@
+//! Seed inputs of recorded games and their replay, shared by the contract's `VerifyGame` query
+//! and the off-chain verifier. Signatures are checked through a cosmwasm `Api`: the contract's
+//! host functions on-chain, the same cosmwasm-crypto natively off-chain.
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    Addr, Api, Binary, Decimal, HashFunction, HexBinary, Uint128, VerificationError,
+    BLS12_381_G2_GENERATOR,
+};
+
+use crate::multipliers::{play_table, PlayError};
+use crate::rng::{
+    block_seed, commitment_seed, drand_message, drand_seed, hash_server_seed, operator_message,
+    settle_block_seed, signature_seed, BlockEntropy, BLS_G1_DST,
+};
+
+/// Everything the seed of a game was derived from, besides the player and nonce
+#[cw_serde]
+pub enum SeedInputs {
+    /// Instant Play settled in the transaction that placed it
+    Block {
+        tx_index: u32,
+        height: u64,
+        time_nanos: u64,
+        contract: Addr,
+        funds_amount: Option<Uint128>, // First coin sent with the Play
+    },
+    /// Play against a committed server seed
+    Commitment {
+        commitment_id: u64,
+        server_seed: HexBinary,
+        client_seed: String,
+    },
+    /// PlaceBet settled with the entropy of the block at its settle height
+    SettleBlock {
+        bet_id: u64,
+        placed_height: u64,
+        height: u64,
+        time_nanos: u64,
+        contract: Addr,
+    },
+    /// PlaceBet settled with an operator signature
+    OperatorSignature {
+        bet_id: u64,
+        contract: Addr,
+        public_key: Binary,
+        signature: Binary,
+    },
+    /// PlaceBet settled with a drand beacon
+    Drand {
+        bet_id: u64,
+        round: u64,
+        public_key: HexBinary,
+        signature: Binary,
+    },
+}
+
+/// The parts of a recorded game a replay checks
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordedGame<'a> {
+    pub player: &'a str,
+    pub nonce: u64,
+    pub rows: u8,
+    pub rng_version: u8,
+    pub bet_amount: Uint128,
+    pub multiplier: Decimal,
+    pub win_amount: Uint128,
+    pub path: &'a [bool],
+    pub seed_inputs: Option<&'a SeedInputs>,
+}
+
+/// Outcome of replaying a game
+#[derive(Clone, Debug, PartialEq)]
+pub struct Verification {
+    pub verified: bool, // Replay matches the record and any signature or commitment checks out
+    pub path: Vec<bool>,
+    pub bucket: usize,
+    pub multiplier: Decimal,
+    pub win_amount: Uint128,
+}
+
+/// Why a game could not be replayed at all
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    NoSeedInputs, // Recorded before seed inputs were stored
+    Play(PlayError),
+}
+
+impl From<PlayError> for VerifyError {
+    fn from(err: PlayError) -> Self {
+        VerifyError::Play(err)
+    }
+}
+
+/// Recompute the 32 byte seed of a game from its recorded inputs
+pub fn derive_seed(inputs: &SeedInputs, player: &str, nonce: u64) -> [u8; 32] {
+    match inputs {
+        SeedInputs::Block {
+            tx_index,
+            height,
+            time_nanos,
+            contract,
+            funds_amount,
+        } => {
+            let block = BlockEntropy {
+                tx_index: *tx_index,
+                height: *height,
+                time_nanos: *time_nanos,
+                contract: contract.as_str(),
+            };
+            block_seed(&block, player, nonce, funds_amount.map(|a| a.u128()))
+        }
+        SeedInputs::Commitment {
+            server_seed,
+            client_seed,
+            ..
+        } => commitment_seed(server_seed, client_seed, player, nonce),
+        SeedInputs::SettleBlock {
+            bet_id,
+            placed_height,
+            height,
+            time_nanos,
+            contract,
+        } => settle_block_seed(
+            contract.as_str(),
+            *bet_id,
+            player,
+            nonce,
+            *placed_height,
+            *height,
+            *time_nanos,
+        ),
+        SeedInputs::OperatorSignature { signature, .. } => signature_seed(signature),
+        SeedInputs::Drand {
+            bet_id, signature, ..
+        } => drand_seed(signature, *bet_id, player, nonce),
+    }
+}
+
+/// Verify an unchained drand beacon: a G1 signature over sha256(round) by a G2 group key
+pub fn verify_drand_beacon(
+    api: &dyn Api,
+    public_key: &[u8],
+    round: u64,
+    signature: &[u8],
+) -> Result<bool, VerificationError> {
+    verify_bls_signature(api, public_key, &drand_message(round), signature)
+}
+
+/// Verify a BLS12-381 G1 signature over `message` by a 96 byte G2 public key
+pub fn verify_bls_signature(
+    api: &dyn Api,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &[u8],
+) -> Result<bool, VerificationError> {
+    let message_point = api.bls12_381_hash_to_g1(HashFunction::Sha256, message, BLS_G1_DST)?;
+
+    // e(signature, g2) == e(H(message), public_key)
+    api.bls12_381_pairing_equality(
+        signature,
+        &BLS12_381_G2_GENERATOR,
+        &message_point,
+        public_key,
+    )
+}
+
+/// Replay a recorded game from its seed inputs and compare the result with the record.
+/// A commitment game only verifies against the on-chain `commitment`; pass None to skip that
+/// check when the commitment is not at hand.
+/// The payout is recomputed from `table`, the multipliers the game was played with. Without
+/// it only the path is replayed and the win is checked against the recorded multiplier.
+pub fn verify_game(
+    api: &dyn Api,
+    game: &RecordedGame,
+    commitment: Option<&[u8]>,
+    table: Option<&[Decimal]>,
+) -> Result<Verification, VerifyError> {
+    let seed_inputs = game.seed_inputs.ok_or(VerifyError::NoSeedInputs)?;
+
+    // The seed source itself must check out, not just the hash of it
+    let source_valid = match seed_inputs {
+        SeedInputs::Block { .. } | SeedInputs::SettleBlock { .. } => true,
+        SeedInputs::Commitment { server_seed, .. } => {
+            commitment.is_none_or(|c| hash_server_seed(server_seed) == c)
+        }
+        SeedInputs::OperatorSignature {
+            bet_id,
+            contract,
+            public_key,
+            signature,
+        } => {
+            let message = operator_message(contract.as_str(), game.player, game.nonce, *bet_id);
+            verify_bls_signature(api, public_key, &message, signature).unwrap_or(false)
+        }
+        SeedInputs::Drand {
+            round,
+            public_key,
+            signature,
+            ..
+        } => verify_drand_beacon(api, public_key, *round, signature).unwrap_or(false),
+    };
+
+    let seed = derive_seed(seed_inputs, game.player, game.nonce);
+    // Games are replayed with the RNG they were played with
+    let bet_amount = game.bet_amount.u128();
+    let outcome = match table {
+        Some(table) => play_table(seed, game.rng_version, table, bet_amount),
+        // Every bucket of the unknown table is taken to pay what the record says it paid
+        None => play_table(
+            seed,
+            game.rng_version,
+            &vec![game.multiplier; game.rows as usize + 1],
+            bet_amount,
+        ),
+    }?;
+
+    let path: Vec<bool> = outcome.path.iter().map(|&b| b != 0).collect();
+    let win_amount = Uint128::new(outcome.win_amount);
+    let verified = source_valid
+        && path == game.path
+        && win_amount == game.win_amount
+        && outcome.multiplier == game.multiplier;
+
+    Ok(Verification {
+        verified,
+        path,
+        bucket: outcome.bucket,
+        multiplier: outcome.multiplier,
+        win_amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multipliers::table_for_rows;
+    use crate::rng::RNG_VERSION;
+    use crate::RiskLevel;
+    use ark_bls12_381::{Fr, G1Affine, G2Affine};
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use cosmwasm_std::testing::MockApi;
+
+    const PLAYER: &str = "inj1player";
+    const DRAND_PUBLIC_KEY: &str = "a1ee12542360bf75742bcade13d6134e7d5283d9eb782887c47d3d9725f05805d37b0106b7f744395bf82c175dd7434a169e998f188a657a030d588892c0cd2c01f996aaf331c4d8bc5b9734bbe261d09e7d2d39ef88b635077f262bd7bbb30f";
+    const DRAND_ROUND_3_SIGNATURE: &str = "b98dae74f6a9d2ec79d75ba273dcfda86a45d589412860eb4c0fd056b00654dbf667c1b6884987c9aee0d43f8ba9db52";
+
+    /// A game on `seed_inputs` as the contract would record it, paid on the 12 row high table
+    struct Recorded {
+        seed_inputs: SeedInputs,
+        path: Vec<bool>,
+        multiplier: Decimal,
+        win_amount: Uint128,
+        table: Vec<Decimal>,
+    }
+
+    impl Recorded {
+        fn new(seed_inputs: SeedInputs) -> Self {
+            let table = table_for_rows(12, &RiskLevel::High).unwrap();
+            let seed = derive_seed(&seed_inputs, PLAYER, 7);
+            let outcome = play_table(seed, RNG_VERSION, &table, 1_000).unwrap();
+            Recorded {
+                seed_inputs,
+                path: outcome.path.iter().map(|&b| b != 0).collect(),
+                multiplier: outcome.multiplier,
+                win_amount: Uint128::new(outcome.win_amount),
+                table,
+            }
+        }
+
+        fn game(&self) -> RecordedGame<'_> {
+            RecordedGame {
+                player: PLAYER,
+                nonce: 7,
+                rows: 12,
+                rng_version: RNG_VERSION,
+                bet_amount: Uint128::new(1_000),
+                multiplier: self.multiplier,
+                win_amount: self.win_amount,
+                path: &self.path,
+                seed_inputs: Some(&self.seed_inputs),
+            }
+        }
+
+        fn verified(&self, game: &RecordedGame, table: bool) -> bool {
+            let table = table.then_some(self.table.as_slice());
+            verify_game(&MockApi::default(), game, None, table)
+                .unwrap()
+                .verified
+        }
+    }
+
+    fn commitment() -> SeedInputs {
+        SeedInputs::Commitment {
+            commitment_id: 1,
+            server_seed: HexBinary::from(b"server seed".as_slice()),
+            client_seed: "client seed".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verify_game() {
+        let recorded = Recorded::new(commitment());
+        let game = recorded.game();
+        assert!(recorded.verified(&game, true));
+        // Without the table the win still follows from the recorded multiplier
+        assert!(recorded.verified(&game, false));
+
+        // The replay is reported whether or not it matches
+        let res = verify_game(&MockApi::default(), &game, None, Some(&recorded.table)).unwrap();
+        assert_eq!(res.path, recorded.path);
+        assert_eq!(res.multiplier, recorded.multiplier);
+        assert_eq!(res.win_amount, recorded.win_amount);
+
+        // The server seed must be the committed one
+        let commitment = hash_server_seed(b"server seed");
+        let api = MockApi::default();
+        assert!(
+            verify_game(&api, &game, Some(&commitment), None)
+                .unwrap()
+                .verified
+        );
+        let other = hash_server_seed(b"other seed");
+        assert!(
+            !verify_game(&api, &game, Some(&other), None)
+                .unwrap()
+                .verified
+        );
+    }
+
+    #[test]
+    fn test_tampered_game() {
+        let recorded = Recorded::new(commitment());
+
+        let flipped: Vec<bool> = recorded.path.iter().map(|b| !b).collect();
+        let mut tampered = recorded.game();
+        tampered.path = &flipped;
+        assert!(!recorded.verified(&tampered, true));
+
+        let mut tampered = recorded.game();
+        tampered.win_amount += Uint128::one();
+        assert!(!recorded.verified(&tampered, true));
+        assert!(!recorded.verified(&tampered, false));
+
+        // A multiplier the table does not pay in that bucket
+        let mut tampered = recorded.game();
+        tampered.multiplier += Decimal::percent(1);
+        assert!(!recorded.verified(&tampered, true));
+
+        let mut tampered = recorded.game();
+        tampered.seed_inputs = None;
+        let err = verify_game(&MockApi::default(), &tampered, None, None).unwrap_err();
+        assert_eq!(err, VerifyError::NoSeedInputs);
+    }
+
+    #[test]
+    fn test_signed_sources() {
+        let api = MockApi::default();
+        let secret = Fr::from(7u64);
+        let mut public_key = Vec::new();
+        (G2Affine::generator() * secret)
+            .into_affine()
+            .serialize_compressed(&mut public_key)
+            .unwrap();
+        let sign = |message: &[u8]| {
+            let point = api
+                .bls12_381_hash_to_g1(HashFunction::Sha256, message, BLS_G1_DST)
+                .unwrap();
+            let point = G1Affine::deserialize_compressed(&point[..]).unwrap();
+            let mut signature = Vec::new();
+            (point * secret)
+                .into_affine()
+                .serialize_compressed(&mut signature)
+                .unwrap();
+            signature
+        };
+        let signed = |signature: &[u8]| SeedInputs::OperatorSignature {
+            bet_id: 1,
+            contract: Addr::unchecked("inj1contract"),
+            public_key: Binary::from(public_key.as_slice()),
+            signature: Binary::from(signature),
+        };
+
+        let signature = sign(&operator_message("inj1contract", PLAYER, 7, 1));
+        let recorded = Recorded::new(signed(&signature));
+        assert!(recorded.verified(&recorded.game(), true));
+
+        // A game replayed from a forged signature
+        let recorded = Recorded::new(signed(&sign(b"another bet")));
+        assert!(!recorded.verified(&recorded.game(), true));
+
+        let beacon = |signature: &str| SeedInputs::Drand {
+            bet_id: 1,
+            round: 3,
+            public_key: HexBinary::from_hex(DRAND_PUBLIC_KEY).unwrap(),
+            signature: HexBinary::from_hex(signature).unwrap().to_vec().into(),
+        };
+        let recorded = Recorded::new(beacon(DRAND_ROUND_3_SIGNATURE));
+        assert!(recorded.verified(&recorded.game(), true));
+
+        let mut signature = DRAND_ROUND_3_SIGNATURE.to_string();
+        signature.replace_range(..2, "a9");
+        let recorded = Recorded::new(beacon(&signature));
+        assert!(!recorded.verified(&recorded.game(), true));
+    }
+}
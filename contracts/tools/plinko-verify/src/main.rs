@@ -0,0 +1,174 @@
+//! Replays recorded Plinko games off-chain and flags any game whose recorded outcome does not
+//! follow from its seed inputs.
+//!
+//! Usage:
+//!   plinko-verify history <history.json | -> [tables.json]
+//!   plinko-verify seed <seed_inputs.json> <player> <nonce> <difficulty|rows> <risk_level> <bet_amount> [rng_version]
+//!
+//! `history` takes the JSON of a `History` query (`HistoryResponse`), and optionally a JSON array
+//! of `PayoutTableVersion` query results to recompute wins on the tables the games were paid on.
+//! `seed` replays a single game from its `SeedInputs` and prints the outcome.
+
+mod record;
+mod verify;
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::ExitCode;
+
+use cosmwasm_std::Decimal;
+use plinko_fairness::rng::{hash_server_seed, RNG_VERSION};
+use plinko_fairness::verify::{derive_seed, SeedInputs};
+use plinko_fairness::{get_rows, play_table, table_for_rows, Difficulty, RiskLevel};
+
+use crate::record::{GameRecord, HistoryResponse, PayoutTableVersion};
+use crate::verify::verify_record;
+
+const USAGE: &str = "usage:
+  plinko-verify history <history.json | -> [tables.json]
+  plinko-verify seed <seed_inputs.json> <player> <nonce> <difficulty|rows> <risk_level> <bet_amount> [rng_version]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("history") if args.len() == 2 || args.len() == 3 => {
+            verify_history(&args[1], args.get(2).map(String::as_str))
+        }
+        Some("seed") if args.len() == 7 || args.len() == 8 => replay_seed(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Verify every game of a `HistoryResponse`, returning whether all of them check out
+fn verify_history(source: &str, tables: Option<&str>) -> Result<bool, String> {
+    let history: HistoryResponse = serde_json::from_str(&read_source(source)?)
+        .map_err(|e| format!("invalid history JSON: {e}"))?;
+    let tables: HashMap<u64, Vec<Decimal>> = match tables {
+        Some(tables) => serde_json::from_str::<Vec<PayoutTableVersion>>(&read_source(tables)?)
+            .map_err(|e| format!("invalid payout tables JSON: {e}"))?
+            .into_iter()
+            .map(|version| (version.table_id, version.multipliers))
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    let mut all_verified = true;
+
+    for game in &history.games {
+        // Games whose table is not at hand have their win checked against the recorded multiplier
+        let table = game.table_id.and_then(|id| tables.get(&id));
+        match verify_record(game, table.map(Vec::as_slice)) {
+            Ok(res) if res.verified => {
+                println!(
+                    "{} #{}: ok bucket={} multiplier={} win_amount={}",
+                    game.player, game.nonce, res.bucket, res.multiplier, res.win_amount
+                );
+                print_commitment_note(game);
+            }
+            Ok(res) => {
+                all_verified = false;
+                println!(
+                    "{} #{}: MISMATCH recorded path={} multiplier={} win_amount={}, replayed path={} multiplier={} win_amount={}",
+                    game.player,
+                    game.nonce,
+                    path_to_string(&game.path),
+                    game.multiplier,
+                    game.win_amount,
+                    path_to_string(&res.path),
+                    res.multiplier,
+                    res.win_amount
+                );
+            }
+            Err(err) => println!("{} #{}: skipped, {err}", game.player, game.nonce),
+        }
+    }
+
+    Ok(all_verified)
+}
+
+/// Replay a single game from its seed inputs
+fn replay_seed(args: &[String]) -> Result<bool, String> {
+    let inputs: SeedInputs = serde_json::from_str(&read_source(&args[0])?)
+        .map_err(|e| format!("invalid seed inputs JSON: {e}"))?;
+    let player = &args[1];
+    let nonce: u64 = args[2].parse().map_err(|_| "invalid nonce".to_string())?;
+    // A row count, or a difficulty standing for one
+    let rows: u8 = match args[3].parse() {
+        Ok(rows) => rows,
+        Err(_) => get_rows(&parse_enum::<Difficulty>(&args[3])?),
+    };
+    let risk_level: RiskLevel = parse_enum(&args[4])?;
+    let bet_amount: u128 = args[5]
+        .parse()
+        .map_err(|_| "invalid bet amount".to_string())?;
+    let rng_version: u8 = match args.get(6) {
+        Some(version) => version
+            .parse()
+            .map_err(|_| "invalid RNG version".to_string())?,
+        None => RNG_VERSION,
+    };
+
+    let seed = derive_seed(&inputs, player, nonce);
+    let table = table_for_rows(rows, &risk_level).ok_or(format!("no table for {rows} rows"))?;
+    let outcome = play_table(seed, rng_version, &table, bet_amount)
+        .map_err(|e| format!("cannot pay out: {e:?}"))?;
+
+    println!(
+        "path={}",
+        outcome
+            .path
+            .iter()
+            .map(|b| b.to_string())
+            .collect::<String>()
+    );
+    println!("bucket={}", outcome.bucket);
+    println!("multiplier={}", outcome.multiplier);
+    println!("win_amount={}", outcome.win_amount);
+    Ok(true)
+}
+
+/// Without chain access the commitment itself cannot be checked, so show what to compare it with
+fn print_commitment_note(game: &GameRecord) {
+    if let Some(SeedInputs::Commitment {
+        commitment_id,
+        server_seed,
+        ..
+    }) = &game.seed_inputs
+    {
+        let hash: String = hash_server_seed(server_seed)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect();
+        println!("  compare sha256(server_seed)={hash} with SeedCommitment {commitment_id}");
+    }
+}
+
+fn parse_enum<T: serde::de::DeserializeOwned>(value: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_lowercase()))
+        .map_err(|_| format!("invalid value: {value}"))
+}
+
+fn read_source(source: &str) -> Result<String, String> {
+    if source == "-" {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .map_err(|e| e.to_string())?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(source).map_err(|e| format!("cannot read {source}: {e}"))
+    }
+}
+
+fn path_to_string(path: &[bool]) -> String {
+    path.iter().map(|&b| if b { '1' } else { '0' }).collect()
+}
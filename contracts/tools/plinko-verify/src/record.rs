@@ -0,0 +1,66 @@
+//! Game records as the contract's `History` query returns them. Only the fields a replay needs
+//! are read; anything else in the JSON is ignored.
+
+use cosmwasm_std::{Decimal, Uint128};
+use plinko_fairness::verify::{RecordedGame, SeedInputs};
+use plinko_fairness::{get_rows, Difficulty, RiskLevel};
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct HistoryResponse {
+    pub games: Vec<GameRecord>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct GameRecord {
+    pub player: String,
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    #[serde(default)]
+    pub rows: u8, // 0 for games recorded before the row count was stored
+    pub risk_level: RiskLevel,
+    pub bet_amount: Uint128,
+    pub multiplier: Decimal,
+    pub win_amount: Uint128,
+    pub path: Vec<bool>,
+    #[serde(default)]
+    pub nonce: u64,
+    #[serde(default)]
+    pub rng_version: u8,
+    #[serde(default)]
+    pub seed_inputs: Option<SeedInputs>,
+    #[serde(default)]
+    pub table_id: Option<u64>, // Payout table version the game was paid on
+}
+
+impl GameRecord {
+    /// Rows the game was played on
+    pub fn board_rows(&self) -> u8 {
+        match (self.rows, &self.difficulty) {
+            (0, Some(difficulty)) => get_rows(difficulty),
+            (rows, _) => rows,
+        }
+    }
+
+    /// The parts of the record a replay checks
+    pub fn recorded_game(&self) -> RecordedGame<'_> {
+        RecordedGame {
+            player: &self.player,
+            nonce: self.nonce,
+            rows: self.board_rows(),
+            rng_version: self.rng_version,
+            bet_amount: self.bet_amount,
+            multiplier: self.multiplier,
+            win_amount: self.win_amount,
+            path: &self.path,
+            seed_inputs: self.seed_inputs.as_ref(),
+        }
+    }
+}
+
+/// A payout table version as the `PayoutTableVersion` query returns it
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct PayoutTableVersion {
+    pub table_id: u64,
+    pub multipliers: Vec<Decimal>,
+}
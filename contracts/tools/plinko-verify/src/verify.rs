@@ -0,0 +1,106 @@
+//! Replay of a recorded game with the fairness library, the same checks the contract's
+//! `VerifyGame` query runs on-chain.
+
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::Decimal;
+use plinko_fairness::verify::{verify_game, Verification, VerifyError};
+
+use crate::record::GameRecord;
+
+/// Replay a recorded game from its seed inputs and compare the result with the record.
+/// The payout is recomputed from `table`, the table version the game was paid on. Without it
+/// only the path is replayed and the win is checked against the recorded multiplier.
+/// Commitments are on-chain, so a commitment game only checks that its seed replays.
+pub fn verify_record(
+    record: &GameRecord,
+    table: Option<&[Decimal]>,
+) -> Result<Verification, String> {
+    // MockApi checks signatures with the same cosmwasm-crypto that backs the chain's host functions
+    verify_game(&MockApi::default(), &record.recorded_game(), None, table).map_err(|e| match e {
+        VerifyError::NoSeedInputs => {
+            "game was recorded without seed inputs and cannot be verified".to_string()
+        }
+        VerifyError::Play(e) => format!("cannot replay: {e:?}"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::record::{HistoryResponse, PayoutTableVersion};
+    use cosmwasm_std::Uint128;
+    use plinko_fairness::verify::SeedInputs;
+
+    /// Games and tables the contract produced, kept in sync by its `test_verifier_fixture`
+    const HISTORY: &str = include_str!("../testdata/history.json");
+    const TABLES: &str = include_str!("../testdata/tables.json");
+
+    fn fixture() -> (Vec<GameRecord>, Vec<PayoutTableVersion>) {
+        let history: HistoryResponse = serde_json::from_str(HISTORY).unwrap();
+        let tables: Vec<PayoutTableVersion> = serde_json::from_str(TABLES).unwrap();
+        (history.games, tables)
+    }
+
+    fn table_of(tables: &[PayoutTableVersion], game: &GameRecord) -> Vec<Decimal> {
+        let table_id = game.table_id.unwrap();
+        let version = tables.iter().find(|v| v.table_id == table_id).unwrap();
+        version.multipliers.clone()
+    }
+
+    #[test]
+    fn test_contract_records_verify() {
+        let (games, tables) = fixture();
+        // One game per kind of seed inputs the contract settles with
+        assert!(matches!(
+            games[0].seed_inputs,
+            Some(SeedInputs::Commitment { .. })
+        ));
+        assert!(matches!(
+            games[1].seed_inputs,
+            Some(SeedInputs::SettleBlock { .. })
+        ));
+        assert!(matches!(
+            games[2].seed_inputs,
+            Some(SeedInputs::OperatorSignature { .. })
+        ));
+
+        for game in &games {
+            let table = table_of(&tables, game);
+            let res = verify_record(game, Some(&table)).unwrap();
+            assert!(res.verified, "game {} does not replay", game.nonce);
+            assert_eq!(res.path, game.path);
+            assert_eq!(res.win_amount, game.win_amount);
+            // Without the table the win still follows from the recorded multiplier
+            assert!(verify_record(game, None).unwrap().verified);
+        }
+    }
+
+    #[test]
+    fn test_tampered_contract_records() {
+        let (games, tables) = fixture();
+
+        for game in &games {
+            let table = table_of(&tables, game);
+
+            let mut tampered = game.clone();
+            tampered.win_amount += Uint128::one();
+            assert!(!verify_record(&tampered, Some(&table)).unwrap().verified);
+
+            let mut tampered = game.clone();
+            tampered.nonce += 1;
+            assert!(!verify_record(&tampered, Some(&table)).unwrap().verified);
+
+            let mut tampered = game.clone();
+            tampered.seed_inputs = None;
+            assert!(verify_record(&tampered, Some(&table)).is_err());
+        }
+
+        // The operator signature only verifies for the bet it signed
+        let mut tampered = games[2].clone();
+        if let Some(SeedInputs::OperatorSignature { bet_id, .. }) = &mut tampered.seed_inputs {
+            *bet_id += 1;
+        }
+        let table = table_of(&tables, &tampered);
+        assert!(!verify_record(&tampered, Some(&table)).unwrap().verified);
+    }
+}
@@ -0,0 +1,16 @@
+#!/bin/bash
+
+# CosmWasm gas of path generation per RNG version, see meter/src/main.rs
+# Needs the nightly toolchain with rust-src and the wasm32-unknown-unknown target
+
+set -e
+
+cd "$(dirname "$0")/../.."
+
+# cosmwasm-vm only accepts MVP wasm, so std is rebuilt without the bulk memory and
+# sign extension ops Rust now enables for wasm32
+RUSTFLAGS="-C target-cpu=mvp -C link-arg=--allow-undefined" cargo +nightly build \
+  --release --lib --target wasm32-unknown-unknown -p rng-gas \
+  --target-dir target/mvp -Zbuild-std=std,panic_abort
+
+cargo run --release --manifest-path tools/rng-gas/meter/Cargo.toml
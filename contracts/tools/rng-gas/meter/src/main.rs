@@ -0,0 +1,112 @@
+//! CosmWasm gas of path generation per RNG version, metered the way a chain meters a contract.
+//!
+//! Usage: `tools/rng-gas/measure.sh` from the contracts workspace, which builds the probe
+//! contract as MVP wasm and runs `rng-gas-meter [rng_gas.wasm]`.
+//!
+//! Each measurement drops DROPS balls in one execution of the probe contract, and the gas of an
+//! execution without drops is taken off, leaving the gas of the RNG alone. Figures are in
+//! CosmWasm gas; wasmd divides them by its gas multiplier to charge SDK gas.
+
+use std::ops::RangeInclusive;
+use std::process::ExitCode;
+
+use cosmwasm_std::{Empty, Response};
+use cosmwasm_vm::testing::{
+    execute, instantiate, mock_env, mock_info, mock_instance_with_gas_limit, MockApi, MockQuerier,
+    MockStorage,
+};
+use cosmwasm_vm::Instance;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+const DEFAULT_WASM: &str = "target/mvp/wasm32-unknown-unknown/release/rng_gas.wasm";
+
+/// plinko_fairness::MIN_ROWS..=MAX_ROWS
+const ROWS: RangeInclusive<u8> = 8..=16;
+
+/// plinko_fairness::rng::RNG_V1_REHASH and RNG_V2_EXPAND
+const RNG_V1_REHASH: u8 = 1;
+const RNG_V2_EXPAND: u8 = 2;
+
+/// Balls dropped per measurement
+const DROPS: u32 = 100;
+
+const GAS_LIMIT: u64 = 1 << 50;
+
+type ProbeInstance = Instance<MockApi, MockStorage, MockQuerier>;
+
+// wasmer-vm links its probestack libcall to the stack probe compiler-builtins no longer exports.
+// Only Cranelift emits that libcall, never the singlepass compiler of cosmwasm-vm, so the symbol
+// has to resolve but is not called
+#[cfg(all(target_arch = "x86_64", not(target_os = "windows")))]
+#[no_mangle]
+extern "C" fn __rust_probestack() {}
+
+/// rng_gas::DropMsg
+#[derive(Serialize, JsonSchema)]
+struct DropMsg {
+    rows: u8,
+    rng_version: u8,
+    drops: u32,
+}
+
+fn main() -> ExitCode {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_WASM.to_string());
+    match measure(&path) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn measure(path: &str) -> Result<(), String> {
+    let wasm = std::fs::read(path)
+        .map_err(|e| format!("cannot read {path}: {e}\nbuild it with tools/rng-gas/measure.sh"))?;
+    let mut instance = mock_instance_with_gas_limit(&wasm, GAS_LIMIT);
+    let info = mock_info("creator", &[]);
+    let _: Response = instantiate(&mut instance, mock_env(), info, Empty {})
+        .into_result()
+        .map_err(|e| format!("cannot instantiate the probe: {e}"))?;
+
+    println!("CosmWasm gas per drop, averaged over {DROPS} drops");
+    println!(
+        "{:>4} {:>12} {:>12} {:>7}",
+        "rows", "v1_rehash", "v2_expand", "saved"
+    );
+    for rows in ROWS {
+        let v1 = gas_per_drop(&mut instance, rows, RNG_V1_REHASH)?;
+        let v2 = gas_per_drop(&mut instance, rows, RNG_V2_EXPAND)?;
+        let saved = 100.0 * (1.0 - v2 as f64 / v1 as f64);
+        println!("{rows:>4} {v1:>12} {v2:>12} {saved:>6.1}%");
+    }
+    Ok(())
+}
+
+/// Gas of one drop of `rows` rows with `rng_version`, without the cost of the execution itself
+fn gas_per_drop(instance: &mut ProbeInstance, rows: u8, rng_version: u8) -> Result<u64, String> {
+    let overhead = gas_used(instance, rows, rng_version, 0)?;
+    let total = gas_used(instance, rows, rng_version, DROPS)?;
+    Ok(total.saturating_sub(overhead) / DROPS as u64)
+}
+
+fn gas_used(
+    instance: &mut ProbeInstance,
+    rows: u8,
+    rng_version: u8,
+    drops: u32,
+) -> Result<u64, String> {
+    let msg = DropMsg {
+        rows,
+        rng_version,
+        drops,
+    };
+    let before = instance.get_gas_left();
+    let _: Response = execute(instance, mock_env(), mock_info("player", &[]), msg)
+        .into_result()
+        .map_err(|e| format!("{rows} rows with version {rng_version}: {e}"))?;
+    Ok(before - instance.get_gas_left())
+}
@@ -0,0 +1,74 @@
+//! Probe contract that only generates paths, so its gas is the RNG's and little else.
+//! Executing `{"rows":16,"rng_version":1,"drops":100}` generates 100 paths of 16 rows from
+//! consecutive seeds with version 1.
+
+use cosmwasm_std::{entry_point, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult};
+use plinko_fairness::rng::{calculate_bucket_index, generate_path};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DropMsg {
+    pub rows: u8,
+    pub rng_version: u8,
+    pub drops: u32,
+}
+
+#[entry_point]
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+#[entry_point]
+pub fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, msg: DropMsg) -> StdResult<Response> {
+    // Summing the buckets keeps the paths from being optimized away
+    let mut buckets = 0u64;
+    for drop in 0..msg.drops {
+        // Distinct seeds that cost next to nothing, where hashing would add to both versions
+        let word = drop.wrapping_add(1).wrapping_mul(0x9E37_79B9);
+        let mut seed = [0u8; 32];
+        for (i, chunk) in seed.chunks_mut(4).enumerate() {
+            chunk.copy_from_slice(&word.rotate_left(4 * i as u32).to_le_bytes());
+        }
+        let path = generate_path(seed, msg.rows, msg.rng_version)
+            .ok_or_else(|| StdError::generic_err("unknown rng version"))?;
+        buckets += calculate_bucket_index(&path) as u64;
+    }
+    Ok(Response::new().add_attribute("buckets", buckets.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::Addr;
+    use plinko_fairness::rng::{RNG_V1_REHASH, RNG_V2_EXPAND};
+
+    fn drop_balls(rows: u8, rng_version: u8, drops: u32) -> StdResult<Response> {
+        let mut deps = mock_dependencies();
+        let info = message_info(&Addr::unchecked("player"), &[]);
+        let msg = DropMsg {
+            rows,
+            rng_version,
+            drops,
+        };
+        execute(deps.as_mut(), mock_env(), info, msg)
+    }
+
+    #[test]
+    fn test_drop_balls() {
+        for rng_version in [RNG_V1_REHASH, RNG_V2_EXPAND] {
+            let res = drop_balls(16, rng_version, 100).unwrap();
+            let buckets: u64 = res.attributes[0].value.parse().unwrap();
+            // 100 paths of 16 coin flips land around bucket 8
+            assert!((600..1000).contains(&buckets), "{buckets}");
+        }
+        let res = drop_balls(16, RNG_V2_EXPAND, 0).unwrap();
+        assert_eq!(res.attributes[0].value, "0");
+        assert!(drop_balls(16, 0, 1).is_err());
+    }
+}
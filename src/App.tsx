@@ -8,7 +8,7 @@ import { useWallet } from './hooks/useWallet';
 import { usePlinkoGame } from './hooks/usePlinkoGame';
 import { Coins } from 'lucide-react';
 import { Difficulty, RiskLevel } from './types/game';
-import { MULTIPLIERS } from './config/multipliers';
+import { get_rows } from '@plinko/plinko-fairness-wasm';
 import { Leaderboard } from './components/Leaderboard';
 import { UserStatsPanel } from './components/UserStatsPanel';
 import { GameStatsPanel } from './components/GameStatsPanel';
@@ -26,18 +26,24 @@ function App() {
   const {
     balls,
     gameHistory,
+    pendingGames,
     plinkBalance,
     isLoading,
     error: gameError,
     contractsValid,
+    betLimits,
+    payoutTables,
     dropBall,
     purchasePlink,
     refreshBalance,
     onAnimationComplete
   } = usePlinkoGame(address, difficulty, riskLevel);
 
-  // --- Derive multipliers from state ---
-  const multipliers = MULTIPLIERS[difficulty][riskLevel];
+  // --- Multipliers of the board's on-chain payout table, none until it is loaded ---
+  const payoutTable = payoutTables.find(
+    (table) => table.rows === get_rows(difficulty) && table.risk_level === riskLevel
+  );
+  const multipliers = payoutTable ? payoutTable.multipliers.map(Number) : [];
 
   const handlePurchase = async (injAmount: string) => {
     try {
@@ -152,6 +158,7 @@ function App() {
               <GameControls
                 onPlay={handlePlay}
                 plinkBalance={plinkBalance}
+                betLimits={betLimits}
                 disabled={!contractsValid}
                 isLoading={isLoading}
                 difficulty={difficulty}
@@ -159,6 +166,11 @@ function App() {
                 onDifficultyChange={setDifficulty}
                 onRiskLevelChange={setRiskLevel}
               />
+              {pendingGames.length > 0 && (
+                <div className="bg-gray-800/50 border border-gray-700 rounded-xl p-4 text-sm text-gray-400">
+                  {pendingGames.length} {pendingGames.length === 1 ? 'ball' : 'balls'} waiting for the server seed reveal
+                </div>
+              )}
               {/* --- Game History for Desktop --- */}
               {gameHistory && gameHistory.length > 0 &&
                 <div className="hidden lg:block">
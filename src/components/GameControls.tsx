@@ -1,9 +1,10 @@
 import React, { useState } from 'react';
-import { Difficulty, RiskLevel } from '../types/game';
+import { BetLimits, Difficulty, RiskLevel } from '../types/game';
 
 interface GameControlsProps {
   onPlay: (betAmount: string, numberOfBalls: number) => void; // Updated prop
   plinkBalance: string;
+  betLimits: BetLimits | null; // Limits of the selected board, null until loaded
   disabled: boolean;
   isLoading: boolean;
   difficulty: Difficulty;
@@ -15,6 +16,7 @@ interface GameControlsProps {
 export const GameControls: React.FC<GameControlsProps> = ({
   onPlay,
   plinkBalance,
+  betLimits,
   disabled,
   isLoading,
   difficulty,
@@ -25,6 +27,10 @@ export const GameControls: React.FC<GameControlsProps> = ({
   const [betAmount, setBetAmount] = useState('10');
   const [numberOfBalls, setNumberOfBalls] = useState(1);
 
+  const bet = parseFloat(betAmount);
+  const outsideLimits = betLimits !== null
+    && (bet < parseFloat(betLimits.minBet) || bet > parseFloat(betLimits.maxBet));
+
   const handlePlayClick = () => {
 
     if (!disabled && !isLoading) {
@@ -72,6 +78,11 @@ export const GameControls: React.FC<GameControlsProps> = ({
         <p className="text-xs text-gray-400 mt-2">
           Your balance: <span className="font-bold text-purple-400">{plinkBalance} $PLINK</span>
         </p>
+        {betLimits && (
+          <p className={`text-xs mt-1 ${outsideLimits ? 'text-red-400' : 'text-gray-400'}`}>
+            Bet limits: {betLimits.minBet} – {betLimits.maxBet} $PLINK
+          </p>
+        )}
       </div>
 
       <div>
@@ -114,7 +125,7 @@ export const GameControls: React.FC<GameControlsProps> = ({
 
       <button
         onClick={handlePlayClick}
-        disabled={disabled || isLoading || parseFloat(betAmount) <= 0}
+        disabled={disabled || isLoading || bet <= 0 || outsideLimits}
         className="w-full py-4 bg-gradient-to-r from-green-500 to-emerald-600 text-white font-bold text-lg rounded-xl transition-all duration-300 shadow-lg hover:shadow-2xl disabled:opacity-50 disabled:cursor-not-allowed"
       >
         {isLoading ? 'Processing...' : 'Play'}
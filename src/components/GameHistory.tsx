@@ -1,5 +1,5 @@
 import React from 'react';
-import { History, TrendingUp, TrendingDown } from 'lucide-react';
+import { History, TrendingUp, TrendingDown, ShieldCheck, ShieldAlert } from 'lucide-react';
 import { GameResult } from '../types/game';
 import { formatTokenAmount } from '../utils/format';
 import { PLINK_TOKEN_DECIMALS } from '../config/contracts';
@@ -63,8 +63,18 @@ export const GameHistory: React.FC<GameHistoryProps> = ({ history }) => {
                     <span>Bet: {betFormatted} $PLINK</span>
                     <span>Won: {wonFormatted} $PLINK</span>
                   </div>
-                  <div className="text-xs text-gray-600 mt-2">
-                    {new Date(result.timestamp * 1000).toLocaleString()}
+                  <div className="flex justify-between items-center text-xs text-gray-600 mt-2">
+                    <span>{new Date(result.timestamp * 1000).toLocaleString()}</span>
+                    {result.verified === true && (
+                      <span className="flex items-center gap-1 text-green-500">
+                        <ShieldCheck size={14} /> Verified
+                      </span>
+                    )}
+                    {result.verified === false && (
+                      <span className="flex items-center gap-1 text-red-500">
+                        <ShieldAlert size={14} /> Replay mismatch
+                      </span>
+                    )}
                   </div>
                 </motion.div>
               );
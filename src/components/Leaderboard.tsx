@@ -81,7 +81,7 @@ export const Leaderboard = () => {
                         </div>
                         <div className="text-right">
                             <p className="font-semibold text-white">{formatValue(entry.value, type)}</p>
-                            {entry.multiplier && <p className="text-xs text-pink-400">{entry.multiplier}</p>}
+                            {entry.multiplier && <p className="text-xs text-pink-400">{entry.multiplier}x</p>}
                         </div>
                     </li>
                 ))}
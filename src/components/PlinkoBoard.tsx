@@ -1,7 +1,7 @@
 import React, { useEffect, useRef, useState } from 'react';
 import Matter from 'matter-js';
 import { Ball, GameResult } from '../types/game';
-import { ROWS_CONFIG } from '../config/multipliers';
+import { get_rows } from '@plinko/plinko-fairness-wasm';
 import { useWindowWidth } from '../hooks/useWindowWidth';
 import { AnimatePresence, motion } from 'framer-motion';
 
@@ -63,7 +63,7 @@ export const PlinkoBoard: React.FC<PlinkoBoardProps> = ({
 
   const pegsRef = useRef<Matter.Body[]>([]);
 
-  const rows = ROWS_CONFIG[difficulty];
+  const rows = get_rows(difficulty);
   const windowWidth = useWindowWidth();
 
   const historyContainerRef = useRef<HTMLDivElement>(null);
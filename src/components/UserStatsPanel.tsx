@@ -62,7 +62,7 @@ export const UserStatsPanel = () => {
             <StatItem icon={<TrendingUp size={16} />} label="Total Wagered" value={`${formatStatValue(stats.total_wagered)} PLINK`} />
             <StatItem icon={<Shield size={16} />} label="Total Won" value={`${formatStatValue(stats.total_won)} PLINK`} />
             <StatItem icon={<Trophy size={16} />} label="Best Win (PNL)" value={`${formatStatValue(stats.best_win_pnl)} PLINK`} />
-            <StatItem icon={<Trophy size={16} />} label="Best Multiplier" value={stats.best_win_multiplier || 'N/A'} />
+            <StatItem icon={<Trophy size={16} />} label="Best Multiplier" value={stats.best_win_multiplier ? `${stats.best_win_multiplier}x` : 'N/A'} />
         </div>
     );
 };
\ No newline at end of file
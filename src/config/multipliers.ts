@@ -1,34 +0,0 @@
-import { MultiplierConfig } from "../types/game";
-
-export const MULTIPLIERS: Record<string, MultiplierConfig> = {
-    easy: {
-        low: [5.6, 2.1, 1.1, 1.0, 0.5, 1.0, 1.1, 2.1, 5.6],
-        medium: [13, 3, 1.3, 0.7, 0.4, 0.7, 1.3, 3, 13],
-        high: [29, 4, 1.5, 0.3, 0.2, 0.3, 1.5, 4, 29],
-    },
-    medium: {
-        low: [8.9, 3, 1.4, 1.1, 1.0, 0.5, 0.4, 0.5, 1.0, 1.1, 1.4, 3, 8.9],
-        medium: [18, 4, 1.7, 0.9, 0.5, 0.3, 0.2, 0.3, 0.5, 0.9, 1.7, 4, 18],
-        high: [43, 7, 2, 0.6, 0.2, 0.2, 0.2, 0.2, 0.2, 0.6, 2, 7, 43],
-    },
-    hard: {
-        low: [
-            16, 9, 2, 1.4, 1.1, 1.0, 0.5, 0.4, 0.3, 0.4, 0.5, 1.0, 1.1, 1.4, 2,
-            9, 16,
-        ],
-        medium: [
-            110, 41, 10, 5, 3, 1.5, 1.0, 0.5, 0.4, 0.5, 1.0, 1.5, 3, 5, 10, 41,
-            110,
-        ],
-        high: [
-            1000, 130, 26, 9, 4, 2, 0.5, 0.2, 0.2, 0.2, 0.5, 2, 4, 9, 26, 130,
-            1000,
-        ],
-    },
-};
-
-export const ROWS_CONFIG = {
-    easy: 8,
-    medium: 12,
-    hard: 16,
-};
@@ -2,8 +2,22 @@ import { useState, useEffect, useCallback } from "react";
 import { WalletStrategy } from "@injectivelabs/wallet-strategy";
 import { ChainId, EvmChainId } from "@injectivelabs/ts-types";
 import { ContractService } from "../services/contractService";
-import { CHAIN_ID, validateContracts } from "../config/contracts";
-import { Difficulty, RiskLevel, GameResult } from "../types/game";
+import {
+    CHAIN_ID,
+    CONTRACTS,
+    PLINK_TOKEN_DECIMALS,
+    validateContracts,
+} from "../config/contracts";
+import {
+    Difficulty,
+    RiskLevel,
+    GameResult,
+    BetLimits,
+    PayoutTable,
+    PendingGame,
+} from "../types/game";
+import { formatTokenAmount } from "../utils/format";
+import { verifyGame } from "../utils/fairness";
 
 const chainId = CHAIN_ID === "injective-1" ? ChainId.Mainnet : ChainId.Testnet;
 
@@ -22,6 +36,8 @@ export const useContracts = (userAddress: string) => {
     const [error, setError] = useState<string>("");
     const [gameHistory, setGameHistory] = useState<any[]>([]);
     const [contractsValid, setContractsValid] = useState(false);
+    const [betLimits, setBetLimits] = useState<BetLimits | null>(null);
+    const [payoutTables, setPayoutTables] = useState<PayoutTable[]>([]);
 
     // Validate contracts on mount
     useEffect(() => {
@@ -64,6 +80,40 @@ export const useContracts = (userAddress: string) => {
         }
     }, [userAddress, contractsValid]);
 
+    // Fetch the bet limits of a board, which move with the house balance
+    const fetchBetLimits = useCallback(
+        async (difficulty: Difficulty, riskLevel: RiskLevel) => {
+            if (!contractsValid) return;
+            const contractService = new ContractService(walletStrategy);
+            const limits = await contractService.getBetLimits(
+                difficulty,
+                riskLevel
+            );
+            setBetLimits(
+                limits
+                    ? {
+                          minBet: formatTokenAmount(
+                              limits.min_bet,
+                              PLINK_TOKEN_DECIMALS
+                          ),
+                          maxBet: formatTokenAmount(
+                              limits.max_bet,
+                              PLINK_TOKEN_DECIMALS
+                          ),
+                      }
+                    : null
+            );
+        },
+        [contractsValid]
+    );
+
+    // Fetch the payout tables the admin has put in place
+    const fetchPayoutTables = useCallback(async () => {
+        if (!contractsValid) return;
+        const contractService = new ContractService(walletStrategy);
+        setPayoutTables(await contractService.getPayoutTables());
+    }, [contractsValid]);
+
     const getGameHistory = useCallback(
         async (limit: number = 20) => {
             const contractService = new ContractService(walletStrategy);
@@ -72,11 +122,32 @@ export const useContracts = (userAddress: string) => {
             try {
                 const historyFromContract =
                     await contractService.getGameHistory(userAddress, limit);
-                return historyFromContract.map((game, index) => ({
-                    ...game,
 
-                    ballId: game.ballId || `hist-${game.timestamp}-${index}`,
-                }));
+                // Replay every game locally on the table version it was paid on
+                const tableIds: number[] = [
+                    ...new Set<number>(
+                        historyFromContract
+                            .map((game) => game.table_id)
+                            .filter((tableId) => tableId != null)
+                    ),
+                ];
+                const tables = new Map<number, string[] | null>();
+                for (const tableId of tableIds) {
+                    tables.set(
+                        tableId,
+                        await contractService.getPayoutTableVersion(tableId)
+                    );
+                }
+
+                return historyFromContract.map((game, index) => {
+                    const table = tables.get(game.table_id);
+                    return {
+                        ...game,
+                        verified: table ? verifyGame(game, table) : undefined,
+                        ballId:
+                            game.ballId || `hist-${game.timestamp}-${index}`,
+                    };
+                });
             } catch (err: any) {
                 console.error("Error fetching history:", err);
                 return [];
@@ -162,6 +233,16 @@ export const useContracts = (userAddress: string) => {
                 throw new Error("Insufficient PLINK balance");
             }
 
+            if (
+                betLimits &&
+                (parseFloat(betAmount) < parseFloat(betLimits.minBet) ||
+                    parseFloat(betAmount) > parseFloat(betLimits.maxBet))
+            ) {
+                const message = `Bet must be between ${betLimits.minBet} and ${betLimits.maxBet} PLINK`;
+                setError(message);
+                throw new Error(message);
+            }
+
             setIsLoading(true);
             setError("");
 
@@ -174,9 +255,7 @@ export const useContracts = (userAddress: string) => {
                     userAddress
                 );
 
-                const gameResult = parseMultipleGameResults(result);
-
-                return gameResult;
+                return parsePendingGames(result);
             } catch (err: any) {
                 console.error("Play game error:", err);
                 setError(err.message || "Failed to play game");
@@ -185,13 +264,14 @@ export const useContracts = (userAddress: string) => {
                 setIsLoading(false);
             }
         },
-        [userAddress, plinkBalance, contractsValid]
+        [userAddress, plinkBalance, contractsValid, betLimits]
     );
 
-    const parseMultipleGameResults = (txResult: any): GameResult[] => {
+    // Balls a Play transaction placed, one pending game per nonce
+    const parsePendingGames = (txResult: any): PendingGame[] => {
         try {
             const events = txResult?.events || [];
-            const wasmEvents = events.filter(
+            const playEvent = events.find(
                 (e: any) =>
                     e.type === "wasm" &&
                     e.attributes.some(
@@ -199,92 +279,96 @@ export const useContracts = (userAddress: string) => {
                     )
             );
 
-            if (wasmEvents.length === 0) return [];
-
-            return wasmEvents.map((event: any, i) => {
-                const attrs = event.attributes || [];
-                const getAttr = (key: string) =>
-                    attrs.find((a: any) => a.key === key)?.value;
-
-                const pathString = getAttr("path");
-                // Use a more robust unique ID
-                const uniqueId = `ball-${Date.now()}-${Math.random()
-                    .toString(36)
-                    .substring(2, 9)}`;
-
-                return {
-                    ballId: uniqueId,
-                    bet_amount: getAttr("bet_amount"),
-                    multiplier: `${parseFloat(getAttr("multiplier"))}x`,
-                    win_amount: getAttr("win_amount"),
-                    timestamp: Date.parse(txResult.timestamp) / 1000,
-                    path: pathString ? pathString.split("").map(Number) : [],
-                    eventIndex: i,
-                };
-            });
-        } catch (err) {
-            console.error("Error parsing game results:", err);
-            return [];
-        }
-    };
+            if (!playEvent) return [];
 
-    // Parse game result from transaction
-    const parseGameResult = (txResult: any): GameResult | null => {
-        try {
-            console.log("Transaction result:", txResult);
-            // Extract attributes from transaction events
-            const events = txResult?.events || [];
-            const wasmEvent = events.find(
-                (e: any) =>
-                    e.type === "wasm" &&
-                    e.attributes.some(
-                        (a) => a.key == "action" && a.value == "play"
-                    )
-            );
-
-            if (!wasmEvent) return null;
-
-            const attrs = wasmEvent.attributes || [];
+            const attrs = playEvent.attributes || [];
             const getAttr = (key: string) =>
                 attrs.find((a: any) => a.key === key)?.value;
 
-            const betAmount = getAttr("bet_amount");
-            const winAmount = getAttr("win_amount");
-            const multiplier = getAttr("multiplier");
-            const pathString = getAttr("path");
+            const commitmentId = Number(getAttr("commitment_id"));
+            const firstNonce = Number(getAttr("nonce"));
+            const ballCount = Number(getAttr("ball_count"));
 
-            if (!betAmount || !winAmount || !multiplier || !pathString) {
-                console.error(
-                    "Essential game attributes missing from transaction event"
-                );
-                return null;
-            }
-
-            const path = pathString.split("").map(Number);
-
-            return {
-                ballId: `ball-${Date.now()}`,
-                betAmount,
-                multiplier: parseFloat(multiplier),
-                winAmount,
-                timestamp: Date.now(),
-                path: path,
-            };
+            return Array.from({ length: ballCount }, (_, i) => ({
+                commitmentId,
+                nonce: firstNonce + i,
+                betAmount: getAttr("bet_amount"),
+                placedAt: Date.now() / 1000,
+            }));
         } catch (err) {
-            console.error("Error parsing game result:", err);
-            return null;
+            console.error("Error parsing placed games:", err);
+            return [];
         }
     };
 
+    // Results of the pending games settled so far, from their settle_game events
+    const fetchSettledGames = useCallback(
+        async (pendingGames: PendingGame[]): Promise<GameResult[]> => {
+            if (!userAddress || !contractsValid || pendingGames.length === 0)
+                return [];
+            const contractService = new ContractService(walletStrategy);
+            const pendingNonces = new Set(pendingGames.map((g) => g.nonce));
+
+            try {
+                const txs = await contractService.getSettleTxs(userAddress);
+                const results: GameResult[] = [];
+
+                for (const tx of txs) {
+                    for (const event of tx.events || []) {
+                        if (event.type !== "wasm-settle_game") continue;
+
+                        const attrs = event.attributes || [];
+                        const getAttr = (key: string) =>
+                            attrs.find((a: any) => a.key === key)?.value;
+
+                        const nonce = Number(getAttr("nonce"));
+                        if (
+                            getAttr("_contract_address") !== CONTRACTS.game ||
+                            getAttr("player") !== userAddress ||
+                            !pendingNonces.has(nonce)
+                        ) {
+                            continue;
+                        }
+                        pendingNonces.delete(nonce);
+
+                        const pathString = getAttr("path");
+                        results.push({
+                            ballId: `ball-${nonce}`,
+                            nonce,
+                            bet_amount: getAttr("bet_amount"),
+                            multiplier: `${parseFloat(getAttr("multiplier"))}x`,
+                            win_amount: getAttr("win_amount"),
+                            timestamp: Date.parse(tx.timestamp) / 1000,
+                            path: pathString
+                                ? pathString.split("").map(Number)
+                                : [],
+                        } as any);
+                    }
+                }
+
+                return results;
+            } catch (err) {
+                console.error("Error fetching settled games:", err);
+                return [];
+            }
+        },
+        [userAddress, contractsValid]
+    );
+
     return {
         plinkBalance,
         gameHistory,
         isLoading,
         error,
         contractsValid,
+        betLimits,
+        payoutTables,
         purchasePlink,
         playGame,
+        fetchSettledGames,
         refreshBalance: fetchPlinkBalance,
+        fetchBetLimits,
+        fetchPayoutTables,
         getGameHistory,
     };
 };
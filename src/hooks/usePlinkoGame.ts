@@ -1,11 +1,19 @@
 import { useState, useCallback, useEffect, useRef } from "react";
-import { Difficulty, RiskLevel, Ball, GameResult } from "../types/game";
+import {
+    Difficulty,
+    RiskLevel,
+    Ball,
+    GameResult,
+    PendingGame,
+} from "../types/game";
+import { get_rows } from "@plinko/plinko-fairness-wasm";
 import { useContracts } from "./useContracts";
 
 const CANVAS_WIDTH = 800;
 const SPACING = 45;
 const MIN_DELAY_MS = 200; // The fastest possible time between ball drops
 const MAX_DELAY_MS = 500; // The slowest possible time between ball drops
+const SETTLE_POLL_MS = 5000; // How often pending games are checked for settlement
 
 export const usePlinkoGame = (
     userAddress: string,
@@ -15,6 +23,7 @@ export const usePlinkoGame = (
     const [balls, setBalls] = useState<Ball[]>([]);
     const [gameHistory, setGameHistory] = useState<GameResult[]>([]);
     const [pendingResults, setPendingResults] = useState<GameResult[]>([]);
+    const [pendingGames, setPendingGames] = useState<PendingGame[]>([]);
     const pendingDropTimeoutsRef = useRef<NodeJS.Timeout[]>([]);
 
     const {
@@ -22,9 +31,14 @@ export const usePlinkoGame = (
         isLoading,
         error,
         contractsValid,
+        betLimits,
+        payoutTables,
         purchasePlink,
         playGame,
+        fetchSettledGames,
         refreshBalance,
+        fetchBetLimits,
+        fetchPayoutTables,
         getGameHistory,
     } = useContracts(userAddress);
 
@@ -34,7 +48,15 @@ export const usePlinkoGame = (
         pendingDropTimeoutsRef.current.forEach(clearTimeout);
         pendingDropTimeoutsRef.current = [];
         refreshBalance();
-    }, [difficulty, riskLevel, refreshBalance]);
+        fetchBetLimits(difficulty, riskLevel);
+        fetchPayoutTables();
+    }, [
+        difficulty,
+        riskLevel,
+        refreshBalance,
+        fetchBetLimits,
+        fetchPayoutTables,
+    ]);
 
     useEffect(() => {
         const fetchInitialHistory = async () => {
@@ -84,6 +106,83 @@ export const usePlinkoGame = (
         [pendingResults, refreshBalance]
     );
 
+    // Drop the balls of settled games one after another on the board
+    const launchBalls = useCallback((gameResults: GameResult[]) => {
+        setPendingResults((prev) => [...prev, ...gameResults]);
+
+        const firstRowPegs = 3;
+        const firstRowWidth = (firstRowPegs - 1) * SPACING;
+        const firstRowStartX = (CANVAS_WIDTH - firstRowWidth) / 2;
+        const centerPegIndex = Math.floor(firstRowPegs / 2);
+        const startX = firstRowStartX + centerPegIndex * SPACING;
+
+        // This variable will track the total delay for the next ball.
+        let cumulativeDelay = 0;
+
+        // Loop through results and add each ball to the state with a random delay
+        gameResults.forEach((result) => {
+            // Schedule the creation of the current ball using the current cumulative delay.
+            // For the first ball, this will be 0, so it drops instantly.
+            const timeoutId = setTimeout(() => {
+                const newBall: Ball = {
+                    id: result.ballId,
+                    path: result.path,
+                    x: startX,
+                    y: 40,
+                    vx: 0,
+                    vy: 0,
+                    currentRow: -1,
+                    pegIndex: centerPegIndex,
+                    eventIndex: result.eventIndex,
+                };
+                setBalls((prev) => [...prev, newBall]);
+            }, cumulativeDelay);
+
+            pendingDropTimeoutsRef.current.push(timeoutId);
+
+            // Then, calculate a random interval for the *next* ball and add it to the total.
+            const randomInterval =
+                MIN_DELAY_MS + Math.random() * (MAX_DELAY_MS - MIN_DELAY_MS);
+            cumulativeDelay += randomInterval;
+        });
+    }, []);
+
+    // Balls land once the operator reveals the server seed and the games are settled
+    useEffect(() => {
+        if (pendingGames.length === 0) return;
+
+        let polling = false;
+        const interval = setInterval(async () => {
+            if (polling) return;
+            polling = true;
+            const settled = await fetchSettledGames(pendingGames);
+            polling = false;
+            if (settled.length === 0) return;
+
+            const settledNonces = new Set(settled.map((g: any) => g.nonce));
+            setPendingGames((prev) =>
+                prev.filter((g) => !settledNonces.has(g.nonce))
+            );
+
+            // Games of another board than the one shown go straight to the history
+            const rows = get_rows(difficulty);
+            launchBalls(settled.filter((g: any) => g.path.length === rows));
+            const elsewhere = settled.filter((g: any) => g.path.length !== rows);
+            if (elsewhere.length > 0) {
+                setGameHistory((prev) => [...elsewhere, ...prev]);
+                refreshBalance();
+            }
+        }, SETTLE_POLL_MS);
+
+        return () => clearInterval(interval);
+    }, [
+        pendingGames,
+        difficulty,
+        fetchSettledGames,
+        launchBalls,
+        refreshBalance,
+    ]);
+
     const dropBall = useCallback(
         async (
             difficulty: Difficulty,
@@ -96,52 +195,19 @@ export const usePlinkoGame = (
             }
 
             try {
-                const gameResults = await playGame(
+                const placed = await playGame(
                     difficulty,
                     riskLevel,
                     betAmount,
                     numberOfBalls
                 );
 
-                if (gameResults && gameResults.length > 0) {
-                    setPendingResults((prev) => [...prev, ...gameResults]);
-
-                    const firstRowPegs = 3;
-                    const firstRowWidth = (firstRowPegs - 1) * SPACING;
-                    const firstRowStartX = (CANVAS_WIDTH - firstRowWidth) / 2;
-                    const centerPegIndex = Math.floor(firstRowPegs / 2);
-                    const startX = firstRowStartX + centerPegIndex * SPACING;
-
-                    // This variable will track the total delay for the next ball.
-                    let cumulativeDelay = 0;
-
-                    // Loop through results and add each ball to the state with a random delay
-                    gameResults.forEach((result) => {
-                        // Schedule the creation of the current ball using the current cumulative delay.
-                        // For the first ball, this will be 0, so it drops instantly.
-                        const timeoutId = setTimeout(() => {
-                            const newBall: Ball = {
-                                id: result.ballId,
-                                path: result.path,
-                                x: startX,
-                                y: 40,
-                                vx: 0,
-                                vy: 0,
-                                currentRow: -1,
-                                pegIndex: centerPegIndex,
-                                eventIndex: result.eventIndex,
-                            };
-                            setBalls((prev) => [...prev, newBall]);
-                        }, cumulativeDelay);
-
-                        pendingDropTimeoutsRef.current.push(timeoutId);
-
-                        // Then, calculate a random interval for the *next* ball and add it to the total.
-                        const randomInterval =
-                            MIN_DELAY_MS +
-                            Math.random() * (MAX_DELAY_MS - MIN_DELAY_MS);
-                        cumulativeDelay += randomInterval;
-                    });
+                // The house balance moved, and the largest bet with it
+                fetchBetLimits(difficulty, riskLevel);
+                refreshBalance();
+
+                if (placed && placed.length > 0) {
+                    setPendingGames((prev) => [...prev, ...placed]);
                 }
             } catch (err: any) {
                 console.error("Drop ball failed:", err);
@@ -149,7 +215,7 @@ export const usePlinkoGame = (
                 throw err;
             }
         },
-        [playGame, contractsValid]
+        [playGame, contractsValid, fetchBetLimits, refreshBalance]
     );
 
     const handlePurchasePlink = useCallback(
@@ -168,10 +234,13 @@ export const usePlinkoGame = (
     return {
         balls,
         gameHistory,
+        pendingGames,
         plinkBalance,
         isLoading,
         error,
         contractsValid,
+        betLimits,
+        payoutTables,
         dropBall,
         purchasePlink: handlePurchasePlink,
         refreshBalance,
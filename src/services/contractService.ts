@@ -6,8 +6,18 @@ import {
 import { BigNumberInBase } from "@injectivelabs/utils";
 import { WalletStrategy } from "@injectivelabs/wallet-strategy";
 import { Network, getNetworkEndpoints } from "@injectivelabs/networks";
-import { CONTRACTS, NETWORK, TOKEN_DENOM } from "../config/contracts";
-import { Difficulty, LeaderboardType, RiskLevel } from "../types/game";
+import {
+    CONTRACTS,
+    NETWORK,
+    REST_URL,
+    TOKEN_DENOM,
+} from "../config/contracts";
+import {
+    Difficulty,
+    LeaderboardType,
+    PayoutTable,
+    RiskLevel,
+} from "../types/game";
 import { MsgBroadcaster } from "@injectivelabs/wallet-core";
 
 const network = NETWORK === "mainnet" ? Network.Mainnet : Network.Testnet;
@@ -146,7 +156,8 @@ export class ContractService {
     }
 
     /**
-     * Play the Plinko game
+     * Play the Plinko game. The balls are placed against the active server seed
+     * commitment and only land once the operator reveals that seed.
      */
     async playGame(
         difficulty: Difficulty,
@@ -157,42 +168,55 @@ export class ContractService {
     ): Promise<any> {
         const msgBroadcaster = await this.createMsgBroadcaster();
 
-        // Amount for a single play in base units
-        const singleBetBaseAmount = new BigNumberInBase(betAmount)
+        // The contract splits the funds evenly between the balls
+        const totalBetBaseAmount = new BigNumberInBase(betAmount)
             .toWei(18) // Convert to 18 decimals
+            .times(numberOfPlays)
             .toFixed(0);
 
-        const messages: MsgExecuteContractCompat[] = [];
-
-        // Create a 'play' message for each ball drop
-        for (let i = 0; i < numberOfPlays; i++) {
-            const playMsg = MsgExecuteContractCompat.fromJSON({
-                contractAddress: CONTRACTS.game,
-                sender: userAddress,
-                msg: {
-                    play: {
-                        difficulty: this.mapDifficulty(difficulty),
-                        risk_level: this.mapRiskLevel(riskLevel),
-                    },
+        const playMsg = MsgExecuteContractCompat.fromJSON({
+            contractAddress: CONTRACTS.game,
+            sender: userAddress,
+            msg: {
+                play: {
+                    difficulty: this.mapDifficulty(difficulty),
+                    risk_level: this.mapRiskLevel(riskLevel),
+                    ball_count: numberOfPlays,
                 },
-                // Attach the native tokens for this single play
-                funds: {
-                    denom: TOKEN_DENOM,
-                    amount: singleBetBaseAmount,
-                },
-            });
-            messages.push(playMsg);
-        }
+            },
+            funds: {
+                denom: TOKEN_DENOM,
+                amount: totalBetBaseAmount,
+            },
+        });
 
-        // Broadcast the single transaction with all play messages
         const result = await msgBroadcaster.broadcast({
-            msgs: messages,
+            msgs: playMsg,
             injectiveAddress: userAddress,
         });
 
         return result;
     }
 
+    /**
+     * Search the transactions that settled a player's games, newest first
+     */
+    async getSettleTxs(userAddress: string, limit: number = 20): Promise<any[]> {
+        try {
+            const query = encodeURIComponent(
+                `wasm-settle_game.player='${userAddress}'`
+            );
+            const response = await fetch(
+                `${REST_URL}/cosmos/tx/v1beta1/txs?query=${query}&order_by=ORDER_BY_DESC&pagination.limit=${limit}`
+            );
+            const decoded = await response.json();
+            return decoded.tx_responses || [];
+        } catch (error) {
+            console.error("Error fetching settle transactions:", error);
+            return [];
+        }
+    }
+
     /**
      * [NEW] Query statistics for a specific user
      */
@@ -280,6 +304,69 @@ export class ContractService {
         }
     }
 
+    /**
+     * Query the smallest and largest bet a board accepts right now
+     */
+    async getBetLimits(difficulty: Difficulty, riskLevel: RiskLevel): Promise<any> {
+        try {
+            const queryMsg = {
+                bet_limits: {
+                    difficulty: this.mapDifficulty(difficulty),
+                    risk_level: this.mapRiskLevel(riskLevel),
+                },
+            };
+
+            const query = btoa(JSON.stringify(queryMsg));
+            const info = await this.client.fetchSmartContractState(
+                CONTRACTS.game,
+                query
+            );
+            const decoded = JSON.parse(new TextDecoder().decode(info.data));
+            return decoded;
+        } catch (error) {
+            console.error("Error fetching bet limits:", error);
+            return null;
+        }
+    }
+
+    /**
+     * Query the payout tables of every board in play
+     */
+    async getPayoutTables(): Promise<PayoutTable[]> {
+        try {
+            const query = btoa(JSON.stringify({ payout_tables: {} }));
+            const info = await this.client.fetchSmartContractState(
+                CONTRACTS.game,
+                query
+            );
+            const decoded = JSON.parse(new TextDecoder().decode(info.data));
+            return decoded.tables || [];
+        } catch (error) {
+            console.error("Error fetching payout tables:", error);
+            return [];
+        }
+    }
+
+    /**
+     * Query a payout table as it was put in place, by the table_id games record
+     */
+    async getPayoutTableVersion(tableId: number): Promise<string[] | null> {
+        try {
+            const query = btoa(
+                JSON.stringify({ payout_table_version: { table_id: tableId } })
+            );
+            const info = await this.client.fetchSmartContractState(
+                CONTRACTS.game,
+                query
+            );
+            const decoded = JSON.parse(new TextDecoder().decode(info.data));
+            return decoded.multipliers;
+        } catch (error) {
+            console.error("Error fetching payout table version:", error);
+            return null;
+        }
+    }
+
     // Helper methods to map frontend types to contract types
     private mapDifficulty(difficulty: Difficulty): string {
         const map: Record<Difficulty, string> = {
@@ -7,8 +7,12 @@ export interface GameSettings {
     betAmount: string;
 }
 
-export interface MultiplierConfig {
-    [key: string]: number[];
+// A board's payout table as the contract returns it, multipliers as decimal strings
+export interface PayoutTable {
+    rows: number;
+    risk_level: RiskLevel;
+    multipliers: string[];
+    rtp: string;
 }
 
 export interface Ball {
@@ -30,6 +34,21 @@ export interface GameResult {
     multiplier: string;
     winAmount: string;
     timestamp: number;
+    verified?: boolean; // Replayed locally, undefined until a replay was possible
+}
+
+// A ball placed against a server seed commitment, waiting for the seed to be revealed
+export interface PendingGame {
+    commitmentId: number;
+    nonce: number;
+    betAmount: string;
+    placedAt: number;
+}
+
+// Smallest and largest bet a board accepts right now, in $PLINK
+export interface BetLimits {
+    minBet: string;
+    maxBet: string;
 }
 
 export type LeaderboardType = "bestWins" | "totalWagered";
@@ -0,0 +1,42 @@
+import { derive_seed, play } from "@plinko/plinko-fairness-wasm";
+
+/**
+ * Replays a game from the History query with the contract's own RNG and payout code.
+ * @param game The GameRecord as the contract returns it.
+ * @param multipliers The table version the game was paid on (PayoutTableVersion).
+ * @returns Whether the replay lands where the record says, or undefined for games
+ * recorded before their seed inputs were stored.
+ */
+export const verifyGame = (
+    game: any,
+    multipliers: string[]
+): boolean | undefined => {
+    if (!game.seed_inputs || !game.rows || game.table_id == null) {
+        return undefined;
+    }
+
+    try {
+        const seed = derive_seed(
+            JSON.stringify(game.seed_inputs),
+            game.player,
+            BigInt(game.nonce)
+        );
+        const outcome = play(
+            seed,
+            game.rows,
+            game.rng_version,
+            multipliers,
+            game.bet_amount
+        );
+        const path = game.path.map((right: boolean) => (right ? 1 : 0));
+
+        return (
+            outcome.path.join("") === path.join("") &&
+            outcome.multiplier === game.multiplier &&
+            outcome.win_amount === game.win_amount
+        );
+    } catch (error) {
+        console.error("Failed to replay game:", error);
+        return false;
+    }
+};